@@ -1,28 +1,65 @@
+#![feature(coroutines)]
+
 use async_trait::async_trait;
+use bytes::{BufMut, BytesMut};
+use chrono::{Datelike, NaiveDate};
 use clap::Parser;
-use fnck_sql::db::{DBTransaction, DataBaseBuilder, Database};
+use fnck_sql::catalog::{UserCatalog, UserPassword, SCRAM_ITERATIONS};
+use fnck_sql::db::{DBTransaction, DataBaseBuilder, Database, PreparedStatement};
 use fnck_sql::errors::DatabaseError;
+use fnck_sql::execution::volcano::BoxedExecutor;
+use fnck_sql::parser::parse_sql;
 use fnck_sql::storage::kip::KipStorage;
-use fnck_sql::types::tuple::Tuple;
+use fnck_sql::storage::{Storage, Transaction};
+use fnck_sql::types::tuple::{SchemaRef, Tuple};
+use fnck_sql::types::tuple_builder::AFFECTED_ROWS;
+use fnck_sql::types::value::DataValue;
 use fnck_sql::types::LogicalType;
-use futures::stream;
+use futures::{stream, Sink, SinkExt, StreamExt, TryStreamExt};
+use futures_async_stream::try_stream;
 use log::{error, info, LevelFilter};
 use pgwire::api::auth::noop::NoopStartupHandler;
-use pgwire::api::auth::StartupHandler;
+use pgwire::api::auth::scram::MakeSASLScramAuthStartupHandler;
+use pgwire::api::auth::{
+    AuthSource, DefaultServerParameterProvider, LoginInfo, Password, StartupHandler,
+};
+use pgwire::api::portal::{Format, Portal};
 use pgwire::api::query::{
-    ExtendedQueryHandler, PlaceholderExtendedQueryHandler, SimpleQueryHandler,
+    send_execution_response, send_query_response, ExtendedQueryHandler, SimpleQueryHandler,
+    StatementOrPortal,
+};
+use pgwire::api::results::{
+    DataRowEncoder, DescribeResponse, FieldInfo, QueryResponse, Response, Tag,
 };
-use pgwire::api::results::{DataRowEncoder, FieldFormat, FieldInfo, QueryResponse, Response, Tag};
+use pgwire::api::stmt::{NoopQueryParser, StoredStatement};
+use pgwire::api::store::PortalStore;
 use pgwire::api::MakeHandler;
-use pgwire::api::{ClientInfo, StatelessMakeHandler, Type};
+use pgwire::api::{
+    ClientInfo, ClientPortalStore, PgWireConnectionState, StatelessMakeHandler, Type, DEFAULT_NAME,
+    METADATA_USER,
+};
 use pgwire::error::{ErrorInfo, PgWireError, PgWireResult};
+use pgwire::messages::data::DataRow;
+use pgwire::messages::extendedquery::{
+    Close, CloseComplete, TARGET_TYPE_BYTE_PORTAL, TARGET_TYPE_BYTE_STATEMENT,
+};
+use pgwire::messages::response::{EmptyQueryResponse, ReadyForQuery, READY_STATUS_IDLE};
+use pgwire::messages::simplequery::Query;
+use pgwire::messages::PgWireBackendMessage;
 use pgwire::tokio::process_socket;
+use pgwire::types::ToSqlText;
+use postgres_types::{to_sql_checked, IsNull, ToSql};
+use rust_decimal::Decimal;
+use sqlparser::ast::{ObjectType, Statement};
+use std::collections::HashMap;
+use std::error::Error;
 use std::fmt::Debug;
 use std::io;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::net::TcpListener;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OwnedMutexGuard};
 
 pub(crate) const BANNER: &str = "
 ███████╗███╗   ██╗ ██████╗██╗  ██╗    ███████╗ ██████╗ ██╗
@@ -55,15 +92,89 @@ struct Args {
     port: u16,
     #[clap(long, default_value = "./fncksql_data")]
     path: String,
+    /// Authenticate the users created by `CREATE USER` with SCRAM-SHA-256
+    #[clap(long, default_value = "false")]
+    require_auth: bool,
+    /// The administrator, who is created when the server starts if it does not exist
+    #[clap(long, default_value = "postgres")]
+    superuser: String,
+    /// The password of the administrator, which is required to log in with `--require-auth`
+    #[clap(long)]
+    superuser_password: Option<String>,
 }
 
+/// The prepared statements cached by each session, beyond which one of them is evicted
+const MAX_PREPARED_STATEMENTS: usize = 256;
+
 pub struct FnckSQLBackend {
     inner: Arc<Database<KipStorage>>,
+    require_auth: bool,
+    schema_version: Arc<AtomicUsize>,
 }
 
 pub struct SessionBackend {
     inner: Arc<Database<KipStorage>>,
-    tx: Mutex<Option<DBTransaction<KipStorage>>>,
+    require_auth: bool,
+    // the streams of the statements run in the transaction hold its lock until they are dropped
+    tx: Arc<Mutex<Option<DBTransaction<KipStorage>>>>,
+
+    query_parser: Arc<NoopQueryParser>,
+    // increased by every session whenever the tables may have been changed,
+    // so that the plans prepared before are bound again
+    schema_version: Arc<AtomicUsize>,
+    // statements prepared in this session, keyed by the name of the statement
+    prepared_statements: Mutex<HashMap<String, CachedStatement>>,
+}
+
+struct CachedStatement {
+    sql: String,
+    schema_version: usize,
+    statement: Arc<PreparedStatement>,
+}
+
+enum TransactionControl {
+    Begin,
+    Commit,
+    Rollback,
+}
+
+impl TransactionControl {
+    fn parse(query: &str) -> Option<Self> {
+        match query.trim().to_uppercase().as_str() {
+            "BEGIN;" | "BEGIN" | "BEGIN TRANSACTION;" | "BEGIN TRANSACTION"
+            | "START TRANSACTION;" | "START TRANSACTION" => Some(TransactionControl::Begin),
+            "COMMIT;" | "COMMIT" | "COMMIT WORK;" | "COMMIT WORK" => {
+                Some(TransactionControl::Commit)
+            }
+            "ROLLBACK;" | "ROLLBACK" => Some(TransactionControl::Rollback),
+            _ => None,
+        }
+    }
+}
+
+/// The salted passwords of SCRAM-SHA-256 are read from the users catalog
+pub struct FnckSQLAuthSource {
+    inner: Arc<Database<KipStorage>>,
+}
+
+#[async_trait]
+impl AuthSource for FnckSQLAuthSource {
+    async fn get_password(&self, login: &LoginInfo) -> PgWireResult<Password> {
+        let user_name = login.user().ok_or(PgWireError::UserNameRequired)?;
+        let transaction = self
+            .inner
+            .storage
+            .transaction()
+            .await
+            .map_err(into_pg_error)?;
+        let password = transaction
+            .user(user_name)
+            .map_err(into_pg_error)?
+            .and_then(|user| user.password)
+            .ok_or_else(|| into_pg_error(DatabaseError::InvalidPassword(user_name.to_string())))?;
+
+        Ok(Password::new(Some(password.salt), password.salted_password))
+    }
 }
 
 impl MakeHandler for FnckSQLBackend {
@@ -72,145 +183,713 @@ impl MakeHandler for FnckSQLBackend {
     fn make(&self) -> Self::Handler {
         Arc::new(SessionBackend {
             inner: Arc::clone(&self.inner),
-            tx: Mutex::new(None),
+            require_auth: self.require_auth,
+            tx: Arc::new(Mutex::new(None)),
+            query_parser: Arc::new(NoopQueryParser::new()),
+            schema_version: Arc::clone(&self.schema_version),
+            prepared_statements: Mutex::new(HashMap::new()),
         })
     }
 }
 
 impl FnckSQLBackend {
-    pub async fn new(path: impl Into<PathBuf> + Send) -> Result<FnckSQLBackend, DatabaseError> {
+    pub async fn new(
+        path: impl Into<PathBuf> + Send,
+        require_auth: bool,
+        superuser: String,
+        superuser_password: Option<String>,
+    ) -> Result<FnckSQLBackend, DatabaseError> {
         let database = DataBaseBuilder::path(path).build().await?;
+        Self::bootstrap_superuser(&database, superuser, superuser_password).await?;
 
         Ok(FnckSQLBackend {
             inner: Arc::new(database),
+            require_auth,
+            schema_version: Arc::new(AtomicUsize::new(0)),
         })
     }
+
+    /// The administrator is created, or its password is reset if it is given
+    async fn bootstrap_superuser(
+        database: &Database<KipStorage>,
+        name: String,
+        password: Option<String>,
+    ) -> Result<(), DatabaseError> {
+        let mut transaction = database.storage.transaction().await?;
+        let password = password.map(|password| UserPassword::new(&password));
+
+        match transaction.user(&name)? {
+            Some(mut user) => {
+                user.is_admin = true;
+                if password.is_some() {
+                    user.password = password;
+                }
+                transaction.alter_user(user)?;
+            }
+            None => transaction.create_user(UserCatalog::new(name, password, true), false)?,
+        }
+        transaction.commit().await
+    }
 }
 
 #[async_trait]
 impl SimpleQueryHandler for SessionBackend {
+    /// Every statement is run once the response of the previous one has been sent,
+    /// so that the rows of each statement are streamed in the order of the statements.
+    async fn on_query<C>(&self, client: &mut C, query: Query) -> PgWireResult<()>
+    where
+        C: ClientInfo + Sink<PgWireBackendMessage> + Unpin + Send + Sync,
+        C::Error: Debug,
+        PgWireError: From<<C as Sink<PgWireBackendMessage>>::Error>,
+    {
+        client.set_state(PgWireConnectionState::QueryInProgress);
+        let statements = parse_sql(&query.query).map_err(|e| into_pg_error(e.into()))?;
+
+        if statements.is_empty() {
+            client
+                .feed(PgWireBackendMessage::EmptyQueryResponse(EmptyQueryResponse))
+                .await?;
+        }
+        for statement in statements {
+            match self.statement_response(client, &statement).await? {
+                Response::Query(results) => send_query_response(client, results, true).await?,
+                Response::Execution(tag) => send_execution_response(client, tag).await?,
+                Response::EmptyQuery => {
+                    client
+                        .feed(PgWireBackendMessage::EmptyQueryResponse(EmptyQueryResponse))
+                        .await?
+                }
+                Response::Error(e) => {
+                    client
+                        .feed(PgWireBackendMessage::ErrorResponse((*e).into()))
+                        .await?
+                }
+            }
+        }
+        client
+            .feed(PgWireBackendMessage::ReadyForQuery(ReadyForQuery::new(
+                READY_STATUS_IDLE,
+            )))
+            .await?;
+        client.flush().await?;
+        client.set_state(PgWireConnectionState::ReadyForQuery);
+
+        Ok(())
+    }
+
+    /// Tips: `on_query` does not call it. The rows of the statements except the last one are collected,
+    /// because the responses are only sent after all the statements have been run.
     async fn do_query<'a, 'b: 'a, C>(
         &'b self,
-        _client: &mut C,
+        client: &mut C,
         query: &'a str,
     ) -> PgWireResult<Vec<Response<'a>>>
     where
         C: ClientInfo + Unpin + Send + Sync,
     {
-        match query.to_uppercase().as_str() {
-            "BEGIN;" | "BEGIN" | "START TRANSACTION;" | "START TRANSACTION" => {
-                let mut guard = self.tx.lock().await;
+        let statements = parse_sql(query).map_err(|e| into_pg_error(e.into()))?;
+
+        if statements.is_empty() {
+            return Ok(vec![Response::EmptyQuery]);
+        }
+        let mut responses = Vec::with_capacity(statements.len());
+
+        for (i, statement) in statements.iter().enumerate() {
+            let response = match self.statement_response(client, statement).await? {
+                Response::Query(results) if i + 1 < statements.len() => {
+                    let schema = results.row_schema();
+                    let rows = results.data_rows().collect::<Vec<_>>().await;
+
+                    Response::Query(QueryResponse::new(schema, stream::iter(rows)))
+                }
+                response => response,
+            };
+            responses.push(response);
+        }
+
+        Ok(responses)
+    }
+}
+
+#[async_trait]
+impl ExtendedQueryHandler for SessionBackend {
+    type Statement = String;
+    type QueryParser = NoopQueryParser;
+
+    fn query_parser(&self) -> Arc<Self::QueryParser> {
+        self.query_parser.clone()
+    }
+
+    /// The plan of the closed statement is discarded as well
+    async fn on_close<C>(&self, client: &mut C, message: Close) -> PgWireResult<()>
+    where
+        C: ClientInfo + ClientPortalStore + Sink<PgWireBackendMessage> + Unpin + Send + Sync,
+        C::PortalStore: PortalStore<Statement = Self::Statement>,
+        C::Error: Debug,
+        PgWireError: From<<C as Sink<PgWireBackendMessage>>::Error>,
+    {
+        let name = message.name.as_deref().unwrap_or(DEFAULT_NAME);
+
+        match message.target_type {
+            TARGET_TYPE_BYTE_STATEMENT => {
+                client.portal_store().rm_statement(name);
+                self.prepared_statements.lock().await.remove(name);
+            }
+            TARGET_TYPE_BYTE_PORTAL => client.portal_store().rm_portal(name),
+            _ => (),
+        }
+        client
+            .send(PgWireBackendMessage::CloseComplete(CloseComplete))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn do_query<'a, 'b: 'a, C>(
+        &'b self,
+        client: &mut C,
+        portal: &'a Portal<Self::Statement>,
+        max_rows: usize,
+    ) -> PgWireResult<Response<'a>>
+    where
+        C: ClientInfo + Unpin + Send + Sync,
+    {
+        // Tips: pgwire cannot suspend the portal, so its rows cannot be fetched in batches
+        if max_rows > 0 {
+            return Err(into_pg_error(DatabaseError::UnsupportedStmt(
+                "the row limit of the portal".to_string(),
+            )));
+        }
+        let query = &portal.statement.statement;
+
+        if let Some(control) = TransactionControl::parse(query) {
+            return self.control_transaction(client, control).await;
+        }
+        let statement = self.prepare(client, &portal.statement).await?;
+        let args = (0..portal.parameter_len())
+            .map(|i| {
+                let pg_type = parameter_type(&portal.statement.parameter_types, &statement, i)?;
+
+                decode_parameter(portal, i, &pg_type)
+            })
+            .collect::<PgWireResult<Vec<DataValue>>>()?;
+        let guard = self.tx.clone().lock_owned().await;
+
+        let tuples = if guard.is_some() {
+            stream_prepared_in_transaction(guard, statement.clone(), args)
+        } else {
+            drop(guard);
+            self.database(client)
+                .stream_prepared(&statement, &args)
+                .await
+                .map_err(into_pg_error)?
+        };
+
+        let response =
+            into_response(statement.statement(), tuples, &portal.result_column_format).await?;
+        self.invalidate_if_ddl(statement.statement());
+
+        Ok(response)
+    }
+
+    async fn do_describe<C>(
+        &self,
+        client: &mut C,
+        target: StatementOrPortal<'_, Self::Statement>,
+    ) -> PgWireResult<DescribeResponse>
+    where
+        C: ClientInfo + Unpin + Send + Sync,
+    {
+        match target {
+            StatementOrPortal::Statement(stored) => {
+                if TransactionControl::parse(&stored.statement).is_some() {
+                    return Ok(DescribeResponse::no_data());
+                }
+                let statement = self.prepare(client, stored).await?;
+                let param_types = (0..statement.parameter_types.len())
+                    .map(|i| parameter_type(&stored.parameter_types, &statement, i))
+                    .collect::<PgWireResult<Vec<Type>>>()?;
+                let fields = into_field_infos(&statement.schema_ref, &Format::UnifiedText)?;
+
+                Ok(DescribeResponse::new(Some(param_types), fields))
+            }
+            StatementOrPortal::Portal(portal) => {
+                if TransactionControl::parse(&portal.statement.statement).is_some() {
+                    return Ok(DescribeResponse::no_data());
+                }
+                let statement = self.prepare(client, &portal.statement).await?;
+                let fields = into_field_infos(&statement.schema_ref, &portal.result_column_format)?;
+
+                Ok(DescribeResponse::new(None, fields))
+            }
+        }
+    }
+}
+
+impl SessionBackend {
+    /// The SQL of an authenticated session is run as the login user,
+    /// otherwise as the administrator, since the login user cannot be trusted.
+    fn database<C: ClientInfo>(&self, client: &C) -> Database<KipStorage> {
+        let user = self.require_auth.then(|| {
+            client
+                .metadata()
+                .get(METADATA_USER)
+                .cloned()
+                .unwrap_or_default()
+        });
+
+        self.inner.with_user(user)
+    }
+
+    /// The response of the statement, whose rows are encoded as they are pulled from its stream.
+    async fn statement_response<C: ClientInfo + Sync>(
+        &self,
+        client: &C,
+        statement: &Statement,
+    ) -> PgWireResult<Response<'static>> {
+        let sql = statement.to_string();
+
+        if let Some(control) = TransactionControl::parse(&sql) {
+            return self.control_transaction(client, control).await;
+        }
+        let guard = self.tx.clone().lock_owned().await;
+
+        let tuples = if guard.is_some() {
+            stream_in_transaction(guard, sql)
+        } else {
+            drop(guard);
+            self.database(client)
+                .stream(&sql)
+                .await
+                .map_err(into_pg_error)?
+        };
+
+        let response = into_response(statement, tuples, &Format::UnifiedText).await?;
+        self.invalidate_if_ddl(statement);
+
+        Ok(response)
+    }
+
+    /// The cached plans of all the sessions are bound again after the tables are changed.
+    fn invalidate_if_ddl(&self, statement: &Statement) {
+        if matches!(
+            statement,
+            Statement::CreateTable { .. }
+                | Statement::CreateIndex { .. }
+                | Statement::AlterTable { .. }
+                | Statement::Drop { .. }
+        ) {
+            self.schema_version.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    async fn control_transaction<'a, C: ClientInfo + Sync>(
+        &self,
+        client: &C,
+        control: TransactionControl,
+    ) -> PgWireResult<Response<'a>> {
+        let mut guard = self.tx.lock().await;
 
+        match control {
+            TransactionControl::Begin => {
                 if guard.is_some() {
-                    return Err(PgWireError::ApiError(Box::new(
-                        DatabaseError::TransactionAlreadyExists,
-                    )));
+                    return Err(into_pg_error(DatabaseError::TransactionAlreadyExists));
                 }
                 let transaction = self
-                    .inner
+                    .database(client)
                     .new_transaction()
                     .await
-                    .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
+                    .map_err(into_pg_error)?;
                 guard.replace(transaction);
-
-                Ok(vec![Response::Execution(Tag::new("OK"))])
-            }
-            "COMMIT;" | "COMMIT" | "COMMIT WORK;" | "COMMIT WORK" => {
-                let mut guard = self.tx.lock().await;
-
-                if let Some(transaction) = guard.take() {
-                    transaction
-                        .commit()
-                        .await
-                        .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
-
-                    Ok(vec![Response::Execution(Tag::new("OK"))])
-                } else {
-                    Err(PgWireError::ApiError(Box::new(
-                        DatabaseError::NoTransactionBegin,
-                    )))
-                }
             }
-            "ROLLBACK;" | "ROLLBACK" => {
-                let mut guard = self.tx.lock().await;
+            TransactionControl::Commit => {
+                let transaction = guard
+                    .take()
+                    .ok_or_else(|| into_pg_error(DatabaseError::NoTransactionBegin))?;
 
+                transaction.commit().await.map_err(into_pg_error)?;
+            }
+            TransactionControl::Rollback => {
                 if guard.is_none() {
-                    return Err(PgWireError::ApiError(Box::new(
-                        DatabaseError::NoTransactionBegin,
-                    )));
+                    return Err(into_pg_error(DatabaseError::NoTransactionBegin));
                 }
                 drop(guard.take());
+            }
+        }
+        // Tips: the tables changed in the transaction are seen by the other sessions after it is committed,
+        // and the plans bound on them in the transaction are stale after it is rolled back
+        if !matches!(control, TransactionControl::Begin) {
+            self.schema_version.fetch_add(1, Ordering::SeqCst);
+        }
+
+        Ok(Response::Execution(Tag::new("OK")))
+    }
 
-                Ok(vec![Response::Execution(Tag::new("OK"))])
+    /// The statement is parsed and bound only once per session until the tables are changed,
+    /// and then executed with the arguments of each portal.
+    async fn prepare<C: ClientInfo + Sync>(
+        &self,
+        client: &C,
+        stored: &StoredStatement<String>,
+    ) -> PgWireResult<Arc<PreparedStatement>> {
+        let query = &stored.statement;
+        let schema_version = self.schema_version.load(Ordering::SeqCst);
+        let mut prepared_statements = self.prepared_statements.lock().await;
+
+        if let Some(cached) = prepared_statements.get(&stored.id) {
+            if cached.sql == *query && cached.schema_version == schema_version {
+                return Ok(cached.statement.clone());
             }
-            _ => {
-                let mut guard = self.tx.lock().await;
+        }
+        let statement = {
+            let guard = self.tx.lock().await;
 
-                let tuples = if let Some(transaction) = guard.as_mut() {
-                    transaction.run(query).await
-                } else {
-                    self.inner.run(query).await
-                }
-                .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
+            if let Some(transaction) = guard.as_ref() {
+                transaction.prepare(query)
+            } else {
+                self.database(client).prepare(query).await
+            }
+            .map_err(into_pg_error)?
+        };
+        let statement = Arc::new(statement);
 
-                Ok(vec![Response::Query(encode_tuples(tuples)?)])
+        if prepared_statements.len() >= MAX_PREPARED_STATEMENTS
+            && !prepared_statements.contains_key(&stored.id)
+        {
+            if let Some(name) = prepared_statements.keys().next().cloned() {
+                prepared_statements.remove(&name);
             }
         }
+        prepared_statements.insert(
+            stored.id.clone(),
+            CachedStatement {
+                sql: query.clone(),
+                schema_version,
+                statement: statement.clone(),
+            },
+        );
+
+        Ok(statement)
     }
 }
 
-fn encode_tuples<'a>(tuples: Vec<Tuple>) -> PgWireResult<QueryResponse<'a>> {
-    if tuples.is_empty() {
-        return Ok(QueryResponse::new(Arc::new(vec![]), stream::empty()));
+/// The tuples of the SQL run in the transaction of the session,
+/// whose lock is held until the stream is dropped.
+#[try_stream(boxed, ok = Tuple, error = DatabaseError)]
+async fn stream_in_transaction(
+    mut guard: OwnedMutexGuard<Option<DBTransaction<KipStorage>>>,
+    sql: String,
+) {
+    let transaction = guard.as_mut().ok_or(DatabaseError::NoTransactionBegin)?;
+    let mut tuples = transaction.stream(&sql)?;
+
+    while let Some(tuple) = tuples.try_next().await? {
+        yield tuple;
     }
+}
 
-    let mut results = Vec::with_capacity(tuples.len());
-    let schema = Arc::new(
-        tuples[0]
-            .schema_ref
-            .iter()
-            .map(|column| {
-                let pg_type = into_pg_type(column.datatype())?;
-
-                Ok(FieldInfo::new(
-                    column.name().into(),
-                    None,
-                    None,
-                    pg_type,
-                    FieldFormat::Text,
-                ))
-            })
-            .collect::<PgWireResult<Vec<FieldInfo>>>()?,
-    );
+/// The streaming version of `DBTransaction::execute` that holds the lock of the transaction.
+#[try_stream(boxed, ok = Tuple, error = DatabaseError)]
+async fn stream_prepared_in_transaction(
+    mut guard: OwnedMutexGuard<Option<DBTransaction<KipStorage>>>,
+    statement: Arc<PreparedStatement>,
+    args: Vec<DataValue>,
+) {
+    let transaction = guard.as_mut().ok_or(DatabaseError::NoTransactionBegin)?;
+    let mut tuples = transaction.stream_prepared(&statement, &args)?;
+
+    while let Some(tuple) = tuples.try_next().await? {
+        yield tuple;
+    }
+}
+
+fn into_pg_error(e: DatabaseError) -> PgWireError {
+    PgWireError::UserError(Box::new(ErrorInfo::new(
+        "ERROR".to_owned(),
+        sql_state(&e).to_owned(),
+        e.to_string(),
+    )))
+}
+
+/// The SQLSTATE of PostgreSQL, so that clients can tell the errors apart
+fn sql_state(e: &DatabaseError) -> &'static str {
+    match e {
+        // integrity constraint violation
+        DatabaseError::DuplicatePrimaryKey | DatabaseError::DuplicateUniqueValue => "23505",
+        DatabaseError::NotNull | DatabaseError::NeedNullAbleOrDefault => "23502",
+        // syntax error or access rule violation
+        DatabaseError::EmptyStatement
+        | DatabaseError::ParserSql(_)
+        | DatabaseError::ValuesLenMismatch(..)
+        | DatabaseError::LengthMismatch { .. } => "42601",
+        DatabaseError::PermissionDenied(_) => "42501",
+        DatabaseError::TableNotFound => "42P01",
+        DatabaseError::TableExists => "42P07",
+        DatabaseError::DuplicateColumn | DatabaseError::Duplicated("column", _) => "42701",
+        DatabaseError::Duplicated("index", _) => "42P07",
+        DatabaseError::Duplicated(..) => "42710",
+        DatabaseError::InvalidColumn(_) | DatabaseError::NotFound("column", _) => "42703",
+        DatabaseError::NotFound(..) => "42704",
+        DatabaseError::AmbiguousColumn(_) => "42702",
+        DatabaseError::InvalidPlaceholder(_) => "42P02",
+        DatabaseError::AggMiss(_) => "42803",
+        DatabaseError::BinaryOpTypeMismatch(..)
+        | DatabaseError::Incomparable(..)
+        | DatabaseError::MisMatch(..) => "42804",
+        // feature not supported
+        DatabaseError::UnsupportedStmt(_)
+        | DatabaseError::UnsupportedBinaryOperator(..)
+        | DatabaseError::UnsupportedCopySource(_)
+        | DatabaseError::NotImplementedSqlparserDataType(_) => "0A000",
+        // data exception
+        DatabaseError::CastFail
+        | DatabaseError::ParseInt(_)
+        | DatabaseError::ParseFloat(_)
+        | DatabaseError::ParseBool(_)
+        | DatabaseError::ParseDate(_) => "22P02",
+        DatabaseError::TryFromInt(_) | DatabaseError::TryFromDecimal(_) => "22003",
+        DatabaseError::TooLong => "22001",
+        DatabaseError::InvalidArgument(..) => "22023",
+        // invalid authorization specification
+        DatabaseError::InvalidPassword(_) => "28P01",
+        // invalid transaction state
+        DatabaseError::TransactionAlreadyExists => "25001",
+        DatabaseError::NoTransactionBegin => "25P01",
+        _ => "XX000",
+    }
+}
+
+/// The type declared by the client takes precedence over the type inferred from the statement
+fn parameter_type(
+    declared_types: &[Type],
+    statement: &PreparedStatement,
+    i: usize,
+) -> PgWireResult<Type> {
+    match declared_types.get(i) {
+        Some(pg_type) if *pg_type != Type::UNKNOWN => Ok(pg_type.clone()),
+        _ => statement
+            .parameter_types
+            .get(i)
+            .map(into_pg_type)
+            .unwrap_or(Ok(Type::UNKNOWN)),
+    }
+}
+
+fn decode_parameter(portal: &Portal<String>, i: usize, pg_type: &Type) -> PgWireResult<DataValue> {
+    Ok(if *pg_type == Type::BOOL {
+        DataValue::Boolean(portal.parameter::<bool>(i, pg_type)?)
+    } else if *pg_type == Type::CHAR {
+        DataValue::Int8(portal.parameter::<i8>(i, pg_type)?)
+    } else if *pg_type == Type::INT2 {
+        DataValue::Int16(portal.parameter::<i16>(i, pg_type)?)
+    } else if *pg_type == Type::INT4 {
+        DataValue::Int32(portal.parameter::<i32>(i, pg_type)?)
+    } else if *pg_type == Type::INT8 {
+        DataValue::Int64(portal.parameter::<i64>(i, pg_type)?)
+    } else if *pg_type == Type::FLOAT4 {
+        DataValue::Float32(portal.parameter::<f32>(i, pg_type)?)
+    } else if *pg_type == Type::FLOAT8 {
+        DataValue::Float64(portal.parameter::<f64>(i, pg_type)?)
+    } else if *pg_type == Type::NUMERIC {
+        DataValue::Decimal(portal.parameter::<Decimal>(i, pg_type)?)
+    } else if *pg_type == Type::DATE {
+        DataValue::Date32(
+            portal
+                .parameter::<NaiveDate>(i, pg_type)?
+                .map(|date| date.num_days_from_ce()),
+        )
+    } else if *pg_type == Type::VARCHAR || *pg_type == Type::TEXT || *pg_type == Type::UNKNOWN {
+        DataValue::Utf8(portal.parameter::<String>(i, pg_type)?)
+    } else {
+        return Err(PgWireError::UserError(Box::new(ErrorInfo::new(
+            "ERROR".to_owned(),
+            "0A000".to_owned(),
+            format!("Unsupported Parameter Type {pg_type}"),
+        ))));
+    })
+}
 
-    for tuple in tuples {
-        let mut encoder = DataRowEncoder::new(schema.clone());
-        for value in tuple.values {
-            match value.logical_type() {
-                LogicalType::SqlNull => encoder.encode_field(&None::<i8>),
-                LogicalType::Boolean => encoder.encode_field(&value.bool()),
-                LogicalType::Tinyint => encoder.encode_field(&value.i8()),
-                LogicalType::UTinyint => encoder.encode_field(&value.u8().map(|v| v as i8)),
-                LogicalType::Smallint => encoder.encode_field(&value.i16()),
-                LogicalType::USmallint => encoder.encode_field(&value.u16().map(|v| v as i16)),
-                LogicalType::Integer => encoder.encode_field(&value.i32()),
-                LogicalType::UInteger => encoder.encode_field(&value.u32()),
-                LogicalType::Bigint => encoder.encode_field(&value.i64()),
-                LogicalType::UBigint => encoder.encode_field(&value.u64().map(|v| v as i64)),
-                LogicalType::Float => encoder.encode_field(&value.float()),
-                LogicalType::Double => encoder.encode_field(&value.double()),
-                LogicalType::Varchar(_) => encoder.encode_field(&value.utf8()),
-                LogicalType::Date => encoder.encode_field(&value.date()),
-                LogicalType::DateTime => encoder.encode_field(&value.datetime()),
-                LogicalType::Decimal(_, _) => todo!(),
-                _ => unreachable!(),
-            }?;
+fn into_field_infos(schema_ref: &SchemaRef, format: &Format) -> PgWireResult<Vec<FieldInfo>> {
+    schema_ref
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let pg_type = into_pg_type(column.datatype())?;
+
+            Ok(FieldInfo::new(
+                column.name().into(),
+                None,
+                None,
+                pg_type,
+                format.format_for(i),
+            ))
+        })
+        .collect()
+}
+
+/// The statements that do not return rows are completed with their command tags,
+/// and `INSERT`, `UPDATE`, `DELETE` and `COPY` tell the number of affected rows.
+/// The rows of `RETURNING` are sent as the result of a query.
+///
+/// Tips: pgwire completes every query response with `SELECT n`,
+/// so the statements with `RETURNING` are not completed with their own command tags.
+async fn into_response<'a>(
+    statement: &Statement,
+    tuples: BoxedExecutor<'a>,
+    format: &Format,
+) -> PgWireResult<Response<'a>> {
+    let Some(command) = command_tag(statement).filter(|_| !has_returning(statement)) else {
+        return Ok(Response::Query(encode_tuples(tuples, format).await?));
+    };
+    // the statement is completed once its results are consumed
+    let tuples = tuples
+        .try_collect::<Vec<_>>()
+        .await
+        .map_err(into_pg_error)?;
+    // Tips: pgwire does not write the oid of the tag, so the oid of `INSERT` is a part of its command
+    let mut tag = if matches!(statement, Statement::Insert { .. }) {
+        Tag::new(&format!("{command} 0"))
+    } else {
+        Tag::new(command)
+    };
+
+    if let Some(rows) = affected_rows(&tuples) {
+        tag = tag.with_rows(rows);
+    }
+
+    Ok(Response::Execution(tag))
+}
+
+fn command_tag(statement: &Statement) -> Option<&'static str> {
+    Some(match statement {
+        Statement::Insert { .. } => "INSERT",
+        Statement::Update { .. } => "UPDATE",
+        Statement::Delete { .. } => "DELETE",
+        Statement::Copy { .. } => "COPY",
+        Statement::CreateTable { .. } => "CREATE TABLE",
+        Statement::CreateIndex { .. } => "CREATE INDEX",
+        Statement::CreateRole { .. } => "CREATE ROLE",
+        Statement::AlterTable { .. } => "ALTER TABLE",
+        Statement::AlterRole { .. } => "ALTER ROLE",
+        Statement::Drop { object_type, .. } => match object_type {
+            ObjectType::Table => "DROP TABLE",
+            ObjectType::Index => "DROP INDEX",
+            ObjectType::Role => "DROP ROLE",
+            _ => "DROP",
+        },
+        Statement::Truncate { .. } => "TRUNCATE TABLE",
+        Statement::Analyze { .. } => "ANALYZE",
+        Statement::Grant { .. } => "GRANT",
+        Statement::Revoke { .. } => "REVOKE",
+        _ => return None,
+    })
+}
+
+fn has_returning(statement: &Statement) -> bool {
+    matches!(
+        statement,
+        Statement::Insert {
+            returning: Some(_),
+            ..
+        } | Statement::Update {
+            returning: Some(_),
+            ..
+        } | Statement::Delete {
+            returning: Some(_),
+            ..
         }
+    )
+}
 
-        results.push(encoder.finish());
+fn affected_rows(tuples: &[Tuple]) -> Option<usize> {
+    match tuples {
+        [tuple] if tuple.schema_ref.len() == 1 && tuple.schema_ref[0].name() == AFFECTED_ROWS => {
+            match tuple.values[0].as_ref() {
+                DataValue::UInt64(Some(rows)) => Some(*rows as usize),
+                _ => None,
+            }
+        }
+        _ => None,
     }
+}
 
-    Ok(QueryResponse::new(schema, stream::iter(results)))
+/// The rows are encoded lazily, as the tuples are pulled from the executor.
+async fn encode_tuples<'a>(
+    mut tuples: BoxedExecutor<'a>,
+    format: &Format,
+) -> PgWireResult<QueryResponse<'a>> {
+    let first = tuples.try_next().await.map_err(into_pg_error)?;
+    let Some(first) = first else {
+        return Ok(QueryResponse::new(Arc::new(vec![]), stream::empty()));
+    };
+    let schema = Arc::new(into_field_infos(&first.schema_ref, format)?);
+    let row_schema = schema.clone();
+    let rows = stream::iter([Ok(first)]).chain(tuples).map(move |tuple| {
+        let tuple = tuple.map_err(into_pg_error)?;
+
+        encode_tuple(row_schema.clone(), tuple)
+    });
+
+    Ok(QueryResponse::new(schema, rows))
+}
+
+fn encode_tuple(schema: Arc<Vec<FieldInfo>>, tuple: Tuple) -> PgWireResult<DataRow> {
+    let mut encoder = DataRowEncoder::new(schema);
+    for value in tuple.values {
+        match value.logical_type() {
+            LogicalType::SqlNull => encoder.encode_field(&None::<i8>),
+            LogicalType::Boolean => encoder.encode_field(&value.bool()),
+            LogicalType::Tinyint => encoder.encode_field(&value.i8()),
+            LogicalType::UTinyint => encoder.encode_field(&value.u8().map(|v| v as i8)),
+            LogicalType::Smallint => encoder.encode_field(&value.i16()),
+            LogicalType::USmallint => encoder.encode_field(&value.u16().map(|v| v as i16)),
+            LogicalType::Integer => encoder.encode_field(&value.i32()),
+            LogicalType::UInteger => encoder.encode_field(&value.u32().map(|v| v as i64)),
+            LogicalType::Bigint => encoder.encode_field(&value.i64()),
+            LogicalType::UBigint => encoder.encode_field(&value.u64().map(|v| v as i64)),
+            LogicalType::Float => encoder.encode_field(&value.float()),
+            LogicalType::Double => encoder.encode_field(&value.double()),
+            LogicalType::Varchar(_) => encoder.encode_field(&value.utf8()),
+            LogicalType::Date => encoder.encode_field(&value.date()),
+            LogicalType::DateTime => encoder.encode_field(&value.datetime()),
+            LogicalType::Decimal(_, _) => encoder.encode_field(&value.decimal().map(Numeric)),
+            _ => unreachable!(),
+        }?;
+    }
+
+    encoder.finish()
+}
+
+/// `NUMERIC` of PostgreSQL, the binary format is encoded by `rust_decimal`
+#[derive(Debug)]
+struct Numeric(Decimal);
+
+impl ToSql for Numeric {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        self.0.to_sql(ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <Decimal as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl ToSqlText for Numeric {
+    fn to_sql_text(
+        &self,
+        _ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        out.put_slice(self.0.to_string().as_bytes());
+
+        Ok(IsNull::No)
+    }
 }
 
 fn into_pg_type(data_type: &LogicalType) -> PgWireResult<Type> {
@@ -219,17 +898,18 @@ fn into_pg_type(data_type: &LogicalType) -> PgWireResult<Type> {
         LogicalType::Boolean => Type::BOOL,
         LogicalType::Tinyint | LogicalType::UTinyint => Type::CHAR,
         LogicalType::Smallint | LogicalType::USmallint => Type::INT2,
-        LogicalType::Integer | LogicalType::UInteger => Type::INT4,
-        LogicalType::Bigint | LogicalType::UBigint => Type::INT8,
+        LogicalType::Integer => Type::INT4,
+        LogicalType::UInteger | LogicalType::Bigint | LogicalType::UBigint => Type::INT8,
         LogicalType::Float => Type::FLOAT4,
         LogicalType::Double => Type::FLOAT8,
         LogicalType::Varchar(_) => Type::VARCHAR,
-        LogicalType::Date | LogicalType::DateTime => Type::DATE,
-        LogicalType::Decimal(_, _) => todo!(),
+        LogicalType::Date => Type::DATE,
+        LogicalType::DateTime => Type::TIMESTAMP,
+        LogicalType::Decimal(_, _) => Type::NUMERIC,
         _ => {
             return Err(PgWireError::UserError(Box::new(ErrorInfo::new(
                 "ERROR".to_owned(),
-                "XX000".to_owned(),
+                "0A000".to_owned(),
                 format!("Unsupported Datatype {data_type}"),
             ))));
         }
@@ -273,19 +953,48 @@ async fn main() {
         "1. all data is in the \'{}\' folder in the directory where the application is run",
         args.path
     );
+    if args.require_auth {
+        info!(
+            "2. users and privileges are managed by the administrator '{}'",
+            args.superuser
+        );
+    } else {
+        info!("2. every session is run as the administrator, authenticate the users with `--require-auth`");
+    }
 
-    let backend = FnckSQLBackend::new(args.path).await.unwrap();
+    let backend = FnckSQLBackend::new(
+        args.path,
+        args.require_auth,
+        args.superuser,
+        args.superuser_password,
+    )
+    .await
+    .unwrap();
+    let auth_source = Arc::new(FnckSQLAuthSource {
+        inner: backend.inner.clone(),
+    });
     let processor = Arc::new(backend);
-    // We have not implemented extended query in this server, use placeholder instead
-    let placeholder = Arc::new(StatelessMakeHandler::new(Arc::new(
-        PlaceholderExtendedQueryHandler,
-    )));
-    let authenticator = Arc::new(StatelessMakeHandler::new(Arc::new(NoopStartupHandler)));
     let server_addr = format!("{}:{}", args.ip, args.port);
     let listener = TcpListener::bind(server_addr).await.unwrap();
 
+    let server = async {
+        if args.require_auth {
+            let mut authenticator = MakeSASLScramAuthStartupHandler::new(
+                auth_source,
+                Arc::new(DefaultServerParameterProvider::default()),
+            );
+            authenticator.set_iterations(SCRAM_ITERATIONS);
+
+            server_run(processor, Arc::new(authenticator), listener).await
+        } else {
+            let authenticator = Arc::new(StatelessMakeHandler::new(Arc::new(NoopStartupHandler)));
+
+            server_run(processor, authenticator, listener).await
+        }
+    };
+
     tokio::select! {
-        res = server_run(processor, placeholder, authenticator, listener) => {
+        res = server => {
             if let Err(err) = res {
                 error!("[Listener][Failed To Accept]: {}", err);
             }
@@ -296,27 +1005,25 @@ async fn main() {
 
 async fn server_run<
     A: MakeHandler<Handler = Arc<impl StartupHandler + 'static>>,
-    Q: MakeHandler<Handler = Arc<impl SimpleQueryHandler + 'static>>,
-    EQ: MakeHandler<Handler = Arc<impl ExtendedQueryHandler + 'static>>,
+    Q: MakeHandler<Handler = Arc<impl SimpleQueryHandler + ExtendedQueryHandler + 'static>>,
 >(
     processor: Arc<Q>,
-    placeholder: Arc<EQ>,
     authenticator: Arc<A>,
     listener: TcpListener,
 ) -> io::Result<()> {
     loop {
         let incoming_socket = listener.accept().await?;
         let authenticator_ref = authenticator.make();
+        // the simple and extended queries of a session share the same transaction
         let processor_ref = processor.make();
-        let placeholder_ref = placeholder.make();
 
         tokio::spawn(async move {
             if let Err(err) = process_socket(
                 incoming_socket.0,
                 None,
                 authenticator_ref,
+                processor_ref.clone(),
                 processor_ref,
-                placeholder_ref,
             )
             .await
             {
@@ -325,3 +1032,111 @@ async fn server_run<
         });
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::sql_state;
+    use fnck_sql::errors::DatabaseError;
+    use fnck_sql::expression::BinaryOperator;
+    use fnck_sql::types::LogicalType;
+    use sqlparser::parser::ParserError;
+
+    #[test]
+    fn test_sql_state() {
+        let cases = vec![
+            (DatabaseError::DuplicatePrimaryKey, "23505"),
+            (DatabaseError::DuplicateUniqueValue, "23505"),
+            (DatabaseError::NotNull, "23502"),
+            (DatabaseError::NeedNullAbleOrDefault, "23502"),
+            (DatabaseError::EmptyStatement, "42601"),
+            (
+                DatabaseError::ParserSql(ParserError::ParserError("".to_string())),
+                "42601",
+            ),
+            (DatabaseError::ValuesLenMismatch(1, 2), "42601"),
+            (
+                DatabaseError::LengthMismatch {
+                    expected: 1,
+                    actual: 2,
+                },
+                "42601",
+            ),
+            (DatabaseError::PermissionDenied("t".to_string()), "42501"),
+            (DatabaseError::TableNotFound, "42P01"),
+            (DatabaseError::TableExists, "42P07"),
+            (DatabaseError::DuplicateColumn, "42701"),
+            (
+                DatabaseError::Duplicated("column", "c".to_string()),
+                "42701",
+            ),
+            (DatabaseError::Duplicated("index", "i".to_string()), "42P07"),
+            (DatabaseError::Duplicated("user", "u".to_string()), "42710"),
+            (DatabaseError::InvalidColumn("c".to_string()), "42703"),
+            (DatabaseError::NotFound("column", "c".to_string()), "42703"),
+            (DatabaseError::NotFound("user", "u".to_string()), "42704"),
+            (DatabaseError::AmbiguousColumn("c".to_string()), "42702"),
+            (DatabaseError::InvalidPlaceholder("$0".to_string()), "42P02"),
+            (DatabaseError::AggMiss("c".to_string()), "42803"),
+            (
+                DatabaseError::BinaryOpTypeMismatch("a".to_string(), "b".to_string()),
+                "42804",
+            ),
+            (
+                DatabaseError::Incomparable(LogicalType::Integer, LogicalType::Boolean),
+                "42804",
+            ),
+            (DatabaseError::MisMatch("a", "b"), "42804"),
+            (DatabaseError::UnsupportedStmt("s".to_string()), "0A000"),
+            (
+                DatabaseError::UnsupportedBinaryOperator(
+                    LogicalType::Boolean,
+                    BinaryOperator::Plus,
+                ),
+                "0A000",
+            ),
+            (
+                DatabaseError::UnsupportedCopySource("c".to_string()),
+                "0A000",
+            ),
+            (
+                DatabaseError::NotImplementedSqlparserDataType("t".to_string()),
+                "0A000",
+            ),
+            (DatabaseError::CastFail, "22P02"),
+            (
+                DatabaseError::from("a".parse::<i32>().unwrap_err()),
+                "22P02",
+            ),
+            (
+                DatabaseError::from("a".parse::<f64>().unwrap_err()),
+                "22P02",
+            ),
+            (
+                DatabaseError::from("a".parse::<bool>().unwrap_err()),
+                "22P02",
+            ),
+            (
+                DatabaseError::from("a".parse::<chrono::NaiveDate>().unwrap_err()),
+                "22P02",
+            ),
+            (DatabaseError::from(u8::try_from(256).unwrap_err()), "22003"),
+            (
+                DatabaseError::TryFromDecimal(rust_decimal::Error::ExceedsMaximumPossibleValue),
+                "22003",
+            ),
+            (DatabaseError::TooLong, "22001"),
+            (
+                DatabaseError::InvalidArgument("password", "1".to_string()),
+                "22023",
+            ),
+            (DatabaseError::InvalidPassword("u".to_string()), "28P01"),
+            (DatabaseError::TransactionAlreadyExists, "25001"),
+            (DatabaseError::NoTransactionBegin, "25P01"),
+            (DatabaseError::ColumnsEmpty, "XX000"),
+        ];
+
+        for (error, state) in cases {
+            assert_eq!(sql_state(&error), state, "{}", error);
+        }
+    }
+}
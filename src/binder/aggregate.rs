@@ -136,7 +136,9 @@ impl<'a, T: Transaction> Binder<'a, T> {
                     self.visit_column_agg_expr(expr)?;
                 }
             }
-            ScalarExpression::Constant(_) | ScalarExpression::ColumnRef { .. } => (),
+            ScalarExpression::Constant(_)
+            | ScalarExpression::ColumnRef { .. }
+            | ScalarExpression::Placeholder { .. } => (),
             ScalarExpression::Reference { .. } | ScalarExpression::Empty => unreachable!(),
             ScalarExpression::Tuple(args)
             | ScalarExpression::Function(ScalarFunction { args, .. }) => {
@@ -144,6 +146,37 @@ impl<'a, T: Transaction> Binder<'a, T> {
                     self.visit_column_agg_expr(expr)?;
                 }
             }
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                for expr in args
+                    .iter_mut()
+                    .chain(partition_by.iter_mut())
+                    .chain(order_by.iter_mut().map(|field| &mut field.expr))
+                {
+                    self.visit_column_agg_expr(expr)?;
+                }
+            }
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ..
+            } => {
+                if let Some(expr) = operand_expr {
+                    self.visit_column_agg_expr(expr)?;
+                }
+                for (when_expr, then_expr) in expr_pairs {
+                    self.visit_column_agg_expr(when_expr)?;
+                    self.visit_column_agg_expr(then_expr)?;
+                }
+                if let Some(expr) = else_expr {
+                    self.visit_column_agg_expr(expr)?;
+                }
+            }
         }
 
         Ok(())
@@ -186,7 +219,7 @@ impl<'a, T: Transaction> Binder<'a, T> {
             HashSet::from_iter(group_raw_exprs.iter());
 
         for expr in select_items {
-            if expr.has_agg_call() {
+            if expr.has_agg_call() || expr.has_window_call() {
                 continue;
             }
             group_raw_set.remove(expr);
@@ -315,7 +348,7 @@ impl<'a, T: Transaction> Binder<'a, T> {
                 }
                 Ok(())
             }
-            ScalarExpression::Constant(_) => Ok(()),
+            ScalarExpression::Constant(_) | ScalarExpression::Placeholder { .. } => Ok(()),
             ScalarExpression::Reference { .. } | ScalarExpression::Empty => unreachable!(),
             ScalarExpression::Tuple(args)
             | ScalarExpression::Function(ScalarFunction { args, .. }) => {
@@ -324,6 +357,32 @@ impl<'a, T: Transaction> Binder<'a, T> {
                 }
                 Ok(())
             }
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                for expr in args
+                    .iter()
+                    .chain(partition_by.iter())
+                    .chain(order_by.iter().map(|field| &field.expr))
+                {
+                    self.validate_having_orderby(expr)?;
+                }
+                Ok(())
+            }
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ..
+            } => {
+                for expr in ScalarExpression::case_when_exprs(operand_expr, expr_pairs, else_expr) {
+                    self.validate_having_orderby(expr)?;
+                }
+                Ok(())
+            }
         }
     }
 }
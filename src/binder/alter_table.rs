@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use super::{is_valid_identifier, Binder};
 use crate::binder::lower_case_name;
+use crate::catalog::Privilege;
 use crate::errors::DatabaseError;
 use crate::planner::operator::alter_table::add_column::AddColumnOperator;
 use crate::planner::operator::alter_table::drop_column::DropColumnOperator;
@@ -19,6 +20,7 @@ impl<'a, T: Transaction> Binder<'a, T> {
         operation: &AlterTableOperation,
     ) -> Result<LogicalPlan, DatabaseError> {
         let table_name: Arc<String> = Arc::new(lower_case_name(name)?);
+        self.context.check_privilege(&table_name, Privilege::Ddl)?;
 
         if let Some(table) = self.context.table(table_name.clone()) {
             let plan = match operation {
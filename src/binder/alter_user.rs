@@ -0,0 +1,39 @@
+use crate::binder::{lower_ident, Binder};
+use crate::errors::DatabaseError;
+use crate::planner::operator::alter_user::AlterUserOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use sqlparser::ast::{AlterRoleOperation, Ident, RoleOption};
+
+impl<'a, T: Transaction> Binder<'a, T> {
+    /// Only the password of the user and whether it is the administrator can be altered
+    pub(crate) fn bind_alter_user(
+        &mut self,
+        name: &Ident,
+        operation: &AlterRoleOperation,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        self.context.check_admin("ALTER USER")?;
+        let user_name = lower_ident(name);
+        let mut user = self
+            .context
+            .transaction
+            .user(&user_name)?
+            .ok_or(DatabaseError::NotFound("user", user_name))?;
+        let AlterRoleOperation::WithOptions { options } = operation else {
+            return Err(DatabaseError::UnsupportedStmt(operation.to_string()));
+        };
+        for option in options {
+            match option {
+                RoleOption::Password(password) => user.password = self.bind_password(password)?,
+                RoleOption::SuperUser(is_admin) => user.is_admin = *is_admin,
+                _ => return Err(DatabaseError::UnsupportedStmt(option.to_string())),
+            }
+        }
+
+        Ok(LogicalPlan::new(
+            Operator::AlterUser(AlterUserOperator { user }),
+            vec![],
+        ))
+    }
+}
@@ -1,4 +1,5 @@
 use crate::binder::{lower_case_name, Binder};
+use crate::catalog::Privilege;
 use crate::errors::DatabaseError;
 use crate::planner::operator::analyze::AnalyzeOperator;
 use crate::planner::operator::scan::ScanOperator;
@@ -12,6 +13,8 @@ use std::sync::Arc;
 impl<'a, T: Transaction> Binder<'a, T> {
     pub(crate) fn bind_analyze(&mut self, name: &ObjectName) -> Result<LogicalPlan, DatabaseError> {
         let table_name = Arc::new(lower_case_name(name)?);
+        self.context.check_privilege(&table_name, Privilege::Ddl)?;
+        self.context.with_statement_table(table_name.clone());
 
         let table_catalog = self.context.table_and_bind(table_name.clone(), None)?;
         let columns = table_catalog
@@ -71,6 +71,14 @@ impl<'a, T: Transaction> Binder<'a, T> {
             }
         };
 
+        let privilege = if to {
+            Privilege::Select
+        } else {
+            Privilege::Insert
+        };
+        self.context
+            .check_privilege(&table_name.to_string(), privilege)?;
+
         if let Some(table) = self.context.table(Arc::new(table_name.to_string())) {
             let schema_ref = table.schema_ref().clone();
             let ext_source = ExtSource {
@@ -0,0 +1,65 @@
+use crate::binder::{is_valid_identifier, lower_case_name, lower_ident, Binder};
+use crate::catalog::Privilege;
+use crate::errors::DatabaseError;
+use crate::planner::operator::create_index::CreateIndexOperator;
+use crate::planner::operator::scan::ScanOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use itertools::Itertools;
+use sqlparser::ast::{Expr, ObjectName, OrderByExpr};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+impl<'a, T: Transaction> Binder<'a, T> {
+    pub(crate) fn bind_create_index(
+        &mut self,
+        name: &ObjectName,
+        table_name: &ObjectName,
+        exprs: &[OrderByExpr],
+        is_unique: bool,
+        if_not_exists: bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let table_name = Arc::new(lower_case_name(table_name)?);
+        let index_name = lower_case_name(name)?;
+        self.context.check_privilege(&table_name, Privilege::Ddl)?;
+
+        if !is_valid_identifier(&index_name) {
+            return Err(DatabaseError::InvalidIndex);
+        }
+        let table = self
+            .context
+            .table(table_name.clone())
+            .ok_or(DatabaseError::TableNotFound)?;
+        let mut set = HashSet::new();
+        let columns = exprs
+            .iter()
+            .map(|OrderByExpr { expr, .. }| {
+                let column_name = match expr {
+                    Expr::Identifier(ident) => lower_ident(ident),
+                    expr => return Err(DatabaseError::UnsupportedStmt(expr.to_string())),
+                };
+                if !set.insert(column_name.clone()) {
+                    return Err(DatabaseError::Duplicated("column", column_name));
+                }
+
+                table
+                    .get_column_by_name(&column_name)
+                    .cloned()
+                    .ok_or(DatabaseError::NotFound("column", column_name))
+            })
+            .try_collect()?;
+        let plan = ScanOperator::build(table_name.clone(), table);
+
+        Ok(LogicalPlan::new(
+            Operator::CreateIndex(CreateIndexOperator {
+                table_name,
+                index_name,
+                columns,
+                is_unique,
+                if_not_exists,
+            }),
+            vec![plan],
+        ))
+    }
+}
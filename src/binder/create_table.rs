@@ -50,6 +50,10 @@ impl<'a, T: Transaction> Binder<'a, T> {
             .iter()
             .map(|col| self.bind_column(col))
             .try_collect()?;
+        // the primary key may be defined on a single column or as a table constraint, but only once
+        let mut primary_key_count = columns.iter().filter(|col| col.desc.is_primary()).count();
+        let mut unique_keys = Vec::new();
+
         for constraint in constraints {
             match constraint {
                 TableConstraint::Unique {
@@ -57,34 +61,55 @@ impl<'a, T: Transaction> Binder<'a, T> {
                     is_primary,
                     ..
                 } => {
-                    for column_name in column_names.iter().map(|ident| ident.value.to_lowercase()) {
-                        if let Some(column) = columns
+                    let column_names = column_names
+                        .iter()
+                        .map(|ident| ident.value.to_lowercase())
+                        .collect_vec();
+
+                    for (i, column_name) in column_names.iter().enumerate() {
+                        let column = columns
                             .iter_mut()
                             .find(|column| column.name() == column_name)
-                        {
-                            if *is_primary {
-                                column.desc.is_primary = true;
-                            } else {
-                                column.desc.is_unique = true;
-                            }
+                            .ok_or_else(|| {
+                                DatabaseError::NotFound("column", column_name.to_string())
+                            })?;
+
+                        if *is_primary {
+                            column.desc.primary = Some(i);
+                            column.nullable = false;
+                        } else if column_names.len() == 1 {
+                            column.desc.is_unique = true;
                         }
                     }
+                    if *is_primary {
+                        primary_key_count += 1;
+                    } else if column_names.len() > 1 {
+                        unique_keys.push(column_names);
+                    }
                 }
                 _ => todo!(),
             }
         }
 
-        if columns.iter().filter(|col| col.desc.is_primary).count() != 1 {
+        if primary_key_count == 0 {
             return Err(DatabaseError::InvalidTable(
                 "The primary key field must exist and have at least one".to_string(),
             ));
         }
+        if primary_key_count > 1 {
+            return Err(DatabaseError::InvalidTable(
+                "multiple primary keys are not allowed, use `PRIMARY KEY (c1, c2)` instead"
+                    .to_string(),
+            ));
+        }
 
         let plan = LogicalPlan::new(
             Operator::CreateTable(CreateTableOperator {
                 table_name,
                 columns,
+                unique_keys,
                 if_not_exists,
+                owner: self.context.user().map(str::to_string),
             }),
             vec![],
         );
@@ -108,7 +133,7 @@ impl<'a, T: Transaction> Binder<'a, T> {
                 ColumnOption::NotNull => nullable = false,
                 ColumnOption::Unique { is_primary } => {
                     if *is_primary {
-                        column_desc.is_primary = true;
+                        column_desc.primary = Some(0);
                         nullable = false;
                         // Skip other options when using primary key
                         break;
@@ -176,4 +201,50 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_create_bind_with_composite_keys() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = KipStorage::new(temp_dir.path()).await?;
+        let transaction = storage.transaction().await?;
+        let functions = Default::default();
+
+        let sql = "create table t1 (a int, b int, c int, primary key (a, b), unique (b, c))";
+        let mut binder = Binder::new(BinderContext::new(&transaction, &functions));
+        let stmt = crate::parser::parse_sql(sql).unwrap();
+        let plan1 = binder.bind(&stmt[0]).unwrap();
+
+        match plan1.operator {
+            Operator::CreateTable(op) => {
+                assert!(op.columns[0].desc.is_primary());
+                assert!(!op.columns[0].nullable);
+                assert!(op.columns[1].desc.is_primary());
+                assert!(!op.columns[1].nullable);
+                assert!(!op.columns[2].desc.is_primary());
+                assert!(!op.columns[2].desc.is_unique);
+                assert_eq!(op.unique_keys, vec![vec!["b".to_string(), "c".to_string()]]);
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "create table t1 (a int, b int, primary key (b, a))";
+        let mut binder = Binder::new(BinderContext::new(&transaction, &functions));
+        let stmt = crate::parser::parse_sql(sql).unwrap();
+        let plan2 = binder.bind(&stmt[0]).unwrap();
+
+        match plan2.operator {
+            Operator::CreateTable(op) => {
+                assert_eq!(op.columns[0].desc.primary, Some(1));
+                assert_eq!(op.columns[1].desc.primary, Some(0));
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "create table t2 (a int primary key, b int, primary key (a, b))";
+        let mut binder = Binder::new(BinderContext::new(&transaction, &functions));
+        let stmt = crate::parser::parse_sql(sql).unwrap();
+        assert!(binder.bind(&stmt[0]).is_err());
+
+        Ok(())
+    }
 }
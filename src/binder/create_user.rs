@@ -0,0 +1,57 @@
+use crate::binder::{lower_case_name, Binder};
+use crate::catalog::{UserCatalog, UserPassword};
+use crate::errors::DatabaseError;
+use crate::expression::ScalarExpression;
+use crate::planner::operator::create_user::CreateUserOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use sqlparser::ast::{ObjectName, Password};
+
+impl<'a, T: Transaction> Binder<'a, T> {
+    /// `CREATE USER` is parsed as `CREATE ROLE`, every role is a user who can log in
+    /// and `SUPERUSER` creates the administrator.
+    pub(crate) fn bind_create_user(
+        &mut self,
+        name: &ObjectName,
+        password: &Option<Password>,
+        superuser: Option<bool>,
+        if_not_exists: bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        self.context.check_admin("CREATE USER")?;
+        let user_name = lower_case_name(name)?;
+        let password = match password {
+            Some(password) => self.bind_password(password)?,
+            None => None,
+        };
+
+        Ok(LogicalPlan::new(
+            Operator::CreateUser(CreateUserOperator {
+                user: UserCatalog::new(user_name, password, superuser.unwrap_or(false)),
+                if_not_exists,
+            }),
+            vec![],
+        ))
+    }
+
+    /// The password is salted and hashed while binding, so the plaintext never reaches the plan
+    pub(crate) fn bind_password(
+        &mut self,
+        password: &Password,
+    ) -> Result<Option<UserPassword>, DatabaseError> {
+        match password {
+            Password::Password(expr) => match self.bind_expr(expr)? {
+                ScalarExpression::Constant(value) => match value.utf8() {
+                    Some(password) => Ok(Some(UserPassword::new(&password))),
+                    None if value.is_null() => Ok(None),
+                    None => Err(DatabaseError::InvalidArgument(
+                        "password",
+                        value.to_string(),
+                    )),
+                },
+                expr => Err(DatabaseError::InvalidArgument("password", expr.to_string())),
+            },
+            Password::NullPassword => Ok(None),
+        }
+    }
+}
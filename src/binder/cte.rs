@@ -0,0 +1,193 @@
+use crate::binder::{lower_ident, Binder, BinderContext};
+use crate::catalog::{ColumnCatalog, ColumnDesc, TableCatalog, TableName};
+use crate::errors::DatabaseError;
+use crate::expression::{AliasType, ScalarExpression};
+use crate::planner::operator::project::ProjectOperator;
+use crate::planner::operator::recursive_cte::RecursiveCteOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::tuple::{Schema, SchemaRef};
+use itertools::Itertools;
+use sqlparser::ast::{Cte, Ident, SetExpr, SetOperator, SetQuantifier, TableAlias, With};
+use std::mem;
+use std::sync::Arc;
+
+impl<'a, T: Transaction> Binder<'a, T> {
+    pub(crate) fn bind_with(&mut self, with: &With) -> Result<(), DatabaseError> {
+        for Cte {
+            alias: TableAlias { name, columns },
+            query,
+            ..
+        } in with.cte_tables.iter()
+        {
+            let table_name = Arc::new(lower_ident(name));
+
+            if with.recursive && query.order_by.is_empty() && query.limit.is_none() {
+                if let SetExpr::SetOperation {
+                    op: SetOperator::Union,
+                    set_quantifier,
+                    left,
+                    right,
+                } = query.body.as_ref()
+                {
+                    if let Some(cte) = self.bind_recursive_cte(
+                        table_name.clone(),
+                        columns,
+                        set_quantifier,
+                        left,
+                        right,
+                    )? {
+                        self.context.ctes.insert(table_name, cte);
+                        continue;
+                    }
+                }
+            }
+            let mut plan = self.bind_in_new_scope(|binder| binder.bind_query(query))?;
+            let schema = plan.output_schema().clone();
+            let table = Self::bind_cte_table(table_name.clone(), columns, &schema)?;
+            // rename the output columns of the query to the columns of the common table expression
+            let exprs = schema
+                .iter()
+                .zip(table.columns())
+                .map(|(column, cte_column)| ScalarExpression::Alias {
+                    expr: Box::new(ScalarExpression::ColumnRef(column.clone())),
+                    alias: AliasType::Expr(Box::new(ScalarExpression::ColumnRef(
+                        cte_column.clone(),
+                    ))),
+                })
+                .collect_vec();
+            let plan = LogicalPlan::new(Operator::Project(ProjectOperator { exprs }), vec![plan]);
+
+            self.context.ctes.insert(table_name, (table, plan));
+        }
+
+        Ok(())
+    }
+
+    /// Binds `anchor UNION [ALL] recursive term`,
+    /// none if the recursive term does not reference the common table expression.
+    fn bind_recursive_cte(
+        &mut self,
+        table_name: TableName,
+        alias_columns: &[Ident],
+        set_quantifier: &SetQuantifier,
+        anchor: &SetExpr,
+        recursive: &SetExpr,
+    ) -> Result<Option<(TableCatalog, LogicalPlan)>, DatabaseError> {
+        let mut anchor_plan = self.bind_in_new_scope(|binder| binder.bind_set_expr(anchor))?;
+        let anchor_schema_ref = anchor_plan.output_schema().clone();
+        let table = Self::bind_cte_table(table_name.clone(), alias_columns, &anchor_schema_ref)?;
+        let schema_ref = table.schema_ref().clone();
+
+        let origin_cte = self.context.ctes.insert(
+            table_name.clone(),
+            (
+                table.clone(),
+                RecursiveCteOperator::working_table(schema_ref.clone()),
+            ),
+        );
+        let recursive_plan = self.bind_in_new_scope(|binder| binder.bind_set_expr(recursive));
+        match origin_cte {
+            Some(cte) => self.context.ctes.insert(table_name.clone(), cte),
+            None => self.context.ctes.remove(&table_name),
+        };
+        let mut recursive_plan = recursive_plan?;
+
+        if !Self::is_recursive(&recursive_plan, &schema_ref) {
+            return Ok(None);
+        }
+        let recursive_schema_ref = recursive_plan.output_schema().clone();
+
+        if anchor_schema_ref.len() != recursive_schema_ref.len()
+            || anchor_schema_ref
+                .iter()
+                .zip(recursive_schema_ref.iter())
+                .any(|(anchor_column, recursive_column)| {
+                    anchor_column.datatype() != recursive_column.datatype()
+                })
+        {
+            return Err(DatabaseError::MisMatch(
+                "the output types of the anchor",
+                "the output types of the recursive term",
+            ));
+        }
+        let plan = RecursiveCteOperator::build(
+            RecursiveCteOperator {
+                table_name,
+                schema_ref,
+                anchor_schema_ref,
+                recursive_schema_ref,
+                is_distinct: !matches!(set_quantifier, SetQuantifier::All),
+                max_recursion_depth: self.context.max_recursion_depth,
+            },
+            anchor_plan,
+            recursive_plan,
+        );
+
+        Ok(Some((table, plan)))
+    }
+
+    fn is_recursive(plan: &LogicalPlan, schema_ref: &SchemaRef) -> bool {
+        RecursiveCteOperator::is_working_table(&plan.operator, schema_ref)
+            || plan
+                .childrens
+                .iter()
+                .any(|child| Self::is_recursive(child, schema_ref))
+    }
+
+    /// The columns are named after the aliases if there are, otherwise after the output of the query,
+    /// which is also used for the columns of the table functions.
+    pub(crate) fn bind_cte_table(
+        table_name: TableName,
+        alias_columns: &[Ident],
+        schema: &Schema,
+    ) -> Result<TableCatalog, DatabaseError> {
+        if !alias_columns.is_empty() && alias_columns.len() != schema.len() {
+            return Err(DatabaseError::MisMatch("alias", "columns"));
+        }
+        let columns = schema
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let name = alias_columns
+                    .get(i)
+                    .map(lower_ident)
+                    .unwrap_or_else(|| column.name().to_string());
+
+                ColumnCatalog::new(
+                    name,
+                    column.nullable,
+                    ColumnDesc::new(*column.datatype(), false, false, None),
+                )
+            })
+            .collect_vec();
+
+        TableCatalog::new(table_name, columns)
+    }
+
+    /// Binds apart from the tables and aliases of this query, such as the query of a common table expression.
+    pub(crate) fn bind_in_new_scope<F, R>(&mut self, f: F) -> Result<R, DatabaseError>
+    where
+        F: FnOnce(&mut Binder<'a, T>) -> Result<R, DatabaseError>,
+    {
+        let context = &mut self.context;
+        let mut binder = Binder::new(BinderContext {
+            outer_tables: context.outer_tables.clone(),
+            temp_table_id: context.temp_table_id,
+            placeholder_types: mem::take(&mut context.placeholder_types),
+            user: context.user,
+            max_recursion_depth: context.max_recursion_depth,
+            required_privileges: mem::take(&mut context.required_privileges),
+            ctes: context.ctes.clone(),
+            ..BinderContext::new(context.transaction, context.functions)
+        });
+        let result = f(&mut binder);
+
+        context.temp_table_id = binder.context.temp_table_id;
+        context.placeholder_types = binder.context.placeholder_types;
+        context.required_privileges = binder.context.required_privileges;
+
+        result
+    }
+}
@@ -1,43 +1,49 @@
 use crate::binder::{lower_case_name, Binder};
+use crate::catalog::Privilege;
 use crate::errors::DatabaseError;
 use crate::planner::operator::delete::DeleteOperator;
-use crate::planner::operator::scan::ScanOperator;
 use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
-use sqlparser::ast::{Expr, TableFactor, TableWithJoins};
+use itertools::Itertools;
+use sqlparser::ast::{Expr, SelectItem, TableFactor, TableWithJoins};
+use std::slice;
 use std::sync::Arc;
 
 impl<'a, T: Transaction> Binder<'a, T> {
     pub(crate) fn bind_delete(
         &mut self,
         from: &TableWithJoins,
+        using: &[TableWithJoins],
         selection: &Option<Expr>,
+        returning: &Option<Vec<SelectItem>>,
     ) -> Result<LogicalPlan, DatabaseError> {
-        if let TableFactor::Table { name, alias, .. } = &from.relation {
+        if let TableFactor::Table { name, .. } = &from.relation {
             let table_name = Arc::new(lower_case_name(name)?);
+            self.context
+                .check_privilege(&table_name, Privilege::Delete)?;
+            self.context.with_statement_table(table_name.clone());
 
-            let table_catalog = self.context.table_and_bind(table_name.clone(), None)?;
-            let primary_key_column = table_catalog
-                .columns()
-                .find(|column| column.desc.is_primary)
+            let mut plan = self.bind_table_ref(slice::from_ref(from))?;
+            let table = self
+                .context
+                .table(table_name.clone())
                 .cloned()
-                .unwrap();
-            let mut plan = ScanOperator::build(table_name.clone(), table_catalog);
-
-            if let Some(alias) = alias {
-                self.context
-                    .add_table_alias(alias.to_string(), table_name.clone());
-            }
-
-            if let Some(predicate) = selection {
-                plan = self.bind_where(plan, predicate)?;
-            }
+                .ok_or(DatabaseError::TableNotFound)?;
+            let primary_keys = table
+                .primary_keys()?
+                .into_iter()
+                .map(|(_, column)| column.clone())
+                .collect_vec();
+            plan = self.bind_where_with_sources(plan, using, selection)?;
+            let returning = self.bind_returning(&table, returning)?;
+            self.check_read_columns(&table_name, &plan, &returning.iter().collect_vec())?;
 
             Ok(LogicalPlan::new(
                 Operator::Delete(DeleteOperator {
                     table_name,
-                    primary_key_column,
+                    primary_keys,
+                    returning,
                 }),
                 vec![plan],
             ))
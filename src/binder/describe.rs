@@ -1,4 +1,5 @@
 use crate::binder::{lower_case_name, Binder};
+use crate::catalog::Privilege;
 use crate::errors::DatabaseError;
 use crate::planner::operator::describe::DescribeOperator;
 use crate::planner::operator::Operator;
@@ -13,6 +14,8 @@ impl<'a, T: Transaction> Binder<'a, T> {
         name: &ObjectName,
     ) -> Result<LogicalPlan, DatabaseError> {
         let table_name = Arc::new(lower_case_name(name)?);
+        self.context
+            .check_privilege(&table_name, Privilege::Select)?;
 
         Ok(LogicalPlan::new(
             Operator::Describe(DescribeOperator { table_name }),
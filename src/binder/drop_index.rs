@@ -0,0 +1,34 @@
+use crate::binder::{lower_ident, Binder};
+use crate::catalog::Privilege;
+use crate::errors::DatabaseError;
+use crate::planner::operator::drop_index::DropIndexOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use sqlparser::ast::ObjectName;
+use std::sync::Arc;
+
+impl<'a, T: Transaction> Binder<'a, T> {
+    /// The index belongs to a table, so the name is `{table_name}.{index_name}`
+    pub(crate) fn bind_drop_index(
+        &mut self,
+        name: &ObjectName,
+        if_exists: &bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let (table_name, index_name) = match name.0.as_slice() {
+            [table_name, index_name] => (lower_ident(table_name), lower_ident(index_name)),
+            _ => return Err(DatabaseError::InvalidIndex),
+        };
+        self.context.check_privilege(&table_name, Privilege::Ddl)?;
+
+        let plan = LogicalPlan::new(
+            Operator::DropIndex(DropIndexOperator {
+                table_name: Arc::new(table_name),
+                index_name,
+                if_exists: *if_exists,
+            }),
+            vec![],
+        );
+        Ok(plan)
+    }
+}
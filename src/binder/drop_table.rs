@@ -1,4 +1,5 @@
 use crate::binder::{lower_case_name, Binder};
+use crate::catalog::Privilege;
 use crate::errors::DatabaseError;
 use crate::planner::operator::drop_table::DropTableOperator;
 use crate::planner::operator::Operator;
@@ -14,6 +15,7 @@ impl<'a, T: Transaction> Binder<'a, T> {
         if_exists: &bool,
     ) -> Result<LogicalPlan, DatabaseError> {
         let table_name = Arc::new(lower_case_name(name)?);
+        self.context.check_privilege(&table_name, Privilege::Ddl)?;
 
         let plan = LogicalPlan::new(
             Operator::DropTable(DropTableOperator {
@@ -0,0 +1,26 @@
+use crate::binder::{lower_case_name, Binder};
+use crate::errors::DatabaseError;
+use crate::planner::operator::drop_user::DropUserOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use sqlparser::ast::ObjectName;
+
+impl<'a, T: Transaction> Binder<'a, T> {
+    pub(crate) fn bind_drop_user(
+        &mut self,
+        name: &ObjectName,
+        if_exists: &bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        self.context.check_admin("DROP USER")?;
+        let user_name = lower_case_name(name)?;
+
+        Ok(LogicalPlan::new(
+            Operator::DropUser(DropUserOperator {
+                user_name,
+                if_exists: *if_exists,
+            }),
+            vec![],
+        ))
+    }
+}
@@ -1,16 +1,17 @@
 use crate::catalog::ColumnCatalog;
 use crate::errors::DatabaseError;
 use crate::expression;
-use crate::expression::agg::AggKind;
+use crate::expression::agg::{AggKind, AggregateFunction};
 use itertools::Itertools;
 use sqlparser::ast::{
-    BinaryOperator, DataType, Expr, Function, FunctionArg, FunctionArgExpr, Ident, UnaryOperator,
+    ArrayAgg, BinaryOperator, DataType, DateTimeField, Expr, Function, FunctionArg,
+    FunctionArgExpr, Ident, UnaryOperator, Value, WindowType,
 };
 use std::slice;
 use std::sync::Arc;
 
 use super::{lower_ident, Binder};
-use crate::expression::function::{FunctionSummary, ScalarFunction};
+use crate::expression::function::{BuiltinFunction, FunctionSummary, ScalarFunction};
 use crate::expression::{AliasType, ScalarExpression};
 use crate::storage::Transaction;
 use crate::types::value::DataValue;
@@ -35,6 +36,7 @@ impl<'a, T: Transaction> Binder<'a, T> {
             }
             Expr::CompoundIdentifier(idents) => self.bind_column_ref_from_identifiers(idents, None),
             Expr::BinaryOp { left, right, op } => self.bind_binary_op_internal(left, right, op),
+            Expr::Value(Value::Placeholder(name)) => self.bind_placeholder(name),
             Expr::Value(v) => Ok(ScalarExpression::Constant(Arc::new(v.into()))),
             Expr::Function(func) => self.bind_function(func),
             Expr::Nested(expr) => self.bind_expr(expr),
@@ -52,7 +54,9 @@ impl<'a, T: Transaction> Binder<'a, T> {
                 list,
                 negated,
             } => self.bind_is_in(expr, list, *negated),
-            Expr::Cast { expr, data_type } => self.bind_cast(expr, data_type),
+            Expr::Cast {
+                expr, data_type, ..
+            } => self.bind_cast(expr, data_type),
             Expr::TypedString { data_type, value } => {
                 let logical_type = LogicalType::try_from(data_type.clone())?;
                 let value = DataValue::Utf8(Some(value.to_string())).cast(&logical_type)?;
@@ -64,16 +68,23 @@ impl<'a, T: Transaction> Binder<'a, T> {
                 negated,
                 low,
                 high,
-            } => Ok(ScalarExpression::Between {
-                negated: *negated,
-                expr: Box::new(self.bind_expr(expr)?),
-                left_expr: Box::new(self.bind_expr(low)?),
-                right_expr: Box::new(self.bind_expr(high)?),
-            }),
+            } => {
+                let expr = self.bind_expr(expr)?;
+                self.infer_placeholder_type(low, &expr.return_type());
+                self.infer_placeholder_type(high, &expr.return_type());
+
+                Ok(ScalarExpression::Between {
+                    negated: *negated,
+                    expr: Box::new(expr),
+                    left_expr: Box::new(self.bind_expr(low)?),
+                    right_expr: Box::new(self.bind_expr(high)?),
+                })
+            }
             Expr::Substring {
                 expr,
                 substring_for,
                 substring_from,
+                ..
             } => {
                 let mut for_expr = None;
                 let mut from_expr = None;
@@ -92,7 +103,7 @@ impl<'a, T: Transaction> Binder<'a, T> {
                 })
             }
             Expr::Subquery(query) => {
-                let mut sub_query = self.bind_query(query)?;
+                let mut sub_query = self.bind_subquery(query)?;
                 let sub_query_schema = sub_query.output_schema();
 
                 if sub_query_schema.len() != 1 {
@@ -122,9 +133,45 @@ impl<'a, T: Transaction> Binder<'a, T> {
                 }
                 Ok(ScalarExpression::Tuple(bond_exprs))
             }
-            _ => {
-                todo!()
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => self.bind_case_when(operand, conditions, results, else_result),
+            Expr::Ceil {
+                expr,
+                field: DateTimeField::NoDateTime,
+            } => {
+                let args = vec![self.bind_expr(expr)?];
+
+                self.bind_scalar_function("ceil".to_string(), args)
+            }
+            Expr::Floor {
+                expr,
+                field: DateTimeField::NoDateTime,
+            } => {
+                let args = vec![self.bind_expr(expr)?];
+
+                self.bind_scalar_function("floor".to_string(), args)
             }
+            // Tips: sqlparser parses `array_agg` apart from the other functions
+            Expr::ArrayAgg(ArrayAgg {
+                distinct,
+                expr: arg,
+                order_by: None,
+                limit: None,
+                ..
+            }) => Ok(ScalarExpression::AggCall {
+                distinct: *distinct,
+                kind: AggKind::ArrayAgg,
+                args: vec![self.bind_expr(arg)?],
+                ty: LogicalType::Tuple,
+            }),
+            Expr::Exists { .. } | Expr::InSubquery { .. } => Err(DatabaseError::UnsupportedStmt(
+                format!("{} is only supported as a conjunction of WHERE", expr),
+            )),
+            _ => Err(DatabaseError::UnsupportedStmt(expr.to_string())),
         }
     }
 
@@ -191,6 +238,14 @@ impl<'a, T: Transaction> Binder<'a, T> {
                     break;
                 }
             }
+            // the column of the outer query, which is referenced by the correlated subquery
+            if got_column.is_none() {
+                got_column = self
+                    .context
+                    .outer_tables
+                    .iter()
+                    .find_map(|table_catalog| table_catalog.get_column_by_name(&column_name));
+            }
             let column_catalog =
                 got_column.ok_or_else(|| DatabaseError::NotFound("column", column_name))?;
             Ok(ScalarExpression::ColumnRef(column_catalog.clone()))
@@ -205,6 +260,8 @@ impl<'a, T: Transaction> Binder<'a, T> {
     ) -> Result<ScalarExpression, DatabaseError> {
         let left_expr = Box::new(self.bind_expr(left)?);
         let right_expr = Box::new(self.bind_expr(right)?);
+        self.infer_placeholder_type(left, &right_expr.return_type());
+        self.infer_placeholder_type(right, &left_expr.return_type());
 
         let ty = match op {
             BinaryOperator::Plus
@@ -235,6 +292,37 @@ impl<'a, T: Transaction> Binder<'a, T> {
         })
     }
 
+    fn placeholder_index(name: &str) -> Result<usize, DatabaseError> {
+        name.strip_prefix('$')
+            .and_then(|num| num.parse::<usize>().ok())
+            .filter(|num| *num > 0)
+            .map(|num| num - 1)
+            .ok_or_else(|| DatabaseError::InvalidPlaceholder(name.to_string()))
+    }
+
+    fn bind_placeholder(&mut self, name: &str) -> Result<ScalarExpression, DatabaseError> {
+        let index = Self::placeholder_index(name)?;
+        let _ = self
+            .context
+            .placeholder_types
+            .entry(index)
+            .or_insert(LogicalType::SqlNull);
+
+        Ok(ScalarExpression::Placeholder { index, value: None })
+    }
+
+    /// The type of the `$n` parameter is inferred from the expression it is compared or assigned to
+    pub(crate) fn infer_placeholder_type(&mut self, expr: &Expr, ty: &LogicalType) {
+        if matches!(ty, LogicalType::SqlNull) {
+            return;
+        }
+        if let Expr::Value(Value::Placeholder(name)) = expr {
+            if let Ok(index) = Self::placeholder_index(name) {
+                let _ = self.context.placeholder_types.insert(index, *ty);
+            }
+        }
+    }
+
     fn bind_unary_op_internal(
         &mut self,
         expr: &Expr,
@@ -265,12 +353,39 @@ impl<'a, T: Transaction> Binder<'a, T> {
             match arg_expr {
                 FunctionArgExpr::Expr(expr) => args.push(self.bind_expr(expr)?),
                 FunctionArgExpr::Wildcard => args.push(Self::wildcard_expr()),
-                _ => todo!(),
+                FunctionArgExpr::QualifiedWildcard(_) => {
+                    return Err(DatabaseError::UnsupportedStmt(func.to_string()))
+                }
             }
         }
         let function_name = func.name.to_string().to_lowercase();
 
-        match function_name.as_str() {
+        match &func.over {
+            Some(WindowType::WindowSpec(spec)) => {
+                return self.bind_window_call(&function_name, args, func.distinct, spec)
+            }
+            Some(window @ WindowType::NamedWindow(_)) => {
+                return Err(DatabaseError::UnsupportedStmt(window.to_string()))
+            }
+            None => (),
+        }
+
+        let summary = FunctionSummary {
+            name: function_name,
+            arg_types: args.iter().map(ScalarExpression::return_type).collect_vec(),
+        };
+        if let Some(function) = self.context.functions.aggregates.get(&summary) {
+            return Ok(ScalarExpression::AggCall {
+                distinct: func.distinct,
+                ty: *function.return_type(),
+                kind: AggKind::UserDefined(AggregateFunction {
+                    inner: function.clone(),
+                }),
+                args,
+            });
+        }
+
+        match summary.name.as_str() {
             "count" => {
                 return Ok(ScalarExpression::AggCall {
                     distinct: func.distinct,
@@ -321,21 +436,117 @@ impl<'a, T: Transaction> Binder<'a, T> {
             }
             _ => (),
         }
+        let (kind, arg_range, ty) = match summary.name.as_str() {
+            "stddev" | "stddev_samp" => (AggKind::StddevSamp, 1..=1, LogicalType::Double),
+            "stddev_pop" => (AggKind::StddevPop, 1..=1, LogicalType::Double),
+            "variance" | "var_samp" => (AggKind::VarSamp, 1..=1, LogicalType::Double),
+            "var_pop" => (AggKind::VarPop, 1..=1, LogicalType::Double),
+            "bool_and" | "every" => (AggKind::BoolAnd, 1..=1, LogicalType::Boolean),
+            "bool_or" => (AggKind::BoolOr, 1..=1, LogicalType::Boolean),
+            "string_agg" => (AggKind::StringAgg, 2..=2, LogicalType::Varchar(None)),
+            "group_concat" => (AggKind::StringAgg, 1..=2, LogicalType::Varchar(None)),
+            "array_agg" => (AggKind::ArrayAgg, 1..=1, LogicalType::Tuple),
+            "approx_count_distinct" => (AggKind::ApproxCountDistinct, 1..=1, LogicalType::Bigint),
+            "median" => (AggKind::Median, 1..=1, LogicalType::Double),
+            // Tips: `percentile_cont(expr, fraction)` instead of `percentile_cont(fraction) WITHIN GROUP (ORDER BY expr)`,
+            // which sqlparser cannot parse
+            "percentile_cont" => (AggKind::PercentileCont, 2..=2, LogicalType::Double),
+            _ => return self.bind_scalar_function(summary.name, args),
+        };
+        if !arg_range.contains(&args.len()) {
+            return Err(DatabaseError::MisMatch("the arguments", "the aggregate"));
+        }
+        let is_valid = |ty: &LogicalType| match kind {
+            AggKind::BoolAnd | AggKind::BoolOr => {
+                matches!(ty, LogicalType::Boolean | LogicalType::SqlNull)
+            }
+            AggKind::StringAgg | AggKind::ArrayAgg | AggKind::ApproxCountDistinct => true,
+            _ => ty.is_numeric() || matches!(ty, LogicalType::Decimal(..) | LogicalType::SqlNull),
+        };
+        if !summary.arg_types.iter().all(is_valid) {
+            return Err(DatabaseError::InvalidType);
+        }
+
+        Ok(ScalarExpression::AggCall {
+            distinct: func.distinct,
+            kind,
+            args,
+            ty,
+        })
+    }
+
+    /// Binds the function registered by `DataBaseBuilder::register_function`,
+    /// or the built-in one if there is no such function
+    fn bind_scalar_function(
+        &mut self,
+        function_name: String,
+        args: Vec<ScalarExpression>,
+    ) -> Result<ScalarExpression, DatabaseError> {
         let arg_types = args.iter().map(ScalarExpression::return_type).collect_vec();
         let summary = FunctionSummary {
             name: function_name,
             arg_types,
         };
-        if let Some(function) = self.context.functions.get(&summary) {
+        if let Some(function) = self.context.functions.scalars.get(&summary) {
             return Ok(ScalarExpression::Function(ScalarFunction {
                 args,
                 inner: function.clone(),
             }));
         }
+        if let Some(function) = BuiltinFunction::resolve(&summary)? {
+            return Ok(ScalarExpression::Function(ScalarFunction {
+                args,
+                inner: function,
+            }));
+        }
 
         Err(DatabaseError::NotFound("function", summary.name))
     }
 
+    fn bind_case_when(
+        &mut self,
+        operand: &Option<Box<Expr>>,
+        conditions: &[Expr],
+        results: &[Expr],
+        else_result: &Option<Box<Expr>>,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let operand_expr = operand
+            .as_ref()
+            .map(|expr| self.bind_expr(expr).map(Box::new))
+            .transpose()?;
+        let mut expr_pairs = Vec::with_capacity(conditions.len());
+
+        for (condition, result) in conditions.iter().zip(results) {
+            let when_expr = self.bind_expr(condition)?;
+
+            // the `WHEN` expressions of the simple `CASE` are compared with the operand
+            if let Some(operand_expr) = &operand_expr {
+                let _ = LogicalType::common_type(&[
+                    operand_expr.return_type(),
+                    when_expr.return_type(),
+                ])?;
+            }
+            expr_pairs.push((when_expr, self.bind_expr(result)?));
+        }
+        let else_expr = else_result
+            .as_ref()
+            .map(|expr| self.bind_expr(expr).map(Box::new))
+            .transpose()?;
+        let result_types = expr_pairs
+            .iter()
+            .map(|(_, then_expr)| then_expr)
+            .chain(else_expr.as_deref())
+            .map(ScalarExpression::return_type)
+            .collect_vec();
+
+        Ok(ScalarExpression::CaseWhen {
+            operand_expr,
+            expr_pairs,
+            else_expr,
+            ty: LogicalType::common_type(&result_types)?,
+        })
+    }
+
     fn bind_is_null(
         &mut self,
         expr: &Expr,
@@ -0,0 +1,95 @@
+use crate::binder::{lower_case_name, lower_ident, Binder};
+use crate::catalog::{Privilege, TableName};
+use crate::errors::DatabaseError;
+use crate::planner::operator::grant::GrantOperator;
+use crate::planner::operator::revoke::RevokeOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use itertools::Itertools;
+use sqlparser::ast::{Action, GrantObjects, Ident, Privileges};
+use std::sync::Arc;
+
+impl<'a, T: Transaction> Binder<'a, T> {
+    pub(crate) fn bind_grant(
+        &mut self,
+        privileges: &Privileges,
+        objects: &GrantObjects,
+        grantees: &[Ident],
+    ) -> Result<LogicalPlan, DatabaseError> {
+        self.context.check_admin("GRANT")?;
+        let (privileges, table_names, user_names) =
+            Self::bind_privileges(privileges, objects, grantees)?;
+
+        Ok(LogicalPlan::new(
+            Operator::Grant(GrantOperator {
+                privileges,
+                table_names,
+                user_names,
+            }),
+            vec![],
+        ))
+    }
+
+    pub(crate) fn bind_revoke(
+        &mut self,
+        privileges: &Privileges,
+        objects: &GrantObjects,
+        grantees: &[Ident],
+    ) -> Result<LogicalPlan, DatabaseError> {
+        self.context.check_admin("REVOKE")?;
+        let (privileges, table_names, user_names) =
+            Self::bind_privileges(privileges, objects, grantees)?;
+
+        Ok(LogicalPlan::new(
+            Operator::Revoke(RevokeOperator {
+                privileges,
+                table_names,
+                user_names,
+            }),
+            vec![],
+        ))
+    }
+
+    /// Only the privileges on the whole tables are supported,
+    /// and `CREATE` stands for the DDL privilege on the table.
+    #[allow(clippy::type_complexity)]
+    fn bind_privileges(
+        privileges: &Privileges,
+        objects: &GrantObjects,
+        grantees: &[Ident],
+    ) -> Result<(Vec<Privilege>, Vec<TableName>, Vec<String>), DatabaseError> {
+        let privileges = match privileges {
+            Privileges::All { .. } => Privilege::ALL.to_vec(),
+            Privileges::Actions(actions) => actions
+                .iter()
+                .map(|action| match action {
+                    Action::Select { columns: None } => Ok(Privilege::Select),
+                    Action::Insert { columns: None } => Ok(Privilege::Insert),
+                    Action::Update { columns: None } => Ok(Privilege::Update),
+                    Action::Delete => Ok(Privilege::Delete),
+                    Action::Create => Ok(Privilege::Ddl),
+                    action => Err(DatabaseError::UnsupportedStmt(format!(
+                        "privilege: {}",
+                        action
+                    ))),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+        let table_names = match objects {
+            GrantObjects::Tables(names) => names
+                .iter()
+                .map(|name| Ok(Arc::new(lower_case_name(name)?)))
+                .collect::<Result<Vec<_>, DatabaseError>>()?,
+            objects => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "privilege on: {}",
+                    objects
+                )))
+            }
+        };
+        let user_names = grantees.iter().map(lower_ident).collect_vec();
+
+        Ok((privileges, table_names, user_names))
+    }
+}
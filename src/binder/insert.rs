@@ -1,14 +1,20 @@
-use crate::binder::{lower_case_name, Binder};
+use crate::binder::{lower_case_name, lower_ident, Binder};
+use crate::catalog::{ColumnRef, Privilege, TableCatalog};
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
-use crate::planner::operator::insert::InsertOperator;
+use crate::planner::operator::insert::{InsertOperator, OnConflict, OnConflictAction};
 use crate::planner::operator::values::ValuesOperator;
 use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
 use crate::types::tuple::SchemaRef;
 use crate::types::value::{DataValue, ValueRef};
-use sqlparser::ast::{Expr, Ident, ObjectName};
+use itertools::Itertools;
+use sqlparser::ast::{
+    Assignment, ConflictTarget, DoUpdate, Expr, Ident, ObjectName, OnConflict as SqlOnConflict,
+    OnConflictAction as SqlOnConflictAction, OnInsert, Query, SelectItem, SetExpr,
+};
+use std::borrow::Cow;
 use std::slice;
 use std::sync::Arc;
 
@@ -17,81 +23,64 @@ impl<'a, T: Transaction> Binder<'a, T> {
         &mut self,
         name: &ObjectName,
         idents: &[Ident],
-        expr_rows: &Vec<Vec<Expr>>,
+        source: &Query,
         is_overwrite: bool,
+        on: &Option<OnInsert>,
+        returning: &Option<Vec<SelectItem>>,
     ) -> Result<LogicalPlan, DatabaseError> {
         let table_name = Arc::new(lower_case_name(name)?);
+        self.context
+            .check_privilege(&table_name, Privilege::Insert)?;
 
-        if let Some(table) = self.context.table(table_name.clone()) {
-            let mut _schema_ref = None;
-            let values_len = expr_rows[0].len();
-
-            if idents.is_empty() {
-                let temp_schema_ref = table.schema_ref().clone();
-                if values_len > temp_schema_ref.len() {
-                    return Err(DatabaseError::ValuesLenMismatch(
-                        temp_schema_ref.len(),
-                        values_len,
-                    ));
+        if let Some(table) = self.context.table(table_name.clone()).cloned() {
+            let mut columns = Vec::with_capacity(idents.len());
+            for ident in idents {
+                match self.bind_column_ref_from_identifiers(
+                    slice::from_ref(ident),
+                    Some(table_name.to_string()),
+                )? {
+                    ScalarExpression::ColumnRef(catalog) => columns.push(catalog),
+                    _ => unreachable!(),
                 }
-                _schema_ref = Some(temp_schema_ref);
+            }
+            let (plan, columns) = if let SetExpr::Values(values) = source.body.as_ref() {
+                (
+                    self.bind_insert_values(&table, columns, &values.rows)?,
+                    None,
+                )
             } else {
-                let mut columns = Vec::with_capacity(idents.len());
-                for ident in idents {
-                    match self.bind_column_ref_from_identifiers(
-                        slice::from_ref(ident),
-                        Some(table_name.to_string()),
-                    )? {
-                        ScalarExpression::ColumnRef(catalog) => columns.push(catalog),
-                        _ => unreachable!(),
+                let mut plan = self.bind_query(source)?;
+                let values_len = plan.output_schema().len();
+
+                // Tips: the output of the query is inserted into the columns by position
+                if columns.is_empty() {
+                    if values_len > table.columns_len() {
+                        return Err(DatabaseError::ValuesLenMismatch(
+                            table.columns_len(),
+                            values_len,
+                        ));
                     }
-                }
-                if values_len != columns.len() {
+                    columns = table.columns().take(values_len).cloned().collect_vec();
+                } else if values_len != columns.len() {
                     return Err(DatabaseError::ValuesLenMismatch(columns.len(), values_len));
                 }
-                _schema_ref = Some(Arc::new(columns));
-            }
-            let schema_ref = _schema_ref.ok_or(DatabaseError::ColumnsEmpty)?;
-            let mut rows = Vec::with_capacity(expr_rows.len());
-            for expr_row in expr_rows {
-                if expr_row.len() != values_len {
-                    return Err(DatabaseError::ValuesLenMismatch(expr_row.len(), values_len));
-                }
-                let mut row = Vec::with_capacity(expr_row.len());
-
-                for (i, expr) in expr_row.iter().enumerate() {
-                    match &self.bind_expr(expr)? {
-                        ScalarExpression::Constant(value) => {
-                            // Check if the value length is too long
-                            value.check_len(schema_ref[i].datatype())?;
-                            let cast_value =
-                                DataValue::clone(value).cast(schema_ref[i].datatype())?;
-                            row.push(Arc::new(cast_value))
-                        }
-                        ScalarExpression::Unary { expr, op, .. } => {
-                            if let ScalarExpression::Constant(value) = expr.as_ref() {
-                                row.push(Arc::new(
-                                    DataValue::unary_op(value, op)?
-                                        .cast(schema_ref[i].datatype())?,
-                                ))
-                            } else {
-                                unreachable!()
-                            }
-                        }
-                        _ => unreachable!(),
-                    }
-                }
-
-                rows.push(row);
-            }
-            let values_plan = self.bind_values(rows, schema_ref);
+                (plan, Some(columns))
+            };
+            let on_conflict = on
+                .as_ref()
+                .map(|on| self.bind_on_conflict(&table, on))
+                .transpose()?;
+            let returning = self.bind_returning(&table, returning)?;
 
             Ok(LogicalPlan::new(
                 Operator::Insert(InsertOperator {
                     table_name,
                     is_overwrite,
+                    columns,
+                    on_conflict,
+                    returning,
                 }),
-                vec![values_plan],
+                vec![plan],
             ))
         } else {
             Err(DatabaseError::InvalidTable(format!(
@@ -101,13 +90,214 @@ impl<'a, T: Transaction> Binder<'a, T> {
         }
     }
 
+    fn bind_insert_values(
+        &mut self,
+        table: &TableCatalog,
+        columns: Vec<ColumnRef>,
+        expr_rows: &Vec<Vec<Expr>>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let values_len = expr_rows[0].len();
+
+        let schema_ref = if columns.is_empty() {
+            let temp_schema_ref = table.schema_ref().clone();
+            if values_len > temp_schema_ref.len() {
+                return Err(DatabaseError::ValuesLenMismatch(
+                    temp_schema_ref.len(),
+                    values_len,
+                ));
+            }
+            temp_schema_ref
+        } else {
+            if values_len != columns.len() {
+                return Err(DatabaseError::ValuesLenMismatch(columns.len(), values_len));
+            }
+            Arc::new(columns)
+        };
+        let mut rows = Vec::with_capacity(expr_rows.len());
+        let mut placeholders = Vec::new();
+        for expr_row in expr_rows {
+            if expr_row.len() != values_len {
+                return Err(DatabaseError::ValuesLenMismatch(expr_row.len(), values_len));
+            }
+            let mut row = Vec::with_capacity(expr_row.len());
+
+            for (i, expr) in expr_row.iter().enumerate() {
+                self.infer_placeholder_type(expr, schema_ref[i].datatype());
+
+                match &self.bind_expr(expr)? {
+                    ScalarExpression::Constant(value) => {
+                        // Check if the value length is too long
+                        value.check_len(schema_ref[i].datatype())?;
+                        let cast_value = DataValue::clone(value).cast(schema_ref[i].datatype())?;
+                        row.push(Arc::new(cast_value))
+                    }
+                    // Tips: the value of the parameter is checked and cast when it is bound
+                    ScalarExpression::Placeholder { index, .. } => {
+                        placeholders.push((rows.len(), i, *index));
+                        row.push(Arc::new(DataValue::none(schema_ref[i].datatype())))
+                    }
+                    ScalarExpression::Unary { expr, op, .. } => {
+                        if let ScalarExpression::Constant(value) = expr.as_ref() {
+                            row.push(Arc::new(
+                                DataValue::unary_op(value, op)?.cast(schema_ref[i].datatype())?,
+                            ))
+                        } else {
+                            return Err(DatabaseError::UnsupportedStmt(expr.to_string()));
+                        }
+                    }
+                    _ => unreachable!(),
+                }
+            }
+
+            rows.push(row);
+        }
+
+        Ok(self.bind_values(rows, schema_ref, placeholders))
+    }
+
+    fn bind_on_conflict(
+        &mut self,
+        table: &TableCatalog,
+        on: &OnInsert,
+    ) -> Result<OnConflict, DatabaseError> {
+        let (conflict_target, action) = match on {
+            OnInsert::OnConflict(SqlOnConflict {
+                conflict_target,
+                action,
+            }) => (conflict_target, action),
+            on => return Err(DatabaseError::UnsupportedStmt(on.to_string())),
+        };
+        let index_meta = match conflict_target {
+            Some(ConflictTarget::Columns(idents)) => {
+                let column_ids: Vec<_> = idents
+                    .iter()
+                    .map(|ident| {
+                        let column_name = lower_ident(ident);
+
+                        table
+                            .get_column_by_name(&column_name)
+                            .and_then(|column| column.id())
+                            .ok_or(DatabaseError::NotFound("column", column_name))
+                    })
+                    .try_collect()?;
+                // the conflict target is the unique index on exactly these columns
+                let index_meta = table
+                    .indexes
+                    .iter()
+                    .filter(|meta| meta.is_unique || meta.is_primary)
+                    .find(|meta| {
+                        meta.column_ids
+                            .iter()
+                            .sorted()
+                            .eq(column_ids.iter().sorted())
+                    })
+                    .ok_or_else(|| {
+                        DatabaseError::NotFound(
+                            "unique index",
+                            idents.iter().map(|ident| &ident.value).join(", "),
+                        )
+                    })?;
+                Some(index_meta.clone())
+            }
+            Some(ConflictTarget::OnConstraint(name)) => {
+                let index_name = lower_case_name(name)?;
+                let index_meta = table
+                    .get_index_by_name(&index_name)
+                    .filter(|meta| meta.is_unique || meta.is_primary)
+                    .ok_or(DatabaseError::NotFound("unique index", index_name))?;
+                Some(index_meta.clone())
+            }
+            None => None,
+        };
+        let action = match action {
+            SqlOnConflictAction::DoNothing => OnConflictAction::DoNothing,
+            SqlOnConflictAction::DoUpdate(DoUpdate {
+                assignments,
+                selection,
+            }) => {
+                if index_meta.is_none() {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "ON CONFLICT DO UPDATE requires the conflict target".to_string(),
+                    ));
+                }
+                self.context
+                    .check_privilege(&table.name, Privilege::Update)?;
+                self.bind_in_new_scope(|binder| {
+                    binder.bind_conflict_update(table, assignments, selection)
+                })?
+            }
+        };
+
+        Ok(OnConflict { index_meta, action })
+    }
+
+    /// Binds `DO UPDATE SET ... [WHERE ...]`, where the columns of the table are those of the existing tuple
+    /// and the columns of `EXCLUDED` are those of the tuple proposed for insertion.
+    fn bind_conflict_update(
+        &mut self,
+        table: &TableCatalog,
+        assignments: &[Assignment],
+        selection: &Option<Expr>,
+    ) -> Result<OnConflictAction, DatabaseError> {
+        let excluded = TableCatalog::new(
+            Arc::new("excluded".to_string()),
+            table
+                .columns()
+                .map(|column| column.as_ref().clone())
+                .collect_vec(),
+        )?;
+        let excluded_schema_ref = excluded.schema_ref().clone();
+
+        self.context
+            .bind_table
+            .insert(table.name.clone(), (Cow::Owned(table.clone()), None));
+        self.context.excluded = Some(excluded);
+
+        let mut value_exprs = Vec::with_capacity(assignments.len());
+
+        for assignment in assignments {
+            let expr = self.bind_expr(&assignment.value)?;
+
+            for ident in &assignment.id {
+                match self.bind_column_ref_from_identifiers(
+                    slice::from_ref(ident),
+                    Some(table.name.to_string()),
+                )? {
+                    ScalarExpression::ColumnRef(catalog) => {
+                        self.infer_placeholder_type(&assignment.value, catalog.datatype());
+                        if let ScalarExpression::Constant(value) = &expr {
+                            value.check_len(catalog.datatype())?;
+                        }
+                        value_exprs.push((catalog, expr.clone()));
+                    }
+                    _ => unreachable!(),
+                }
+            }
+        }
+        let selection = selection
+            .as_ref()
+            .map(|expr| self.bind_expr(expr))
+            .transpose()?;
+
+        Ok(OnConflictAction::DoUpdate {
+            excluded_schema_ref,
+            value_exprs,
+            selection,
+        })
+    }
+
     pub(crate) fn bind_values(
         &mut self,
         rows: Vec<Vec<ValueRef>>,
         schema_ref: SchemaRef,
+        placeholders: Vec<(usize, usize, usize)>,
     ) -> LogicalPlan {
         LogicalPlan::new(
-            Operator::Values(ValuesOperator { rows, schema_ref }),
+            Operator::Values(ValuesOperator {
+                rows,
+                schema_ref,
+                placeholders,
+            }),
             vec![],
         )
     }
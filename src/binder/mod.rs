@@ -1,31 +1,45 @@
 pub mod aggregate;
 mod alter_table;
+mod alter_user;
 mod analyze;
 pub mod copy;
+mod create_index;
 mod create_table;
+mod create_user;
+mod cte;
 mod delete;
 mod describe;
 mod distinct;
+mod drop_index;
 mod drop_table;
+mod drop_user;
 mod explain;
 pub mod expr;
+mod grant;
 mod insert;
+mod returning;
 mod select;
 mod show;
+mod subquery;
+mod table_function;
 mod truncate;
 mod update;
+mod window;
 
 use sqlparser::ast::{Ident, ObjectName, ObjectType, SetExpr, Statement};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
-use crate::catalog::{TableCatalog, TableName};
-use crate::db::Functions;
+use crate::catalog::{Privilege, TableCatalog, TableName};
+use crate::db::{Functions, DEFAULT_MAX_RECURSION_DEPTH};
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
 use crate::planner::operator::join::JoinType;
+use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
+use crate::types::LogicalType;
 
 pub enum InputRefType {
     AggCall,
@@ -40,17 +54,69 @@ pub enum QueryBindStep {
     Where,
     Agg,
     Having,
+    Window,
     Distinct,
     Sort,
     Project,
     Limit,
 }
 
+/// The privilege required by the statement, which is checked again
+/// every time the prepared statement is executed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequiredPrivilege {
+    Table(String, Privilege),
+    /// The statement is run only by the administrator
+    Admin(&'static str),
+}
+
+impl RequiredPrivilege {
+    pub(crate) fn check<T: Transaction>(
+        &self,
+        transaction: &T,
+        user: Option<&str>,
+    ) -> Result<(), DatabaseError> {
+        let Some(user) = user else {
+            return Ok(());
+        };
+        if transaction
+            .user(user)?
+            .map(|user| user.is_admin)
+            .unwrap_or(false)
+        {
+            return Ok(());
+        }
+        match self {
+            RequiredPrivilege::Table(table_name, privilege) => {
+                if !transaction
+                    .privileges(user, table_name)?
+                    .contains(privilege)
+                {
+                    return Err(DatabaseError::PermissionDenied(format!(
+                        "user {} has no {} privilege on table {}",
+                        user, privilege, table_name
+                    )));
+                }
+            }
+            RequiredPrivilege::Admin(action) => {
+                return Err(DatabaseError::PermissionDenied(format!(
+                    "user {} cannot {}",
+                    user, action
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Clone)]
 pub struct BinderContext<'a, T: Transaction> {
     functions: &'a Functions,
     pub(crate) transaction: &'a T,
-    pub(crate) bind_table: HashMap<TableName, (&'a TableCatalog, Option<JoinType>)>,
+    pub(crate) bind_table: HashMap<TableName, (Cow<'a, TableCatalog>, Option<JoinType>)>,
+    // tables of the outer queries, whose columns can be referenced by the subquery
+    outer_tables: Vec<Cow<'a, TableCatalog>>,
     // alias
     expr_aliases: HashMap<String, ScalarExpression>,
     table_aliases: HashMap<String, TableName>,
@@ -62,6 +128,27 @@ pub struct BinderContext<'a, T: Transaction> {
     sub_queries: HashMap<QueryBindStep, Vec<LogicalPlan>>,
 
     temp_table_id: usize,
+
+    // the types of the `$n` parameters, whose values are bound when the statement is executed
+    placeholder_types: BTreeMap<usize, LogicalType>,
+
+    // none for the embedding application, which is not restricted by privileges
+    user: Option<&'a str>,
+    pub(crate) required_privileges: Vec<RequiredPrivilege>,
+    // the table written by `UPDATE` or `DELETE` or analyzed by `ANALYZE`,
+    // which requires the privilege of the statement instead of `SELECT`
+    statement_table: Option<TableName>,
+
+    // common table expressions in scope, each with its columns and plan
+    ctes: HashMap<TableName, (TableCatalog, LogicalPlan)>,
+    // the columns of the table functions in `FROM`
+    table_functions: HashMap<TableName, TableCatalog>,
+
+    // the tuple proposed for insertion, which is referenced as `EXCLUDED` by `ON CONFLICT DO UPDATE`
+    excluded: Option<TableCatalog>,
+
+    // the times the recursive term of `WITH RECURSIVE` can be evaluated at most
+    max_recursion_depth: usize,
 }
 
 impl<'a, T: Transaction> BinderContext<'a, T> {
@@ -70,6 +157,7 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
             functions,
             transaction,
             bind_table: Default::default(),
+            outer_tables: vec![],
             expr_aliases: Default::default(),
             table_aliases: Default::default(),
             group_by_exprs: vec![],
@@ -77,9 +165,59 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
             bind_step: QueryBindStep::From,
             sub_queries: Default::default(),
             temp_table_id: 0,
+            placeholder_types: Default::default(),
+            user: None,
+            required_privileges: vec![],
+            statement_table: None,
+            ctes: Default::default(),
+            table_functions: Default::default(),
+            excluded: None,
+            max_recursion_depth: DEFAULT_MAX_RECURSION_DEPTH,
         }
     }
 
+    /// The statement is bound as the user, whose privileges on the tables are checked.
+    pub fn with_user(mut self, user: Option<&'a str>) -> Self {
+        self.user = user;
+        self
+    }
+
+    /// `WITH RECURSIVE` fails once its recursive term has been evaluated for the times.
+    pub fn with_max_recursion_depth(mut self, max_recursion_depth: usize) -> Self {
+        self.max_recursion_depth = max_recursion_depth;
+        self
+    }
+
+    pub(crate) fn check_privilege(
+        &mut self,
+        table_name: &str,
+        privilege: Privilege,
+    ) -> Result<(), DatabaseError> {
+        self.require(RequiredPrivilege::Table(table_name.to_string(), privilege))
+    }
+
+    /// Tables are created and users and privileges are managed only by the administrator
+    pub(crate) fn check_admin(&mut self, action: &'static str) -> Result<(), DatabaseError> {
+        self.require(RequiredPrivilege::Admin(action))
+    }
+
+    fn require(&mut self, required: RequiredPrivilege) -> Result<(), DatabaseError> {
+        required.check(self.transaction, self.user)?;
+        self.required_privileges.push(required);
+
+        Ok(())
+    }
+
+    /// The user who runs the statement, none for the embedding application
+    pub(crate) fn user(&self) -> Option<&'a str> {
+        self.user
+    }
+
+    /// The privilege of the statement is checked on the table instead of `SELECT`
+    pub(crate) fn with_statement_table(&mut self, table_name: TableName) {
+        self.statement_table = Some(table_name);
+    }
+
     pub fn temp_table(&mut self) -> TableName {
         self.temp_table_id += 1;
         Arc::new(format!("_temp_table_{}_", self.temp_table_id))
@@ -101,13 +239,30 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
     }
 
     pub fn table(&self, table_name: TableName) -> Option<&TableCatalog> {
-        if let Some(real_name) = self.table_aliases.get(table_name.as_ref()) {
-            self.transaction.table(real_name.clone())
-        } else {
-            self.transaction.table(table_name)
+        let table_name = self
+            .table_aliases
+            .get(table_name.as_ref())
+            .cloned()
+            .unwrap_or(table_name);
+
+        if let Some((table, _)) = self.ctes.get(&table_name) {
+            return Some(table);
+        }
+        if let Some(table) = self.table_functions.get(&table_name) {
+            return Some(table);
         }
+        if let Some(table) = self
+            .excluded
+            .as_ref()
+            .filter(|table| table.name == table_name)
+        {
+            return Some(table);
+        }
+        self.transaction.table(table_name)
     }
 
+    /// The table is scanned, so the `SELECT` privilege on it is required
+    /// unless it is the table of the statement.
     pub fn table_and_bind(
         &mut self,
         table_name: TableName,
@@ -119,10 +274,14 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
             self.transaction.table(table_name.clone())
         }
         .ok_or(DatabaseError::TableNotFound)?;
+        // Tips: the table of the statement is bound first, and is scanned with `SELECT` anywhere else
+        if self.statement_table.take().as_ref() != Some(&table.name) {
+            self.check_privilege(&table.name, Privilege::Select)?;
+        }
 
         let is_bound = self
             .bind_table
-            .insert(table_name.clone(), (table, join_type))
+            .insert(table_name.clone(), (Cow::Borrowed(table), join_type))
             .is_some();
         if is_bound {
             return Err(DatabaseError::InvalidTable(format!(
@@ -134,6 +293,54 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
         Ok(table)
     }
 
+    /// Binds the common table expression named `table_name` and returns its plan,
+    /// none if there is no such common table expression in scope.
+    pub fn cte_and_bind(
+        &mut self,
+        table_name: TableName,
+        join_type: Option<JoinType>,
+    ) -> Result<Option<LogicalPlan>, DatabaseError> {
+        let Some((table, plan)) = self.ctes.get(&table_name).cloned() else {
+            return Ok(None);
+        };
+
+        let is_bound = self
+            .bind_table
+            .insert(table_name.clone(), (Cow::Owned(table), join_type))
+            .is_some();
+        if is_bound {
+            return Err(DatabaseError::InvalidTable(format!(
+                "{} duplicated",
+                table_name
+            )));
+        }
+
+        Ok(Some(plan))
+    }
+
+    /// Binds the columns of the table function, which is named after the function or its alias.
+    pub fn table_function_and_bind(
+        &mut self,
+        table: TableCatalog,
+        join_type: Option<JoinType>,
+    ) -> Result<(), DatabaseError> {
+        let table_name = table.name.clone();
+
+        let is_bound = self
+            .bind_table
+            .insert(table_name.clone(), (Cow::Owned(table.clone()), join_type))
+            .is_some();
+        if is_bound {
+            return Err(DatabaseError::InvalidTable(format!(
+                "{} duplicated",
+                table_name
+            )));
+        }
+        self.table_functions.insert(table_name, table);
+
+        Ok(())
+    }
+
     // Tips: The order of this index is based on Aggregate being bound first.
     pub fn input_ref_index(&self, ty: InputRefType) -> usize {
         match ty {
@@ -167,7 +374,12 @@ impl<'a, T: Transaction> Binder<'a, T> {
     pub fn bind(&mut self, stmt: &Statement) -> Result<LogicalPlan, DatabaseError> {
         let plan = match stmt {
             Statement::Query(query) => self.bind_query(query)?,
-            Statement::AlterTable { name, operation } => self.bind_alter_table(name, operation)?,
+            Statement::AlterTable {
+                name, operations, ..
+            } => match operations.as_slice() {
+                [operation] => self.bind_alter_table(name, operation)?,
+                _ => return Err(DatabaseError::UnsupportedStmt(stmt.to_string())),
+            },
             Statement::CreateTable {
                 name,
                 columns,
@@ -175,6 +387,22 @@ impl<'a, T: Transaction> Binder<'a, T> {
                 if_not_exists,
                 ..
             } => self.bind_create_table(name, columns, constraints, *if_not_exists)?,
+            Statement::CreateIndex {
+                name: Some(name),
+                table_name,
+                columns,
+                unique,
+                if_not_exists,
+                ..
+            } => self.bind_create_index(name, table_name, columns, *unique, *if_not_exists)?,
+            Statement::CreateRole {
+                names,
+                if_not_exists,
+                password,
+                superuser,
+                ..
+            } => self.bind_create_user(&names[0], password, *superuser, *if_not_exists)?,
+            Statement::AlterRole { name, operation } => self.bind_alter_user(name, operation)?,
             Statement::Drop {
                 object_type,
                 names,
@@ -182,44 +410,51 @@ impl<'a, T: Transaction> Binder<'a, T> {
                 ..
             } => match object_type {
                 ObjectType::Table => self.bind_drop_table(&names[0], if_exists)?,
+                ObjectType::Index => self.bind_drop_index(&names[0], if_exists)?,
+                ObjectType::Role => self.bind_drop_user(&names[0], if_exists)?,
                 _ => todo!(),
             },
+            Statement::Grant {
+                privileges,
+                objects,
+                grantees,
+                ..
+            } => self.bind_grant(privileges, objects, grantees)?,
+            Statement::Revoke {
+                privileges,
+                objects,
+                grantees,
+                ..
+            } => self.bind_revoke(privileges, objects, grantees)?,
             Statement::Insert {
                 table_name,
                 columns,
                 source,
                 overwrite,
+                on,
+                returning,
                 ..
-            } => {
-                if let SetExpr::Values(values) = source.body.as_ref() {
-                    self.bind_insert(table_name, columns, &values.rows, *overwrite)?
-                } else {
-                    todo!()
-                }
-            }
+            } => self.bind_insert(table_name, columns, source, *overwrite, on, returning)?,
             Statement::Update {
                 table,
-                selection,
                 assignments,
+                from,
+                selection,
+                returning,
                 ..
-            } => {
-                if !table.joins.is_empty() {
-                    unimplemented!()
-                } else {
-                    self.bind_update(table, selection, assignments)?
-                }
-            }
+            } => self.bind_update(table, from.as_slice(), selection, assignments, returning)?,
             Statement::Delete {
-                from, selection, ..
-            } => {
-                let table = &from[0];
-
-                if !table.joins.is_empty() {
-                    unimplemented!()
-                } else {
-                    self.bind_delete(table, selection)?
-                }
-            }
+                from,
+                using,
+                selection,
+                returning,
+                ..
+            } => self.bind_delete(
+                &from[0],
+                using.as_deref().unwrap_or(&[]),
+                selection,
+                returning,
+            )?,
             Statement::Analyze { table_name, .. } => self.bind_analyze(table_name)?,
             Statement::Truncate { table_name, .. } => self.bind_truncate(table_name)?,
             Statement::ShowTables { .. } => self.bind_show_tables()?,
@@ -244,6 +479,68 @@ impl<'a, T: Transaction> Binder<'a, T> {
         Ok(plan)
     }
 
+    /// The privileges checked while binding the statement
+    pub fn required_privileges(&self) -> &[RequiredPrivilege] {
+        &self.context.required_privileges
+    }
+
+    /// `WHERE`, `SET` and `RETURNING` of `UPDATE` and `DELETE` that read the columns of the written table
+    /// require the `SELECT` privilege on it as well, as PostgreSQL does.
+    pub(crate) fn check_read_columns(
+        &mut self,
+        table_name: &TableName,
+        plan: &LogicalPlan,
+        exprs: &[&ScalarExpression],
+    ) -> Result<(), DatabaseError> {
+        fn is_read(plan: &LogicalPlan, table_name: &TableName) -> bool {
+            // Tips: all the columns are scanned, whether they are read or not
+            let is_read_by_operator = !matches!(plan.operator, Operator::Scan(_))
+                && plan
+                    .operator
+                    .referenced_columns(true)
+                    .iter()
+                    .any(|column| column.table_name() == Some(table_name));
+
+            is_read_by_operator
+                || plan
+                    .childrens
+                    .iter()
+                    .any(|child| is_read(child, table_name))
+        }
+        let is_read_by_exprs = exprs
+            .iter()
+            .flat_map(|expr| expr.referenced_columns(true))
+            .any(|column| column.table_name() == Some(table_name));
+
+        if is_read_by_exprs || is_read(plan, table_name) {
+            self.context
+                .check_privilege(table_name, Privilege::Select)?;
+        }
+        Ok(())
+    }
+
+    /// Types of the `$n` parameters in the bound statement,
+    /// `LogicalType::SqlNull` if it cannot be inferred.
+    pub fn parameter_types(&self) -> Vec<LogicalType> {
+        let len = self
+            .context
+            .placeholder_types
+            .keys()
+            .last()
+            .map(|index| index + 1)
+            .unwrap_or(0);
+
+        (0..len)
+            .map(|index| {
+                self.context
+                    .placeholder_types
+                    .get(&index)
+                    .cloned()
+                    .unwrap_or(LogicalType::SqlNull)
+            })
+            .collect()
+    }
+
     pub fn bind_set_expr(&mut self, set_expr: &SetExpr) -> Result<LogicalPlan, DatabaseError> {
         match set_expr {
             SetExpr::Select(select) => self.bind_select(select, &[]),
@@ -330,6 +627,28 @@ pub mod test {
             false,
         )?;
 
+        let _ = transaction.create_table(
+            Arc::new("t3".to_string()),
+            vec![
+                ColumnCatalog::new(
+                    "c5".to_string(),
+                    false,
+                    ColumnDesc::new(Integer, true, false, None),
+                ),
+                ColumnCatalog::new(
+                    "c6".to_string(),
+                    false,
+                    ColumnDesc::new(Integer, true, false, None),
+                ),
+                ColumnCatalog::new(
+                    "c7".to_string(),
+                    false,
+                    ColumnDesc::new(Integer, false, false, None),
+                ),
+            ],
+            false,
+        )?;
+
         transaction.commit().await?;
 
         Ok(storage)
@@ -0,0 +1,47 @@
+use crate::binder::Binder;
+use crate::catalog::TableCatalog;
+use crate::errors::DatabaseError;
+use crate::expression::ScalarExpression;
+use crate::storage::Transaction;
+use sqlparser::ast::SelectItem;
+use std::borrow::Cow;
+
+impl<'a, T: Transaction> Binder<'a, T> {
+    /// `RETURNING` of `INSERT`, `UPDATE` and `DELETE`, which can only reference the columns of the target table,
+    /// since it is evaluated on the tuples written or deleted by the statement.
+    pub(crate) fn bind_returning(
+        &mut self,
+        table: &TableCatalog,
+        items: &Option<Vec<SelectItem>>,
+    ) -> Result<Vec<ScalarExpression>, DatabaseError> {
+        let Some(items) = items else {
+            return Ok(vec![]);
+        };
+        self.bind_in_new_scope(|binder| {
+            binder
+                .context
+                .bind_table
+                .insert(table.name.clone(), (Cow::Owned(table.clone()), None));
+            let exprs = binder.normalize_select_item(items)?;
+
+            // the other tables in `FROM` or `USING` are not in the written or deleted tuples
+            if let Some(column) = exprs
+                .iter()
+                .flat_map(|expr| expr.referenced_columns(true))
+                .find(|column| column.table_name() != Some(&table.name))
+            {
+                return Err(DatabaseError::InvalidColumn(column.full_name()));
+            }
+
+            if exprs
+                .iter()
+                .any(|expr| expr.has_agg_call() || expr.has_window_call())
+            {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "aggregate and window functions are not allowed in RETURNING".to_string(),
+                ));
+            }
+            Ok(exprs)
+        })
+    }
+}
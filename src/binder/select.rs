@@ -20,7 +20,9 @@ use crate::catalog::{ColumnCatalog, ColumnSummary, TableName};
 use crate::errors::DatabaseError;
 use crate::execution::volcano::dql::join::joins_nullable;
 use crate::expression::{AliasType, BinaryOperator};
+use crate::planner::operator::except::ExceptOperator;
 use crate::planner::operator::insert::InsertOperator;
+use crate::planner::operator::intersect::IntersectOperator;
 use crate::planner::operator::join::JoinCondition;
 use crate::planner::operator::sort::{SortField, SortOperator};
 use crate::planner::operator::union::UnionOperator;
@@ -30,16 +32,22 @@ use crate::types::tuple::{Schema, SchemaRef};
 use crate::types::LogicalType;
 use itertools::Itertools;
 use sqlparser::ast::{
-    Distinct, Expr, Ident, Join, JoinConstraint, JoinOperator, Offset, OrderByExpr, Query, Select,
+    BinaryOperator as SqlBinaryOperator, Distinct, Expr, FunctionArg, FunctionArgExpr, GroupByExpr,
+    Ident, Join, JoinConstraint, JoinOperator, ObjectName, Offset, OrderByExpr, Query, Select,
     SelectInto, SelectItem, SetExpr, SetOperator, SetQuantifier, TableAlias, TableFactor,
     TableWithJoins,
 };
 
 impl<'a, T: Transaction> Binder<'a, T> {
     pub(crate) fn bind_query(&mut self, query: &Query) -> Result<LogicalPlan, DatabaseError> {
-        if let Some(_with) = &query.with {
-            // TODO support with clause.
-        }
+        let origin_ctes = if let Some(with) = &query.with {
+            let origin_ctes = self.context.ctes.clone();
+            self.bind_with(with)?;
+
+            Some(origin_ctes)
+        } else {
+            None
+        };
 
         let mut plan = match query.body.borrow() {
             SetExpr::Select(select) => self.bind_select(select, &query.order_by),
@@ -59,6 +67,10 @@ impl<'a, T: Transaction> Binder<'a, T> {
         if limit.is_some() || offset.is_some() {
             plan = self.bind_limit(plan, limit, offset)?;
         }
+        // the common table expressions are only visible in the query that defines them
+        if let Some(ctes) = origin_ctes {
+            self.context.ctes = ctes;
+        }
 
         Ok(plan)
     }
@@ -81,8 +93,14 @@ impl<'a, T: Transaction> Binder<'a, T> {
         self.extract_select_join(&mut select_list);
         self.extract_select_aggregate(&mut select_list)?;
 
-        if !select.group_by.is_empty() {
-            self.extract_group_by_aggregate(&mut select_list, &select.group_by)?;
+        match &select.group_by {
+            GroupByExpr::Expressions(exprs) if !exprs.is_empty() => {
+                self.extract_group_by_aggregate(&mut select_list, exprs)?;
+            }
+            GroupByExpr::Expressions(_) => (),
+            GroupByExpr::All => {
+                return Err(DatabaseError::UnsupportedStmt("GROUP BY ALL".to_string()))
+            }
         }
 
         let mut having_orderby = (None, None);
@@ -103,6 +121,12 @@ impl<'a, T: Transaction> Binder<'a, T> {
             plan = self.bind_having(plan, having)?;
         }
 
+        let window_calls = Self::extract_window_calls(&select_list, having_orderby.1.as_deref());
+
+        if !window_calls.is_empty() {
+            plan = self.bind_window(plan, window_calls);
+        }
+
         if let Some(Distinct::Distinct) = select.distinct {
             plan = self.bind_distinct(plan, select_list.clone());
         }
@@ -127,6 +151,9 @@ impl<'a, T: Transaction> Binder<'a, T> {
                 Operator::Insert(InsertOperator {
                     table_name: Arc::new(lower_case_name(name)?),
                     is_overwrite: false,
+                    columns: None,
+                    on_conflict: None,
+                    returning: vec![],
                 }),
                 vec![plan],
             )
@@ -145,9 +172,13 @@ impl<'a, T: Transaction> Binder<'a, T> {
         let is_all = match set_quantifier {
             SetQuantifier::All => true,
             SetQuantifier::Distinct | SetQuantifier::None => false,
+            set_quantifier => {
+                return Err(DatabaseError::UnsupportedStmt(set_quantifier.to_string()))
+            }
         };
-        let mut left_plan = self.bind_set_expr(left)?;
-        let mut right_plan = self.bind_set_expr(right)?;
+        // each side has its own tables, so that the same table can be on both sides
+        let mut left_plan = self.bind_in_new_scope(|binder| binder.bind_set_expr(left))?;
+        let mut right_plan = self.bind_in_new_scope(|binder| binder.bind_set_expr(right))?;
         let fn_eq = |left_schema: &SchemaRef, right_schema: &SchemaRef| {
             let left_len = left_schema.len();
 
@@ -204,44 +235,73 @@ impl<'a, T: Transaction> Binder<'a, T> {
                     distinct_exprs,
                 ))
             }
-            (SetOperator::Intersect, true) => {
-                todo!()
-            }
-            (SetOperator::Intersect, false) => {
-                todo!()
-            }
-            (SetOperator::Except, true) => {
-                todo!()
+            (SetOperator::Intersect, is_all) => {
+                let left_schema = left_plan.output_schema();
+                let right_schema = right_plan.output_schema();
+
+                if !fn_eq(left_schema, right_schema) {
+                    return Err(DatabaseError::MisMatch(
+                        "the output types on the left",
+                        "the output types on the right",
+                    ));
+                }
+                Ok(IntersectOperator::build(
+                    left_schema.clone(),
+                    right_schema.clone(),
+                    is_all,
+                    left_plan,
+                    right_plan,
+                ))
             }
-            (SetOperator::Except, false) => {
-                todo!()
+            (SetOperator::Except, is_all) => {
+                let left_schema = left_plan.output_schema();
+                let right_schema = right_plan.output_schema();
+
+                if !fn_eq(left_schema, right_schema) {
+                    return Err(DatabaseError::MisMatch(
+                        "the output types on the left",
+                        "the output types on the right",
+                    ));
+                }
+                Ok(ExceptOperator::build(
+                    left_schema.clone(),
+                    right_schema.clone(),
+                    is_all,
+                    left_plan,
+                    right_plan,
+                ))
             }
         }
     }
 
+    /// The tables separated by commas are cross joined
     pub(crate) fn bind_table_ref(
         &mut self,
         from: &[TableWithJoins],
     ) -> Result<LogicalPlan, DatabaseError> {
         self.context.step(QueryBindStep::From);
 
-        assert!(from.len() < 2, "not support yet.");
-        if from.is_empty() {
-            return Ok(LogicalPlan::new(Operator::Dummy, vec![]));
-        }
-
-        let TableWithJoins { relation, joins } = &from[0];
+        let mut plan: Option<LogicalPlan> = None;
 
-        let (left_name, mut plan) = self.bind_single_table_ref(relation, None)?;
+        for TableWithJoins { relation, joins } in from {
+            let join_type = plan.is_some().then_some(JoinType::Cross);
+            let (left_name, mut table_plan) = self.bind_single_table_ref(relation, join_type)?;
 
-        if !joins.is_empty() {
-            let left_name = Self::unpack_name(left_name, true);
+            if !joins.is_empty() {
+                let left_name = Self::unpack_name(left_name, true);
 
-            for join in joins {
-                plan = self.bind_join(left_name.clone(), plan, join)?;
+                for join in joins {
+                    table_plan = self.bind_join(left_name.clone(), table_plan, join)?;
+                }
             }
+            plan = Some(match plan {
+                Some(left) => {
+                    LJoinOperator::build(left, table_plan, JoinCondition::None, JoinType::Cross)
+                }
+                None => table_plan,
+            });
         }
-        Ok(plan)
+        Ok(plan.unwrap_or_else(|| LogicalPlan::new(Operator::Dummy, vec![])))
     }
 
     fn unpack_name(table_name: Option<TableName>, is_left: bool) -> TableName {
@@ -255,6 +315,35 @@ impl<'a, T: Transaction> Binder<'a, T> {
         joint_type: Option<JoinType>,
     ) -> Result<(Option<TableName>, LogicalPlan), DatabaseError> {
         let plan_with_name = match table {
+            TableFactor::Table {
+                name,
+                alias,
+                args: Some(args),
+                ..
+            } => {
+                let (table, plan) =
+                    self.bind_table_function(name, args, alias.as_ref(), joint_type)?;
+                (Some(table), plan)
+            }
+            // Tips: sqlparser parses `unnest` apart from the other table functions
+            TableFactor::UNNEST {
+                alias,
+                array_exprs,
+                with_offset: false,
+                ..
+            } => {
+                let args = array_exprs
+                    .iter()
+                    .map(|expr| FunctionArg::Unnamed(FunctionArgExpr::Expr(expr.clone())))
+                    .collect_vec();
+                let (table, plan) = self.bind_table_function(
+                    &ObjectName(vec![Ident::new("unnest")]),
+                    &args,
+                    alias.as_ref(),
+                    joint_type,
+                )?;
+                (Some(table), plan)
+            }
             TableFactor::Table { name, alias, .. } => {
                 let table_name = lower_case_name(name)?;
 
@@ -285,7 +374,7 @@ impl<'a, T: Transaction> Binder<'a, T> {
                     ((tables.len() > 1).then(|| tables.pop()).flatten(), plan)
                 }
             }
-            _ => unimplemented!(),
+            _ => return Err(DatabaseError::UnsupportedStmt(table.to_string())),
         };
 
         Ok(plan_with_name)
@@ -330,6 +419,12 @@ impl<'a, T: Transaction> Binder<'a, T> {
     ) -> Result<(Arc<String>, LogicalPlan), DatabaseError> {
         let table_name = Arc::new(table.to_string());
 
+        if let Some(plan) = self.context.cte_and_bind(table_name.clone(), join_type)? {
+            if let Some(TableAlias { name, columns }) = alias {
+                self.register_alias(columns, name.value.to_lowercase(), table_name.clone())?;
+            }
+            return Ok((table_name, plan));
+        }
         let table_catalog = self.context.table_and_bind(table_name.clone(), join_type)?;
         let scan_op = ScanOperator::build(table_name.clone(), table_catalog);
 
@@ -346,7 +441,7 @@ impl<'a, T: Transaction> Binder<'a, T> {
     /// - Qualified name with wildcard, e.g. `SELECT t.* FROM t,t1`
     /// - Scalar expression or aggregate expression, e.g. `SELECT COUNT(*) + 1 AS count FROM t`
     ///  
-    fn normalize_select_item(
+    pub(crate) fn normalize_select_item(
         &mut self,
         items: &[SelectItem],
     ) -> Result<Vec<ScalarExpression>, DatabaseError> {
@@ -435,10 +530,15 @@ impl<'a, T: Transaction> Binder<'a, T> {
             JoinOperator::RightOuter(constraint) => (JoinType::Right, Some(constraint)),
             JoinOperator::FullOuter(constraint) => (JoinType::Full, Some(constraint)),
             JoinOperator::CrossJoin => (JoinType::Cross, None),
-            _ => unimplemented!(),
+            JoinOperator::LeftSemi(constraint) => (JoinType::LeftSemi, Some(constraint)),
+            JoinOperator::LeftAnti(constraint) => (JoinType::LeftAnti, Some(constraint)),
+            JoinOperator::RightSemi(constraint) => (JoinType::RightSemi, Some(constraint)),
+            JoinOperator::RightAnti(constraint) => (JoinType::RightAnti, Some(constraint)),
+            _ => return Err(DatabaseError::UnsupportedStmt(join.to_string())),
         };
         let (right_table, right) = self.bind_single_table_ref(relation, Some(join_type))?;
         let right_table = Self::unpack_name(right_table, false);
+        let right_table_name = right_table.clone();
 
         let left_table = self
             .context
@@ -457,6 +557,19 @@ impl<'a, T: Transaction> Binder<'a, T> {
             Some(constraint) => self.bind_join_constraint(&left_table, &right_table, constraint)?,
             None => JoinCondition::None,
         };
+        // the columns of the side that is not output by the semi join and the anti join
+        // cannot be referenced after the join
+        match join_type {
+            JoinType::LeftSemi | JoinType::LeftAnti => {
+                self.context.bind_table.remove(&right_table_name);
+            }
+            JoinType::RightSemi | JoinType::RightAnti => {
+                self.context
+                    .bind_table
+                    .retain(|table_name, _| table_name == &right_table_name);
+            }
+            _ => (),
+        }
 
         Ok(LJoinOperator::build(left, right, on, join_type))
     }
@@ -468,44 +581,126 @@ impl<'a, T: Transaction> Binder<'a, T> {
     ) -> Result<LogicalPlan, DatabaseError> {
         self.context.step(QueryBindStep::Where);
 
-        let predicate = self.bind_expr(predicate)?;
+        let mut conjunctions = Vec::new();
+        Self::split_conjunctions(predicate, &mut conjunctions);
+
+        // `[NOT] EXISTS` and `[NOT] IN` with subqueries are bound to the semi joins or the anti joins
+        // after the other predicates
+        let (semi_predicates, predicates): (Vec<&Expr>, Vec<&Expr>) = conjunctions
+            .into_iter()
+            .partition(|expr| matches!(expr, Expr::Exists { .. } | Expr::InSubquery { .. }));
+        let predicates: Vec<ScalarExpression> = predicates
+            .into_iter()
+            .map(|expr| self.bind_expr(expr))
+            .try_collect()?;
+
+        if let Some(mut sub_queries) = self.context.sub_queries_at_now() {
+            let sub_query_columns = sub_queries
+                .iter_mut()
+                .flat_map(|sub_query| Vec::clone(sub_query.output_schema()))
+                .collect_vec();
+            // the predicates without subqueries are filtered before the joins
+            let (join_predicates, filter_predicates): (Vec<_>, Vec<_>) =
+                predicates.into_iter().partition(|expr| {
+                    expr.referenced_columns(true).iter().any(|column| {
+                        sub_query_columns
+                            .iter()
+                            .any(|sub_query_column| sub_query_column.summary() == column.summary())
+                    })
+                });
+            if let Some(predicate) = Self::combine_conjunctions(filter_predicates) {
+                children = FilterOperator::build(predicate, children, false);
+            }
+            let predicate = Self::combine_conjunctions(join_predicates);
 
-        if let Some(sub_queries) = self.context.sub_queries_at_now() {
             for mut sub_query in sub_queries {
                 let mut on_keys: Vec<(ScalarExpression, ScalarExpression)> = vec![];
                 let mut filter = vec![];
 
-                Self::extract_join_keys(
-                    predicate.clone(),
-                    &mut on_keys,
-                    &mut filter,
-                    children.output_schema(),
-                    sub_query.output_schema(),
-                )?;
-
-                // combine multiple filter exprs into one BinaryExpr
-                let join_filter = filter
-                    .into_iter()
-                    .reduce(|acc, expr| ScalarExpression::Binary {
-                        op: BinaryOperator::And,
-                        left_expr: Box::new(acc),
-                        right_expr: Box::new(expr),
-                        ty: LogicalType::Boolean,
-                    });
+                if let Some(predicate) = &predicate {
+                    Self::extract_join_keys(
+                        predicate.clone(),
+                        &mut on_keys,
+                        &mut filter,
+                        children.output_schema(),
+                        sub_query.output_schema(),
+                    )?;
+                }
 
                 children = LJoinOperator::build(
                     children,
                     sub_query,
                     JoinCondition::On {
                         on: on_keys,
-                        filter: join_filter,
+                        filter: Self::combine_conjunctions(filter),
                     },
                     JoinType::Inner,
                 );
             }
-            return Ok(children);
+        } else if let Some(predicate) = Self::combine_conjunctions(predicates) {
+            children = FilterOperator::build(predicate, children, false);
+        }
+        for predicate in semi_predicates {
+            children = self.bind_semi_join(children, predicate)?;
+        }
+
+        Ok(children)
+    }
+
+    /// Binds the `WHERE` of `UPDATE ... FROM` and `DELETE ... USING`, whose source tables are
+    /// joined with the target table like the subqueries, so the equi-join keys are extracted from the `WHERE`.
+    pub(crate) fn bind_where_with_sources(
+        &mut self,
+        mut children: LogicalPlan,
+        sources: &[TableWithJoins],
+        selection: &Option<Expr>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        if !sources.is_empty() {
+            let source = self.bind_table_ref(sources)?;
+
+            if selection.is_none() {
+                return Ok(LJoinOperator::build(
+                    children,
+                    source,
+                    JoinCondition::None,
+                    JoinType::Cross,
+                ));
+            }
+            self.context.step(QueryBindStep::Where);
+            self.context.sub_query(source);
         }
-        Ok(FilterOperator::build(predicate, children, false))
+        if let Some(predicate) = selection {
+            children = self.bind_where(children, predicate)?;
+        }
+
+        Ok(children)
+    }
+
+    fn split_conjunctions<'b>(expr: &'b Expr, conjunctions: &mut Vec<&'b Expr>) {
+        match expr {
+            Expr::BinaryOp {
+                left,
+                op: SqlBinaryOperator::And,
+                right,
+            } => {
+                Self::split_conjunctions(left, conjunctions);
+                Self::split_conjunctions(right, conjunctions);
+            }
+            Expr::Nested(expr) => Self::split_conjunctions(expr, conjunctions),
+            _ => conjunctions.push(expr),
+        }
+    }
+
+    // combine multiple filter exprs into one BinaryExpr
+    fn combine_conjunctions(exprs: Vec<ScalarExpression>) -> Option<ScalarExpression> {
+        exprs
+            .into_iter()
+            .reduce(|acc, expr| ScalarExpression::Binary {
+                op: BinaryOperator::And,
+                left_expr: Box::new(acc),
+                right_expr: Box::new(expr),
+                ty: LogicalType::Boolean,
+            })
     }
 
     fn bind_having(
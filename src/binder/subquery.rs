@@ -0,0 +1,141 @@
+use crate::binder::{Binder, BinderContext};
+use crate::errors::DatabaseError;
+use crate::expression::{BinaryOperator, ScalarExpression};
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::LogicalType;
+use itertools::Itertools;
+use sqlparser::ast::{Expr, Query};
+use std::mem;
+
+impl<'a, T: Transaction> Binder<'a, T> {
+    /// Binds the subquery in a new scope, where the columns of the outer queries can be referenced.
+    /// The correlated subquery is decorrelated into the join by the optimizer.
+    pub(crate) fn bind_subquery(&mut self, query: &Query) -> Result<LogicalPlan, DatabaseError> {
+        let context = &mut self.context;
+        // the tables of the nearer query are found first
+        let outer_tables = context
+            .bind_table
+            .values()
+            .map(|(table, _)| table.clone())
+            .chain(context.outer_tables.iter().cloned())
+            .collect_vec();
+        let mut binder = Binder::new(BinderContext {
+            outer_tables,
+            table_aliases: context.table_aliases.clone(),
+            temp_table_id: context.temp_table_id,
+            placeholder_types: mem::take(&mut context.placeholder_types),
+            user: context.user,
+            max_recursion_depth: context.max_recursion_depth,
+            required_privileges: mem::take(&mut context.required_privileges),
+            ctes: context.ctes.clone(),
+            ..BinderContext::new(context.transaction, context.functions)
+        });
+        let result = binder.bind_query(query);
+
+        context.temp_table_id = binder.context.temp_table_id;
+        context.placeholder_types = binder.context.placeholder_types;
+        context.required_privileges = binder.context.required_privileges;
+
+        result
+    }
+
+    /// `[NOT] EXISTS (subquery)` and `expr [NOT] IN (subquery)`
+    ///
+    /// - `EXISTS` is the semi join and `NOT EXISTS` is the anti join without conditions.
+    /// - `IN` is the semi join on the expression and the output of the subquery.
+    /// - `NOT IN` is the anti join, which also discards the tuple when either side is NULL,
+    ///   so that no tuple is output if the subquery returns NULL.
+    pub(crate) fn bind_semi_join(
+        &mut self,
+        children: LogicalPlan,
+        predicate: &Expr,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let (sub_query, on, negated) = match predicate {
+            Expr::Exists { subquery, negated } => {
+                let sub_query = self.bind_subquery(subquery)?;
+
+                (
+                    sub_query,
+                    JoinCondition::On {
+                        on: vec![],
+                        filter: None,
+                    },
+                    *negated,
+                )
+            }
+            Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => {
+                let mut left_expr = self.bind_expr(expr)?;
+                let mut sub_query = self.bind_subquery(subquery)?;
+                let sub_query_schema = sub_query.output_schema();
+
+                if sub_query_schema.len() != 1 {
+                    return Err(DatabaseError::MisMatch(
+                        "expects only one expression to be returned",
+                        "the expression returned by the subquery",
+                    ));
+                }
+                let mut right_expr = ScalarExpression::ColumnRef(sub_query_schema[0].clone());
+                let ty = LogicalType::max_logical_type(
+                    &left_expr.return_type(),
+                    &right_expr.return_type(),
+                )?;
+                for expr in [&mut left_expr, &mut right_expr] {
+                    if expr.return_type() != ty {
+                        let inner = Box::new(mem::replace(expr, ScalarExpression::Empty));
+                        *expr = ScalarExpression::TypeCast { expr: inner, ty };
+                    }
+                }
+
+                let on = if *negated {
+                    let is_null = |expr: &ScalarExpression| ScalarExpression::IsNull {
+                        negated: false,
+                        expr: Box::new(expr.clone()),
+                    };
+                    let or = |left_expr, right_expr| ScalarExpression::Binary {
+                        op: BinaryOperator::Or,
+                        left_expr: Box::new(left_expr),
+                        right_expr: Box::new(right_expr),
+                        ty: LogicalType::Boolean,
+                    };
+                    let filter = or(
+                        or(
+                            ScalarExpression::Binary {
+                                op: BinaryOperator::Eq,
+                                left_expr: Box::new(left_expr.clone()),
+                                right_expr: Box::new(right_expr.clone()),
+                                ty: LogicalType::Boolean,
+                            },
+                            is_null(&left_expr),
+                        ),
+                        is_null(&right_expr),
+                    );
+
+                    JoinCondition::On {
+                        on: vec![],
+                        filter: Some(filter),
+                    }
+                } else {
+                    JoinCondition::On {
+                        on: vec![(left_expr, right_expr)],
+                        filter: None,
+                    }
+                };
+                (sub_query, on, *negated)
+            }
+            _ => unreachable!("only EXISTS and IN with the subquery are bound to the semi join"),
+        };
+        let join_type = if negated {
+            JoinType::LeftAnti
+        } else {
+            JoinType::LeftSemi
+        };
+
+        Ok(JoinOperator::build(children, sub_query, on, join_type))
+    }
+}
@@ -0,0 +1,79 @@
+use crate::binder::{lower_case_name, Binder};
+use crate::errors::DatabaseError;
+use crate::expression::function::table::{BuiltinTableFunction, TableFunction};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::planner::operator::join::JoinType;
+use crate::planner::operator::table_function::TableFunctionOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use itertools::Itertools;
+use sqlparser::ast::{FunctionArg, FunctionArgExpr, ObjectName, TableAlias};
+use std::sync::Arc;
+
+impl<'a, T: Transaction> Binder<'a, T> {
+    /// Binds the function in `FROM`, e.g. `generate_series(1, 10) AS t(n)`,
+    /// whose arguments cannot reference the columns of the other tables.
+    pub(crate) fn bind_table_function(
+        &mut self,
+        name: &ObjectName,
+        args: &[FunctionArg],
+        alias: Option<&TableAlias>,
+        join_type: Option<JoinType>,
+    ) -> Result<(Arc<String>, LogicalPlan), DatabaseError> {
+        let function_name = lower_case_name(name)?;
+        let args: Vec<ScalarExpression> = args
+            .iter()
+            .map(|arg| match arg {
+                FunctionArg::Named {
+                    arg: FunctionArgExpr::Expr(expr),
+                    ..
+                }
+                | FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => self.bind_expr(expr),
+                _ => Err(DatabaseError::UnsupportedStmt(format!(
+                    "argument {} of the table function {}",
+                    arg, function_name
+                ))),
+            })
+            .try_collect()?;
+        if args
+            .iter()
+            .any(|arg| !arg.referenced_columns(true).is_empty())
+        {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "column references in the arguments of the table function {}",
+                function_name
+            )));
+        }
+        let summary = FunctionSummary {
+            name: function_name,
+            arg_types: args.iter().map(ScalarExpression::return_type).collect_vec(),
+        };
+        let inner = match self.context.functions.tables.get(&summary) {
+            Some(function) => function.clone(),
+            None => BuiltinTableFunction::resolve(&summary, &args)?
+                .ok_or_else(|| DatabaseError::NotFound("table function", summary.name.clone()))?,
+        };
+        let (table_name, alias_columns) = match alias {
+            Some(TableAlias { name, columns }) => {
+                (Arc::new(name.value.to_lowercase()), columns.as_slice())
+            }
+            None => (Arc::new(summary.name.clone()), [].as_slice()),
+        };
+        let table = Self::bind_cte_table(table_name.clone(), alias_columns, inner.output_schema())?;
+        let schema_ref = table.schema_ref().clone();
+        self.context.table_function_and_bind(table, join_type)?;
+
+        let plan = LogicalPlan::new(
+            Operator::TableFunction(TableFunctionOperator {
+                table_name: table_name.clone(),
+                function: TableFunction { args, inner },
+                schema_ref,
+            }),
+            vec![],
+        );
+
+        Ok((table_name, plan))
+    }
+}
@@ -1,4 +1,5 @@
 use crate::binder::{lower_case_name, Binder};
+use crate::catalog::Privilege;
 use crate::errors::DatabaseError;
 use crate::planner::operator::truncate::TruncateOperator;
 use crate::planner::operator::Operator;
@@ -13,6 +14,7 @@ impl<'a, T: Transaction> Binder<'a, T> {
         name: &ObjectName,
     ) -> Result<LogicalPlan, DatabaseError> {
         let table_name = Arc::new(lower_case_name(name)?);
+        self.context.check_privilege(&table_name, Privilege::Ddl)?;
 
         Ok(LogicalPlan::new(
             Operator::Truncate(TruncateOperator { table_name }),
@@ -1,12 +1,13 @@
 use crate::binder::{lower_case_name, Binder};
+use crate::catalog::Privilege;
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
 use crate::planner::operator::update::UpdateOperator;
 use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
-use crate::types::value::ValueRef;
-use sqlparser::ast::{Assignment, Expr, TableFactor, TableWithJoins};
+use itertools::Itertools;
+use sqlparser::ast::{Assignment, Expr, SelectItem, TableFactor, TableWithJoins};
 use std::slice;
 use std::sync::Arc;
 
@@ -14,26 +15,25 @@ impl<'a, T: Transaction> Binder<'a, T> {
     pub(crate) fn bind_update(
         &mut self,
         to: &TableWithJoins,
+        from: &[TableWithJoins],
         selection: &Option<Expr>,
         assignments: &[Assignment],
+        returning: &Option<Vec<SelectItem>>,
     ) -> Result<LogicalPlan, DatabaseError> {
         if let TableFactor::Table { name, .. } = &to.relation {
             let table_name = Arc::new(lower_case_name(name)?);
+            self.context
+                .check_privilege(&table_name, Privilege::Update)?;
+            self.context.with_statement_table(table_name.clone());
 
             let mut plan = self.bind_table_ref(slice::from_ref(to))?;
+            plan = self.bind_where_with_sources(plan, from, selection)?;
 
-            if let Some(predicate) = selection {
-                plan = self.bind_where(plan, predicate)?;
-            }
-
-            let mut columns = Vec::with_capacity(assignments.len());
-            let mut row = Vec::with_capacity(assignments.len());
+            let mut value_exprs = Vec::with_capacity(assignments.len());
 
             for assignment in assignments {
-                let value = match self.bind_expr(&assignment.value)? {
-                    ScalarExpression::Constant(value) => Ok::<ValueRef, DatabaseError>(value),
-                    _ => unreachable!(),
-                }?;
+                // Tips: the values can reference the columns of the tables in `FROM`
+                let expr = self.bind_expr(&assignment.value)?;
 
                 for ident in &assignment.id {
                     match self.bind_column_ref_from_identifiers(
@@ -41,20 +41,37 @@ impl<'a, T: Transaction> Binder<'a, T> {
                         Some(table_name.to_string()),
                     )? {
                         ScalarExpression::ColumnRef(catalog) => {
-                            value.check_len(catalog.datatype())?;
-                            columns.push(catalog);
-                            row.push(value.clone());
+                            self.infer_placeholder_type(&assignment.value, catalog.datatype());
+                            if let ScalarExpression::Constant(value) = &expr {
+                                value.check_len(catalog.datatype())?;
+                            }
+                            value_exprs.push((catalog, expr.clone()));
                         }
                         _ => unreachable!(),
                     }
                 }
             }
 
-            let values_plan = self.bind_values(vec![row], Arc::new(columns));
+            let table = self
+                .context
+                .table(table_name.clone())
+                .cloned()
+                .ok_or(DatabaseError::TableNotFound)?;
+            let returning = self.bind_returning(&table, returning)?;
+            let read_exprs = value_exprs
+                .iter()
+                .map(|(_, expr)| expr)
+                .chain(returning.iter())
+                .collect_vec();
+            self.check_read_columns(&table_name, &plan, &read_exprs)?;
 
             Ok(LogicalPlan::new(
-                Operator::Update(UpdateOperator { table_name }),
-                vec![plan, values_plan],
+                Operator::Update(UpdateOperator {
+                    table_name,
+                    value_exprs,
+                    returning,
+                }),
+                vec![plan],
             ))
         } else {
             unreachable!("only table")
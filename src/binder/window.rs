@@ -0,0 +1,276 @@
+use crate::binder::{Binder, QueryBindStep};
+use crate::errors::DatabaseError;
+use crate::expression::agg::AggKind;
+use crate::expression::function::ScalarFunction;
+use crate::expression::window::{WindowFrame, WindowFrameBound, WindowFrameUnits, WindowKind};
+use crate::expression::ScalarExpression;
+use crate::planner::operator::sort::SortField;
+use crate::planner::operator::window::WindowOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use itertools::Itertools;
+use sqlparser::ast::{
+    Expr, OrderByExpr, WindowFrame as SqlWindowFrame, WindowFrameBound as SqlWindowFrameBound,
+    WindowFrameUnits as SqlWindowFrameUnits, WindowSpec,
+};
+use std::mem;
+
+impl<'a, T: Transaction> Binder<'a, T> {
+    pub(crate) fn bind_window_call(
+        &mut self,
+        function_name: &str,
+        mut args: Vec<ScalarExpression>,
+        distinct: bool,
+        spec: &WindowSpec,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        if distinct {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "distinct in the window function: {}",
+                function_name
+            )));
+        }
+        let (kind, arg_range) = match function_name {
+            "row_number" => (WindowKind::RowNumber, 0..=0),
+            "rank" => (WindowKind::Rank, 0..=0),
+            "dense_rank" => (WindowKind::DenseRank, 0..=0),
+            "lag" => (WindowKind::Lag, 1..=3),
+            "lead" => (WindowKind::Lead, 1..=3),
+            "first_value" => (WindowKind::FirstValue, 1..=1),
+            "last_value" => (WindowKind::LastValue, 1..=1),
+            "count" => (WindowKind::Agg(AggKind::Count), 1..=1),
+            "sum" => (WindowKind::Agg(AggKind::Sum), 1..=1),
+            "min" => (WindowKind::Agg(AggKind::Min), 1..=1),
+            "max" => (WindowKind::Agg(AggKind::Max), 1..=1),
+            "avg" => (WindowKind::Agg(AggKind::Avg), 1..=1),
+            _ => {
+                return Err(DatabaseError::NotFound(
+                    "window function",
+                    function_name.to_string(),
+                ))
+            }
+        };
+        if !arg_range.contains(&args.len()) {
+            return Err(DatabaseError::MisMatch(
+                "the arguments",
+                "the window function",
+            ));
+        }
+        let ty = match &kind {
+            WindowKind::RowNumber | WindowKind::Rank | WindowKind::DenseRank => LogicalType::Bigint,
+            WindowKind::Agg(AggKind::Count) => LogicalType::Integer,
+            _ => args[0].return_type(),
+        };
+        // the default value of `LAG`/`LEAD` takes the place of the value
+        if let (WindowKind::Lag | WindowKind::Lead, Some(default)) = (&kind, args.get_mut(2)) {
+            if default.return_type() != ty {
+                let expr = Box::new(mem::replace(default, ScalarExpression::Empty));
+                *default = ScalarExpression::TypeCast { expr, ty };
+            }
+        }
+        let partition_by = spec
+            .partition_by
+            .iter()
+            .map(|expr| self.bind_expr(expr))
+            .try_collect()?;
+        let order_by = spec
+            .order_by
+            .iter()
+            .map(
+                |OrderByExpr {
+                     expr,
+                     asc,
+                     nulls_first,
+                 }| {
+                    Ok::<SortField, DatabaseError>(SortField::new(
+                        self.bind_expr(expr)?,
+                        asc.map_or(true, |asc| asc),
+                        nulls_first.map_or(true, |first| first),
+                    ))
+                },
+            )
+            .try_collect::<_, Vec<_>, _>()?;
+        let frame = match &spec.window_frame {
+            Some(frame) => self.bind_window_frame(frame)?,
+            None => WindowFrame::default_with(!order_by.is_empty()),
+        };
+
+        Ok(ScalarExpression::WindowCall {
+            kind,
+            args,
+            partition_by,
+            order_by,
+            frame,
+            ty,
+        })
+    }
+
+    /// Only the offsets of `ROWS` frames are supported, which must be non-negative integers.
+    fn bind_window_frame(&mut self, frame: &SqlWindowFrame) -> Result<WindowFrame, DatabaseError> {
+        let units = match frame.units {
+            SqlWindowFrameUnits::Rows => WindowFrameUnits::Rows,
+            SqlWindowFrameUnits::Range => WindowFrameUnits::Range,
+            SqlWindowFrameUnits::Groups => {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "window frame in GROUPS mode".to_string(),
+                ))
+            }
+        };
+        let start = self.bind_window_frame_bound(&frame.start_bound)?;
+        let end = match &frame.end_bound {
+            Some(bound) => self.bind_window_frame_bound(bound)?,
+            None => WindowFrameBound::CurrentRow,
+        };
+        if matches!(start, WindowFrameBound::UnboundedFollowing)
+            || matches!(end, WindowFrameBound::UnboundedPreceding)
+        {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "window frame between {} and {}",
+                start, end
+            )));
+        }
+        if units == WindowFrameUnits::Range
+            && [start, end].iter().any(|bound| {
+                matches!(
+                    bound,
+                    WindowFrameBound::Preceding(_) | WindowFrameBound::Following(_)
+                )
+            })
+        {
+            return Err(DatabaseError::UnsupportedStmt(
+                "window frame in RANGE mode with offset".to_string(),
+            ));
+        }
+
+        Ok(WindowFrame { units, start, end })
+    }
+
+    fn bind_window_frame_bound(
+        &mut self,
+        bound: &SqlWindowFrameBound,
+    ) -> Result<WindowFrameBound, DatabaseError> {
+        Ok(match bound {
+            SqlWindowFrameBound::CurrentRow => WindowFrameBound::CurrentRow,
+            SqlWindowFrameBound::Preceding(None) => WindowFrameBound::UnboundedPreceding,
+            SqlWindowFrameBound::Preceding(Some(expr)) => {
+                WindowFrameBound::Preceding(self.bind_frame_offset(expr)?)
+            }
+            SqlWindowFrameBound::Following(None) => WindowFrameBound::UnboundedFollowing,
+            SqlWindowFrameBound::Following(Some(expr)) => {
+                WindowFrameBound::Following(self.bind_frame_offset(expr)?)
+            }
+        })
+    }
+
+    fn bind_frame_offset(&mut self, expr: &Expr) -> Result<u64, DatabaseError> {
+        match self.bind_expr(expr)? {
+            ScalarExpression::Constant(dv) => match dv.as_ref() {
+                DataValue::Int32(Some(v)) if *v >= 0 => Ok(*v as u64),
+                DataValue::Int64(Some(v)) if *v >= 0 => Ok(*v as u64),
+                _ => Err(DatabaseError::InvalidType),
+            },
+            _ => Err(DatabaseError::InvalidColumn(
+                "invalid window frame offset.".to_owned(),
+            )),
+        }
+    }
+
+    pub(crate) fn bind_window(
+        &mut self,
+        children: LogicalPlan,
+        window_calls: Vec<ScalarExpression>,
+    ) -> LogicalPlan {
+        self.context.step(QueryBindStep::Window);
+
+        WindowOperator::build(children, window_calls)
+    }
+
+    /// Collects the window functions of the select list and the ORDER BY clause.
+    pub(crate) fn extract_window_calls(
+        select_items: &[ScalarExpression],
+        orderby: Option<&[SortField]>,
+    ) -> Vec<ScalarExpression> {
+        let mut window_calls = Vec::new();
+
+        for expr in select_items
+            .iter()
+            .chain(orderby.unwrap_or_default().iter().map(|field| &field.expr))
+        {
+            Self::visit_window_call(expr, &mut window_calls);
+        }
+        window_calls
+    }
+
+    fn visit_window_call(expr: &ScalarExpression, window_calls: &mut Vec<ScalarExpression>) {
+        match expr {
+            ScalarExpression::WindowCall { .. } => {
+                if !window_calls.contains(expr) {
+                    window_calls.push(expr.clone());
+                }
+            }
+            ScalarExpression::Alias { expr, .. }
+            | ScalarExpression::TypeCast { expr, .. }
+            | ScalarExpression::IsNull { expr, .. }
+            | ScalarExpression::Unary { expr, .. } => Self::visit_window_call(expr, window_calls),
+            ScalarExpression::Binary {
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                Self::visit_window_call(left_expr, window_calls);
+                Self::visit_window_call(right_expr, window_calls);
+            }
+            ScalarExpression::In { expr, args, .. } => {
+                Self::visit_window_call(expr, window_calls);
+                for arg in args {
+                    Self::visit_window_call(arg, window_calls);
+                }
+            }
+            ScalarExpression::Between {
+                expr,
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                Self::visit_window_call(expr, window_calls);
+                Self::visit_window_call(left_expr, window_calls);
+                Self::visit_window_call(right_expr, window_calls);
+            }
+            ScalarExpression::SubString {
+                expr,
+                for_expr,
+                from_expr,
+            } => {
+                Self::visit_window_call(expr, window_calls);
+                if let Some(expr) = for_expr {
+                    Self::visit_window_call(expr, window_calls);
+                }
+                if let Some(expr) = from_expr {
+                    Self::visit_window_call(expr, window_calls);
+                }
+            }
+            ScalarExpression::AggCall { args, .. }
+            | ScalarExpression::Tuple(args)
+            | ScalarExpression::Function(ScalarFunction { args, .. }) => {
+                for arg in args {
+                    Self::visit_window_call(arg, window_calls);
+                }
+            }
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ..
+            } => {
+                for expr in ScalarExpression::case_when_exprs(operand_expr, expr_pairs, else_expr) {
+                    Self::visit_window_call(expr, window_calls);
+                }
+            }
+            ScalarExpression::Constant(_)
+            | ScalarExpression::ColumnRef(_)
+            | ScalarExpression::Placeholder { .. } => (),
+            ScalarExpression::Reference { .. } | ScalarExpression::Empty => unreachable!(),
+        }
+    }
+}
@@ -96,7 +96,8 @@ impl ColumnCatalog {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
 pub struct ColumnDesc {
     pub(crate) column_datatype: LogicalType,
-    pub(crate) is_primary: bool,
+    /// The position of the column in the declared primary key, e.g. `PRIMARY KEY (b, a)`
+    pub(crate) primary: Option<usize>,
     pub(crate) is_unique: bool,
     pub(crate) default: Option<ValueRef>,
 }
@@ -108,15 +109,21 @@ impl ColumnDesc {
         is_unique: bool,
         default: Option<ValueRef>,
     ) -> ColumnDesc {
+        let primary = if is_primary { Some(0) } else { None };
+
         ColumnDesc {
             column_datatype,
-            is_primary,
+            primary,
             is_unique,
             default,
         }
     }
 
+    pub(crate) fn is_primary(&self) -> bool {
+        self.primary.is_some()
+    }
+
     pub(crate) fn is_index(&self) -> bool {
-        self.is_unique || self.is_primary
+        self.is_unique || self.is_primary()
     }
 }
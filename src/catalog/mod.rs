@@ -2,6 +2,8 @@
 
 pub(crate) use self::column::*;
 pub(crate) use self::table::*;
+pub use self::user::*;
 
 mod column;
 mod table;
+mod user;
@@ -30,10 +30,48 @@ pub struct TableMeta {
 }
 
 impl TableCatalog {
-    pub(crate) fn get_unique_index(&self, col_id: &ColumnId) -> Option<&IndexMetaRef> {
+    /// The unique index on exactly the columns, in the order of the index
+    pub(crate) fn get_unique_index(&self, col_ids: &[ColumnId]) -> Option<&IndexMetaRef> {
         self.indexes
             .iter()
-            .find(|meta| meta.is_unique && &meta.column_ids[0] == col_id)
+            .find(|meta| meta.is_unique && meta.column_ids == col_ids)
+    }
+
+    /// Indexes of the table except the primary key, including the unique and composite ones
+    pub(crate) fn secondary_indexes(&self) -> impl Iterator<Item = &IndexMetaRef> {
+        self.indexes.iter().filter(|meta| !meta.is_primary)
+    }
+
+    pub(crate) fn get_index_by_name(&self, name: &str) -> Option<&IndexMetaRef> {
+        self.indexes.iter().find(|meta| meta.name == name)
+    }
+
+    /// Positions of the index columns in the schema of the table
+    pub(crate) fn index_positions(
+        &self,
+        index_meta: &IndexMeta,
+    ) -> Result<Vec<usize>, DatabaseError> {
+        index_meta
+            .column_ids
+            .iter()
+            .map(|col_id| {
+                self.columns.get(col_id).cloned().ok_or_else(|| {
+                    DatabaseError::NotFound("column", format!("{} of {}", col_id, index_meta.name))
+                })
+            })
+            .try_collect()
+    }
+
+    /// All indexes that cover the column
+    pub(crate) fn indexes_by_column(
+        &self,
+        col_id: &ColumnId,
+    ) -> impl Iterator<Item = &IndexMetaRef> {
+        let col_id = *col_id;
+
+        self.indexes
+            .iter()
+            .filter(move |meta| meta.column_ids.contains(&col_id))
     }
 
     #[allow(dead_code)]
@@ -41,7 +79,6 @@ impl TableCatalog {
         self.columns.get(id).map(|i| &self.schema_ref[*i])
     }
 
-    #[allow(dead_code)]
     pub(crate) fn get_column_id_by_name(&self, name: &str) -> Option<ColumnId> {
         self.column_idxs.get(name).map(|(id, _)| id).cloned()
     }
@@ -68,12 +105,21 @@ impl TableCatalog {
         self.columns.len()
     }
 
-    pub(crate) fn primary_key(&self) -> Result<(usize, &ColumnRef), DatabaseError> {
-        self.schema_ref
+    /// Primary key columns in declaration order, a composite primary key has more than one
+    pub(crate) fn primary_keys(&self) -> Result<Vec<(usize, &ColumnRef)>, DatabaseError> {
+        let primary_keys = self
+            .schema_ref
             .iter()
             .enumerate()
-            .find(|(_, column)| column.desc.is_primary)
-            .ok_or(DatabaseError::PrimaryKeyNotFound)
+            .filter_map(|(i, column)| column.desc.primary.map(|pos| (pos, (i, column))))
+            .sorted_by_key(|(pos, _)| *pos)
+            .map(|(_, primary_key)| primary_key)
+            .collect_vec();
+
+        if primary_keys.is_empty() {
+            return Err(DatabaseError::PrimaryKeyNotFound);
+        }
+        Ok(primary_keys)
     }
 
     pub(crate) fn types(&self) -> Vec<LogicalType> {
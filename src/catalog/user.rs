@@ -0,0 +1,116 @@
+use pbkdf2::pbkdf2_hmac;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fmt;
+use std::fmt::Formatter;
+
+/// Iterations of the SCRAM-SHA-256 authentication
+pub const SCRAM_ITERATIONS: usize = 4096;
+const SALT_LEN: usize = 16;
+const SALTED_PASSWORD_LEN: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UserCatalog {
+    pub name: String,
+    /// The user without password cannot pass the password authentication
+    pub password: Option<UserPassword>,
+    /// The superuser is not restricted by privileges, and manages the tables, users and privileges
+    pub is_admin: bool,
+}
+
+/// Only the salted password of SCRAM-SHA-256 is persisted, never the plaintext.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UserPassword {
+    pub salt: Vec<u8>,
+    pub salted_password: Vec<u8>,
+}
+
+impl UserCatalog {
+    pub fn new(name: String, password: Option<UserPassword>, is_admin: bool) -> Self {
+        UserCatalog {
+            name,
+            password,
+            is_admin,
+        }
+    }
+}
+
+impl UserPassword {
+    pub fn new(password: &str) -> Self {
+        let salt: [u8; SALT_LEN] = rand::random();
+
+        Self::with_salt(password, salt.to_vec())
+    }
+
+    /// `Hi(Normalize(password), salt, i)` of RFC 5802
+    pub fn with_salt(password: &str, salt: Vec<u8>) -> Self {
+        // same as PostgreSQL, the password is used as it is if it cannot be normalized
+        let password = stringprep::saslprep(password)
+            .map(|password| password.to_string())
+            .unwrap_or_else(|_| password.to_string());
+        let mut salted_password = vec![0; SALTED_PASSWORD_LEN];
+        pbkdf2_hmac::<Sha256>(
+            password.as_bytes(),
+            &salt,
+            SCRAM_ITERATIONS as u32,
+            &mut salted_password,
+        );
+
+        UserPassword {
+            salt,
+            salted_password,
+        }
+    }
+
+    pub fn verify(&self, password: &str) -> bool {
+        Self::with_salt(password, self.salt.clone()).salted_password == self.salted_password
+    }
+}
+
+/// The privileges granted to a user on a table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Privilege {
+    Select,
+    Insert,
+    Update,
+    Delete,
+    /// `ALTER TABLE`, `DROP TABLE`, `TRUNCATE`, `ANALYZE` and the indexes of the table
+    Ddl,
+}
+
+impl Privilege {
+    pub const ALL: [Privilege; 5] = [
+        Privilege::Select,
+        Privilege::Insert,
+        Privilege::Update,
+        Privilege::Delete,
+        Privilege::Ddl,
+    ];
+}
+
+impl fmt::Display for Privilege {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Privilege::Select => write!(f, "SELECT"),
+            Privilege::Insert => write!(f, "INSERT"),
+            Privilege::Update => write!(f, "UPDATE"),
+            Privilege::Delete => write!(f, "DELETE"),
+            Privilege::Ddl => write!(f, "DDL"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::UserPassword;
+
+    #[test]
+    fn test_user_password() {
+        let password = UserPassword::new("pencil");
+
+        assert_ne!(password.salted_password, b"pencil".to_vec());
+        assert!(password.verify("pencil"));
+        assert!(!password.verify("pen"));
+        assert_ne!(UserPassword::new("pencil").salt, password.salt);
+    }
+}
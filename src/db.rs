@@ -1,23 +1,37 @@
 use ahash::HashMap;
+use futures::TryStreamExt;
+use futures_async_stream::try_stream;
 use sqlparser::ast::Statement;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use crate::binder::{Binder, BinderContext};
+use crate::binder::{Binder, BinderContext, RequiredPrivilege};
 use crate::errors::DatabaseError;
-use crate::execution::volcano::{build_write, try_collect};
+use crate::execution::volcano::{build_write, try_collect, BoxedExecutor};
+use crate::expression::agg::AggregateFunctionImpl;
+use crate::expression::function::table::TableFunctionImpl;
 use crate::expression::function::{FunctionSummary, ScalarFunctionImpl};
+use crate::optimizer::core::column_meta::ColumnMetaLoader;
 use crate::optimizer::heuristic::batch::HepBatchStrategy;
 use crate::optimizer::heuristic::optimizer::HepOptimizer;
 use crate::optimizer::rule::implementation::ImplementationRuleImpl;
-use crate::optimizer::rule::normalization::NormalizationRuleImpl;
+use crate::optimizer::rule::normalization::{NormalizationRuleImpl, PushPredicateIntoScan};
 use crate::parser::parse_sql;
+use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::kip::KipStorage;
 use crate::storage::{Storage, Transaction};
-use crate::types::tuple::Tuple;
-
-pub(crate) type Functions = HashMap<FunctionSummary, Arc<dyn ScalarFunctionImpl>>;
+use crate::types::tuple::{SchemaRef, Tuple};
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+
+/// The functions registered by `DataBaseBuilder`, which are looked up by their summaries
+#[derive(Default)]
+pub struct Functions {
+    pub(crate) scalars: HashMap<FunctionSummary, Arc<dyn ScalarFunctionImpl>>,
+    pub(crate) aggregates: HashMap<FunctionSummary, Arc<dyn AggregateFunctionImpl>>,
+    pub(crate) tables: HashMap<FunctionSummary, Arc<dyn TableFunctionImpl>>,
+}
 
 #[derive(Copy, Clone)]
 pub enum QueryExecute {
@@ -25,9 +39,13 @@ pub enum QueryExecute {
     Codegen,
 }
 
+/// The times the recursive term of `WITH RECURSIVE` can be evaluated by default, as MySQL does
+pub const DEFAULT_MAX_RECURSION_DEPTH: usize = 1000;
+
 pub struct DataBaseBuilder {
     path: PathBuf,
     functions: Functions,
+    max_recursion_depth: usize,
 }
 
 impl DataBaseBuilder {
@@ -35,13 +53,34 @@ impl DataBaseBuilder {
         DataBaseBuilder {
             path: path.into(),
             functions: Default::default(),
+            max_recursion_depth: DEFAULT_MAX_RECURSION_DEPTH,
         }
     }
 
+    /// `WITH RECURSIVE` fails once its recursive term has been evaluated for the times.
+    pub fn max_recursion_depth(mut self, max_recursion_depth: usize) -> Self {
+        self.max_recursion_depth = max_recursion_depth;
+        self
+    }
+
     pub fn register_function(mut self, function: Arc<dyn ScalarFunctionImpl>) -> Self {
         let summary = function.summary().clone();
 
-        self.functions.insert(summary, function);
+        self.functions.scalars.insert(summary, function);
+        self
+    }
+
+    pub fn register_aggregate_function(mut self, function: Arc<dyn AggregateFunctionImpl>) -> Self {
+        let summary = function.summary().clone();
+
+        self.functions.aggregates.insert(summary, function);
+        self
+    }
+
+    pub fn register_table_function(mut self, function: Arc<dyn TableFunctionImpl>) -> Self {
+        let summary = function.summary().clone();
+
+        self.functions.tables.insert(summary, function);
         self
     }
 
@@ -51,6 +90,8 @@ impl DataBaseBuilder {
         Ok(Database {
             storage,
             functions: Arc::new(self.functions),
+            user: None,
+            max_recursion_depth: self.max_recursion_depth,
         })
     }
 }
@@ -58,6 +99,9 @@ impl DataBaseBuilder {
 pub struct Database<S: Storage> {
     pub storage: S,
     functions: Arc<Functions>,
+    /// The privileges of the user are checked while binding, none for the administrator
+    user: Option<String>,
+    max_recursion_depth: usize,
 }
 
 impl Database<KipStorage> {
@@ -98,11 +142,26 @@ impl Database<KipStorage> {
 }
 
 impl<S: Storage> Database<S> {
+    /// The same database, whose SQL is run as the user.
+    pub fn with_user(&self, user: Option<String>) -> Self {
+        Database {
+            storage: self.storage.clone(),
+            functions: self.functions.clone(),
+            user,
+            max_recursion_depth: self.max_recursion_depth,
+        }
+    }
+
     /// Run SQL queries.
     pub async fn run<T: AsRef<str>>(&self, sql: T) -> Result<Vec<Tuple>, DatabaseError> {
         let transaction = self.storage.transaction().await?;
-        let (plan, _) =
-            Self::build_plan::<T, S::TransactionType>(sql, &transaction, &self.functions)?;
+        let (plan, _) = Self::build_plan::<T, S::TransactionType>(
+            sql,
+            &transaction,
+            &self.functions,
+            self.user.as_deref(),
+            self.max_recursion_depth,
+        )?;
 
         Self::run_volcano(transaction, plan).await
     }
@@ -120,26 +179,100 @@ impl<S: Storage> Database<S> {
         Ok(tuples)
     }
 
+    /// Run SQL queries, and yield the tuples as they are produced.
+    ///
+    /// The transaction is held by the stream, and committed once the stream is exhausted.
+    /// Dropping the stream before that discards the transaction.
+    pub async fn stream<T: AsRef<str>>(
+        &self,
+        sql: T,
+    ) -> Result<BoxedExecutor<'static>, DatabaseError> {
+        let transaction = self.storage.transaction().await?;
+        let (plan, _) = Self::build_plan::<T, S::TransactionType>(
+            sql,
+            &transaction,
+            &self.functions,
+            self.user.as_deref(),
+            self.max_recursion_depth,
+        )?;
+
+        Ok(Box::pin(Self::stream_volcano(transaction, plan)))
+    }
+
+    /// The streaming version of `Database::execute`.
+    pub async fn stream_prepared(
+        &self,
+        statement: &PreparedStatement,
+        params: &[DataValue],
+    ) -> Result<BoxedExecutor<'static>, DatabaseError> {
+        let transaction = self.storage.transaction().await?;
+        let plan =
+            Self::build_prepared_plan(statement, params, &transaction, self.user.as_deref())?;
+
+        Ok(Box::pin(Self::stream_volcano(transaction, plan)))
+    }
+
+    #[try_stream(ok = Tuple, error = DatabaseError)]
+    async fn stream_volcano(mut transaction: <S as Storage>::TransactionType, plan: LogicalPlan) {
+        let mut stream = build_write(plan, &mut transaction);
+
+        while let Some(tuple) = stream.try_next().await? {
+            yield tuple;
+        }
+        drop(stream);
+        transaction.commit().await?;
+    }
+
     pub async fn new_transaction(&self) -> Result<DBTransaction<S>, DatabaseError> {
         let transaction = self.storage.transaction().await?;
 
         Ok(DBTransaction {
             inner: transaction,
             functions: self.functions.clone(),
+            user: self.user.clone(),
+            max_recursion_depth: self.max_recursion_depth,
         })
     }
 
+    /// Parse and bind the SQL once, the values of its `$n` or `?` parameters are given at execution.
+    pub async fn prepare<T: AsRef<str>>(&self, sql: T) -> Result<PreparedStatement, DatabaseError> {
+        let transaction = self.storage.transaction().await?;
+
+        Self::prepare_statement(
+            sql,
+            &transaction,
+            &self.functions,
+            self.user.as_deref(),
+            self.max_recursion_depth,
+        )
+    }
+
+    /// Run the prepared statement with the values of its parameters.
+    pub async fn execute(
+        &self,
+        statement: &PreparedStatement,
+        params: &[DataValue],
+    ) -> Result<Vec<Tuple>, DatabaseError> {
+        let transaction = self.storage.transaction().await?;
+        let plan =
+            Self::build_prepared_plan(statement, params, &transaction, self.user.as_deref())?;
+
+        Self::run_volcano(transaction, plan).await
+    }
+
     pub fn build_plan<V: AsRef<str>, T: Transaction>(
         sql: V,
         transaction: &<S as Storage>::TransactionType,
         functions: &Functions,
+        user: Option<&str>,
+        max_recursion_depth: usize,
     ) -> Result<(LogicalPlan, Statement), DatabaseError> {
-        // parse
-        let mut stmts = parse_sql(sql)?;
-        if stmts.is_empty() {
-            return Err(DatabaseError::EmptyStatement);
-        }
-        let mut binder = Binder::new(BinderContext::new(transaction, functions));
+        let statement = Self::parse_statement(sql)?;
+        let mut binder = Binder::new(
+            BinderContext::new(transaction, functions)
+                .with_user(user)
+                .with_max_recursion_depth(max_recursion_depth),
+        );
         /// Build a logical plan.
         ///
         /// SELECT a,b FROM t1 ORDER BY a LIMIT 1;
@@ -147,18 +280,120 @@ impl<S: Storage> Database<S> {
         ///   Sort(a)
         ///     Limit(1)
         ///       Project(a,b)
-        let source_plan = binder.bind(&stmts[0])?;
+        let source_plan = binder.bind(&statement)?;
         // println!("source_plan plan: {:#?}", source_plan);
 
         let best_plan =
             Self::default_optimizer(source_plan).find_best(Some(&transaction.meta_loader()))?;
         // println!("best_plan plan: {:#?}", best_plan);
 
-        Ok((best_plan, stmts.remove(0)))
+        Ok((best_plan, statement))
+    }
+
+    fn parse_statement<V: AsRef<str>>(sql: V) -> Result<Statement, DatabaseError> {
+        let mut stmts = parse_sql(sql)?;
+        if stmts.is_empty() {
+            return Err(DatabaseError::EmptyStatement);
+        }
+        Ok(stmts.remove(0))
+    }
+
+    pub(crate) fn prepare_statement<V: AsRef<str>>(
+        sql: V,
+        transaction: &<S as Storage>::TransactionType,
+        functions: &Functions,
+        user: Option<&str>,
+        max_recursion_depth: usize,
+    ) -> Result<PreparedStatement, DatabaseError> {
+        let statement = Self::parse_statement(sql)?;
+        // the parameters are bound to the placeholders, whose values are bound when executing
+        let mut binder = Binder::new(
+            BinderContext::new(transaction, functions)
+                .with_user(user)
+                .with_max_recursion_depth(max_recursion_depth),
+        );
+        let mut source_plan = binder.bind(&statement)?;
+        let parameter_types = binder.parameter_types();
+        let required_privileges = binder.required_privileges().to_vec();
+        let schema_ref = source_plan.output_schema().clone();
+        let best_plan =
+            Self::default_optimizer(source_plan).find_best(Some(&transaction.meta_loader()))?;
+
+        Ok(PreparedStatement {
+            statement,
+            best_plan,
+            parameter_types,
+            required_privileges,
+            schema_ref,
+        })
+    }
+
+    /// The privileges are checked again, as they may have been revoked since the statement was prepared.
+    pub(crate) fn build_prepared_plan(
+        statement: &PreparedStatement,
+        params: &[DataValue],
+        transaction: &<S as Storage>::TransactionType,
+        user: Option<&str>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        for required in statement.required_privileges.iter() {
+            required.check(transaction, user)?;
+        }
+        if params.len() != statement.parameter_types.len() {
+            return Err(DatabaseError::ValuesLenMismatch(
+                statement.parameter_types.len(),
+                params.len(),
+            ));
+        }
+        let mut best_plan = statement.best_plan.clone();
+        if params.is_empty() {
+            return Ok(best_plan);
+        }
+        // the values are cast to the types inferred when preparing
+        let args = params
+            .iter()
+            .zip(statement.parameter_types.iter())
+            .map(|(value, ty)| {
+                let value = if matches!(ty, LogicalType::SqlNull) {
+                    value.clone()
+                } else {
+                    value.clone().cast(ty)?
+                };
+                Ok(Arc::new(value))
+            })
+            .collect::<Result<Vec<ValueRef>, DatabaseError>>()?;
+        Self::bind_placeholders(&mut best_plan, &args, &transaction.meta_loader())?;
+
+        Ok(best_plan)
+    }
+
+    /// The scan under the filter with the parameters is chosen again once they are bound,
+    /// as the ranges of its indexes depend on the values.
+    fn bind_placeholders<T: Transaction>(
+        plan: &mut LogicalPlan,
+        params: &[ValueRef],
+        loader: &ColumnMetaLoader<'_, T>,
+    ) -> Result<(), DatabaseError> {
+        for child in plan.childrens.iter_mut() {
+            Self::bind_placeholders(child, params, loader)?;
+        }
+        if plan.operator.bind_placeholders(params)? {
+            if let (Operator::Filter(op), Some(child)) =
+                (&plan.operator, plan.childrens.first_mut())
+            {
+                PushPredicateIntoScan::bind_scan(&op.predicate, child, loader)?;
+            }
+        }
+
+        Ok(())
     }
 
     pub(crate) fn default_optimizer(source_plan: LogicalPlan) -> HepOptimizer {
         HepOptimizer::new(source_plan)
+            .batch(
+                "Decorrelate Subquery".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![NormalizationRuleImpl::PullUpCorrelatedPredicates],
+            )
             .batch(
                 "Column Pruning".to_string(),
                 HepBatchStrategy::once_topdown(),
@@ -199,6 +434,11 @@ impl<S: Storage> Database<S> {
                     NormalizationRuleImpl::EliminateLimits,
                 ],
             )
+            .batch(
+                "Join Reorder".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![NormalizationRuleImpl::JoinReorder],
+            )
             .batch(
                 "Expression Remapper".to_string(),
                 HepBatchStrategy::once_topdown(),
@@ -211,12 +451,18 @@ impl<S: Storage> Database<S> {
                 ImplementationRuleImpl::Dummy,
                 ImplementationRuleImpl::Filter,
                 ImplementationRuleImpl::HashJoin,
+                ImplementationRuleImpl::NestedLoopJoin,
+                ImplementationRuleImpl::SortMergeJoin,
+                ImplementationRuleImpl::HashIntersect,
+                ImplementationRuleImpl::HashExcept,
                 ImplementationRuleImpl::Limit,
                 ImplementationRuleImpl::Projection,
                 ImplementationRuleImpl::SeqScan,
                 ImplementationRuleImpl::IndexScan,
                 ImplementationRuleImpl::Sort,
+                ImplementationRuleImpl::TableFunction,
                 ImplementationRuleImpl::Values,
+                ImplementationRuleImpl::Window,
                 // DML
                 ImplementationRuleImpl::Analyze,
                 ImplementationRuleImpl::CopyFromFile,
@@ -226,28 +472,114 @@ impl<S: Storage> Database<S> {
                 ImplementationRuleImpl::Update,
                 // DLL
                 ImplementationRuleImpl::AddColumn,
+                ImplementationRuleImpl::AlterUser,
+                ImplementationRuleImpl::CreateIndex,
                 ImplementationRuleImpl::CreateTable,
+                ImplementationRuleImpl::CreateUser,
                 ImplementationRuleImpl::DropColumn,
+                ImplementationRuleImpl::DropIndex,
                 ImplementationRuleImpl::DropTable,
+                ImplementationRuleImpl::DropUser,
+                ImplementationRuleImpl::Grant,
+                ImplementationRuleImpl::Revoke,
                 ImplementationRuleImpl::Truncate,
             ])
     }
 }
 
+/// A statement parsed, bound and optimized once, and executed with the values of its `$n` or `?` parameters.
+#[derive(Debug, Clone)]
+pub struct PreparedStatement {
+    statement: Statement,
+    /// The plan with the placeholders of the parameters, whose values are bound when executing
+    best_plan: LogicalPlan,
+    /// `LogicalType::SqlNull` if the type of the parameter cannot be inferred
+    pub parameter_types: Vec<LogicalType>,
+    required_privileges: Vec<RequiredPrivilege>,
+    pub schema_ref: SchemaRef,
+}
+
+impl PreparedStatement {
+    pub fn statement(&self) -> &Statement {
+        &self.statement
+    }
+}
+
 pub struct DBTransaction<S: Storage> {
     inner: S::TransactionType,
     functions: Arc<Functions>,
+    user: Option<String>,
+    max_recursion_depth: usize,
 }
 
 impl<S: Storage> DBTransaction<S> {
     pub async fn run<T: AsRef<str>>(&mut self, sql: T) -> Result<Vec<Tuple>, DatabaseError> {
-        let (plan, _) =
-            Database::<S>::build_plan::<T, S::TransactionType>(sql, &self.inner, &self.functions)?;
+        let (plan, _) = Database::<S>::build_plan::<T, S::TransactionType>(
+            sql,
+            &self.inner,
+            &self.functions,
+            self.user.as_deref(),
+            self.max_recursion_depth,
+        )?;
+        let mut stream = build_write(plan, &mut self.inner);
+
+        try_collect(&mut stream).await
+    }
+
+    /// Run SQL queries in the transaction, and yield the tuples as they are produced.
+    pub fn stream<T: AsRef<str>>(&mut self, sql: T) -> Result<BoxedExecutor, DatabaseError> {
+        let (plan, _) = Database::<S>::build_plan::<T, S::TransactionType>(
+            sql,
+            &self.inner,
+            &self.functions,
+            self.user.as_deref(),
+            self.max_recursion_depth,
+        )?;
+
+        Ok(build_write(plan, &mut self.inner))
+    }
+
+    pub fn prepare<T: AsRef<str>>(&self, sql: T) -> Result<PreparedStatement, DatabaseError> {
+        Database::<S>::prepare_statement(
+            sql,
+            &self.inner,
+            &self.functions,
+            self.user.as_deref(),
+            self.max_recursion_depth,
+        )
+    }
+
+    pub async fn execute(
+        &mut self,
+        statement: &PreparedStatement,
+        params: &[DataValue],
+    ) -> Result<Vec<Tuple>, DatabaseError> {
+        let plan = Database::<S>::build_prepared_plan(
+            statement,
+            params,
+            &self.inner,
+            self.user.as_deref(),
+        )?;
         let mut stream = build_write(plan, &mut self.inner);
 
         try_collect(&mut stream).await
     }
 
+    pub fn stream_prepared(
+        &mut self,
+        statement: &PreparedStatement,
+        params: &[DataValue],
+    ) -> Result<BoxedExecutor, DatabaseError> {
+        let plan = Database::<S>::build_prepared_plan(
+            statement,
+            params,
+            &self.inner,
+            self.user.as_deref(),
+        )?;
+
+        Ok(build_write(plan, &mut self.inner))
+    }
+
     pub async fn commit(self) -> Result<(), DatabaseError> {
         self.inner.commit().await?;
 
@@ -258,15 +590,23 @@ impl<S: Storage> DBTransaction<S> {
 #[cfg(test)]
 mod test {
     use crate::catalog::{ColumnCatalog, ColumnDesc};
-    use crate::db::{DataBaseBuilder, DatabaseError, QueryExecute};
+    use crate::db::{DataBaseBuilder, Database, DatabaseError, QueryExecute};
+    use crate::expression::agg::AggregateFunctionImpl;
     use crate::expression::function::{FuncMonotonicity, FunctionSummary, ScalarFunctionImpl};
+    use crate::expression::simplify::ConstantBinary;
     use crate::expression::ScalarExpression;
     use crate::expression::{BinaryOperator, UnaryOperator};
     use crate::function;
+    use crate::planner::operator::filter::FilterOperator;
+    use crate::planner::operator::{Operator, PhysicalOption};
+    use crate::storage::kip::KipStorage;
     use crate::storage::{Storage, Transaction};
+    use crate::types::index::IndexInfo;
     use crate::types::tuple::{create_table, Tuple};
+    use crate::types::tuple_builder::AFFECTED_ROWS;
     use crate::types::value::{DataValue, ValueRef};
     use crate::types::LogicalType;
+    use futures::TryStreamExt;
     use std::sync::Arc;
     use tempfile::TempDir;
 
@@ -326,6 +666,99 @@ mod test {
         Ok(())
     }
 
+    /// The product of the values which are not NULL
+    #[derive(Debug)]
+    struct ProductAggregate {
+        summary: FunctionSummary,
+    }
+
+    impl AggregateFunctionImpl for ProductAggregate {
+        fn init_state(&self) -> DataValue {
+            DataValue::Int64(None)
+        }
+
+        fn update(&self, state: &mut DataValue, args: &[ValueRef]) -> Result<(), DatabaseError> {
+            self.merge(
+                state,
+                &DataValue::clone(&args[0]).cast(&LogicalType::Bigint)?,
+            )
+        }
+
+        fn merge(&self, state: &mut DataValue, other: &DataValue) -> Result<(), DatabaseError> {
+            if let DataValue::Int64(Some(other)) = other {
+                *state = DataValue::Int64(Some(state.i64().unwrap_or(1) * other));
+            }
+            Ok(())
+        }
+
+        fn evaluate(&self, state: &DataValue) -> Result<DataValue, DatabaseError> {
+            Ok(state.clone())
+        }
+
+        fn return_type(&self) -> &LogicalType {
+            &LogicalType::Bigint
+        }
+
+        fn summary(&self) -> &FunctionSummary {
+            &self.summary
+        }
+    }
+
+    #[tokio::test]
+    async fn test_udaf() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path())
+            .register_aggregate_function(Arc::new(ProductAggregate {
+                summary: FunctionSummary {
+                    name: "product".to_string(),
+                    arg_types: vec![LogicalType::Integer],
+                },
+            }))
+            .build()
+            .await?;
+        let _ = fnck_sql
+            .run("CREATE TABLE test (id int primary key, c1 int, c2 int);")
+            .await?;
+        let _ = fnck_sql
+            .run(
+                "INSERT INTO test VALUES (0, 2, 1), (1, 3, 1), (2, 3, 2), (3, null, 2), (4, 5, 3);",
+            )
+            .await?;
+
+        let tuples = fnck_sql
+            .run("select product(c1), product(distinct c1), count(c1) from test")
+            .await?;
+        assert_eq!(
+            tuples[0].values,
+            vec![
+                Arc::new(DataValue::Int64(Some(90))),
+                Arc::new(DataValue::Int64(Some(30))),
+                Arc::new(DataValue::Int32(Some(4))),
+            ]
+        );
+
+        let tuples = fnck_sql
+            .run("select c2, product(c1 + 1) from test group by c2 order by c2")
+            .await?;
+        assert_eq!(
+            tuples
+                .iter()
+                .map(|tuple| tuple.values[1].clone())
+                .collect::<Vec<_>>(),
+            vec![
+                Arc::new(DataValue::Int64(Some(12))),
+                Arc::new(DataValue::Int64(Some(4))),
+                Arc::new(DataValue::Int64(Some(6))),
+            ]
+        );
+        assert!(fnck_sql
+            .run("select product(c1, c2) from test")
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_transaction_sql() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
@@ -390,6 +823,345 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_affected_rows() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build().await?;
+        let _ = fnck_sql
+            .run("create table t1 (a int primary key, b int)")
+            .await?;
+
+        let affected_rows = |tuples: Vec<Tuple>| {
+            assert_eq!(tuples.len(), 1);
+            assert_eq!(tuples[0].schema_ref[0].name(), AFFECTED_ROWS);
+            tuples[0].values[0].clone()
+        };
+        let tuples = fnck_sql
+            .run("insert into t1 values (0, 0), (1, 1), (2, 1)")
+            .await?;
+        assert_eq!(*affected_rows(tuples), DataValue::UInt64(Some(3)));
+
+        let tuples = fnck_sql.run("update t1 set b = 2 where b = 1").await?;
+        assert_eq!(*affected_rows(tuples), DataValue::UInt64(Some(2)));
+
+        let tuples = fnck_sql.run("delete from t1 where a = 3").await?;
+        assert_eq!(*affected_rows(tuples), DataValue::UInt64(Some(0)));
+
+        let tuples = fnck_sql.run("delete from t1").await?;
+        assert_eq!(*affected_rows(tuples), DataValue::UInt64(Some(3)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_privileges() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build().await?;
+        let _ = fnck_sql.run("create table t1 (a int primary key)").await?;
+        let _ = fnck_sql.run("create user kip").await?;
+        let _ = fnck_sql.run("grant select on t1 to kip").await?;
+
+        let kip = fnck_sql.with_user(Some("kip".to_string()));
+        let _ = kip.run("select * from t1").await?;
+        assert!(matches!(
+            kip.run("insert into t1 values (1)").await,
+            Err(DatabaseError::PermissionDenied(_))
+        ));
+        // the user is granted all the privileges on the table it creates
+        let _ = kip.run("create table t3 (a int primary key)").await?;
+        let _ = kip.run("insert into t3 values (1)").await?;
+        let _ = kip.run("select * from t3").await?;
+        let _ = kip.run("drop table t3").await?;
+        assert!(matches!(
+            kip.run("grant insert on t1 to kip").await,
+            Err(DatabaseError::PermissionDenied(_))
+        ));
+
+        let _ = fnck_sql.run("grant insert, delete on t1 to kip").await?;
+        let _ = kip.run("insert into t1 values (1)").await?;
+        let _ = kip.run("delete from t1 where a = 1").await?;
+        assert!(matches!(
+            kip.run("drop table t1").await,
+            Err(DatabaseError::PermissionDenied(_))
+        ));
+
+        let select = kip.prepare("select * from t1").await?;
+        let _ = kip.execute(&select, &[]).await?;
+        let _ = fnck_sql.run("revoke select on t1 from kip").await?;
+        assert!(matches!(
+            kip.run("select * from t1").await,
+            Err(DatabaseError::PermissionDenied(_))
+        ));
+        // the privileges of the prepared statement are checked again when executing
+        assert!(matches!(
+            kip.execute(&select, &[]).await,
+            Err(DatabaseError::PermissionDenied(_))
+        ));
+        let mut transaction = kip.new_transaction().await?;
+        assert!(matches!(
+            transaction.run("select * from t1").await,
+            Err(DatabaseError::PermissionDenied(_))
+        ));
+        drop(transaction);
+        // the `SELECT` privilege is also required to read the columns by `WHERE` or `RETURNING`
+        let _ = kip.run("insert into t1 values (2)").await?;
+        assert!(matches!(
+            kip.run("delete from t1 where a = 2").await,
+            Err(DatabaseError::PermissionDenied(_))
+        ));
+        assert!(matches!(
+            kip.run("delete from t1 returning a").await,
+            Err(DatabaseError::PermissionDenied(_))
+        ));
+        let _ = kip.run("delete from t1").await?;
+        let _ = fnck_sql.run("grant update on t1 to kip").await?;
+        let _ = kip.run("update t1 set a = 1").await?;
+        assert!(matches!(
+            kip.run("update t1 set a = a + 1").await,
+            Err(DatabaseError::PermissionDenied(_))
+        ));
+
+        let _ = fnck_sql.run("create user fnck superuser").await?;
+        let fnck = fnck_sql.with_user(Some("fnck".to_string()));
+        let _ = fnck.run("create table t2 (a int primary key)").await?;
+        let _ = fnck.run("grant select on t2 to kip").await?;
+        let _ = kip.run("select * from t2").await?;
+        let _ = fnck.run("alter user fnck nosuperuser").await?;
+        let _ = fnck.run("drop table t2").await?;
+        assert!(matches!(
+            fnck.run("drop table t1").await,
+            Err(DatabaseError::PermissionDenied(_))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_max_recursion_depth() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path())
+            .max_recursion_depth(10)
+            .build()
+            .await?;
+
+        let tuples = fnck_sql
+            .run("with recursive r(n) as (select 1 union all select n + 1 from r where n < 10) select * from r")
+            .await?;
+        assert_eq!(tuples.len(), 10);
+        assert!(matches!(
+            fnck_sql
+                .run("with recursive r(n) as (select 1 union all select n + 1 from r where n < 20) select * from r")
+                .await,
+            Err(DatabaseError::TooDeepRecursion(10))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prepared_statement() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build().await?;
+        let _ = fnck_sql
+            .run("create table t1 (a int primary key, b varchar)")
+            .await?;
+
+        let insert = fnck_sql.prepare("insert into t1 values ($1, $2)").await?;
+        assert_eq!(
+            insert.parameter_types,
+            vec![LogicalType::Integer, LogicalType::Varchar(None)]
+        );
+        for i in 0..3 {
+            let _ = fnck_sql
+                .execute(
+                    &insert,
+                    &[
+                        DataValue::Int32(Some(i)),
+                        DataValue::Utf8(Some(format!("{i}"))),
+                    ],
+                )
+                .await?;
+        }
+
+        let select = fnck_sql.prepare("select b from t1 where a > $1").await?;
+        assert_eq!(select.parameter_types, vec![LogicalType::Integer]);
+        assert_eq!(select.schema_ref.len(), 1);
+        assert_eq!(select.schema_ref[0].name(), "b");
+
+        let tuples = fnck_sql
+            .execute(&select, &[DataValue::Int32(Some(0))])
+            .await?;
+        assert_eq!(tuples.len(), 2);
+        assert_eq!(
+            tuples[0].values,
+            vec![Arc::new(DataValue::Utf8(Some("1".to_string())))]
+        );
+
+        assert!(fnck_sql.execute(&select, &[]).await.is_err());
+
+        // the values are cast to the types of the parameters
+        let select = fnck_sql
+            .prepare("select a from t1 where b = ? and a < ?")
+            .await?;
+        assert_eq!(
+            select.parameter_types,
+            vec![LogicalType::Varchar(None), LogicalType::Integer]
+        );
+        let tuples = fnck_sql
+            .execute(
+                &select,
+                &[
+                    DataValue::Utf8(Some("2".to_string())),
+                    DataValue::Int64(Some(10)),
+                ],
+            )
+            .await?;
+        assert_eq!(tuples.len(), 1);
+        assert_eq!(tuples[0].values, vec![Arc::new(DataValue::Int32(Some(2)))]);
+
+        // a quoted value is not parsed as SQL
+        let tuples = fnck_sql
+            .execute(
+                &select,
+                &[
+                    DataValue::Utf8(Some("2' or '1' = '1".to_string())),
+                    DataValue::Int64(Some(10)),
+                ],
+            )
+            .await?;
+        assert!(tuples.is_empty());
+
+        let mut tx = fnck_sql.new_transaction().await?;
+        let update = tx.prepare("update t1 set b = $2 where a = $1")?;
+        assert_eq!(
+            update.parameter_types,
+            vec![LogicalType::Integer, LogicalType::Varchar(None)]
+        );
+        let _ = tx
+            .execute(
+                &update,
+                &[
+                    DataValue::Int32(Some(2)),
+                    DataValue::Utf8(Some("two".to_string())),
+                ],
+            )
+            .await?;
+        // the values are bound to a copy, so the cached plan keeps its placeholders
+        let mut filter = &update.best_plan;
+        while !matches!(filter.operator, Operator::Filter(_)) {
+            filter = &filter.childrens[0];
+        }
+        let Operator::Filter(FilterOperator {
+            predicate: ScalarExpression::Binary { right_expr, .. },
+            ..
+        }) = &filter.operator
+        else {
+            unreachable!()
+        };
+        assert_eq!(
+            right_expr.as_ref(),
+            &ScalarExpression::Placeholder {
+                index: 0,
+                value: None
+            }
+        );
+        let select = tx.prepare("select b from t1 where a = 2")?;
+        let tuples = tx.execute(&select, &[]).await?;
+        assert_eq!(
+            tuples[0].values,
+            vec![Arc::new(DataValue::Utf8(Some("two".to_string())))]
+        );
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prepared_plan() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build().await?;
+        let _ = fnck_sql
+            .run("create table t1 (a int primary key, b int)")
+            .await?;
+        for i in 0..100 {
+            let _ = fnck_sql
+                .run(format!("insert into t1 values ({i}, {i})"))
+                .await?;
+        }
+        let _ = fnck_sql.run("analyze table t1").await?;
+
+        let select = fnck_sql.prepare("select b from t1 where a = $1").await?;
+        let transaction = fnck_sql.storage.transaction().await?;
+
+        // the cached plan is reused, and the index is scanned with the bound values
+        for i in [1, 42] {
+            let plan = Database::<KipStorage>::build_prepared_plan(
+                &select,
+                &[DataValue::Int32(Some(i))],
+                &transaction,
+                None,
+            )?;
+            let mut scan = &plan;
+            while !matches!(scan.operator, Operator::Scan(_)) {
+                scan = &scan.childrens[0];
+            }
+            let Some(PhysicalOption::IndexScan(IndexInfo { binaries, .. })) = &scan.physical_option
+            else {
+                unreachable!("the primary key index should be scanned")
+            };
+            assert_eq!(
+                binaries,
+                &Some(vec![ConstantBinary::Eq(Arc::new(DataValue::Int32(Some(
+                    i
+                ))))])
+            );
+
+            let tuples = fnck_sql
+                .execute(&select, &[DataValue::Int32(Some(i))])
+                .await?;
+            assert_eq!(tuples[0].values, vec![Arc::new(DataValue::Int32(Some(i)))]);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stream_sql() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build().await?;
+        let _ = fnck_sql
+            .run("create table t1 (a int primary key, b int)")
+            .await?;
+
+        let mut stream = fnck_sql
+            .stream("insert into t1 values (0, 0), (1, 1), (2, 2)")
+            .await?;
+        while stream.try_next().await?.is_some() {}
+        drop(stream);
+
+        let mut stream = fnck_sql.stream("select a from t1").await?;
+        let mut count = 0;
+        while let Some(tuple) = stream.try_next().await? {
+            assert_eq!(tuple.values, vec![Arc::new(DataValue::Int32(Some(count)))]);
+            count += 1;
+        }
+        assert_eq!(count, 3);
+
+        // the transaction is discarded if the stream is dropped before it is exhausted
+        let stream = fnck_sql.stream("insert into t1 values (3, 3)").await?;
+        drop(stream);
+        assert_eq!(fnck_sql.run("select * from t1").await?.len(), 3);
+
+        let mut tx = fnck_sql.new_transaction().await?;
+        let mut stream = tx.stream("select * from t1 where a > 0")?;
+        let tuple = stream.try_next().await?.unwrap();
+        assert_eq!(tuple.values[0], Arc::new(DataValue::Int32(Some(1))));
+        drop(stream);
+        tx.commit().await?;
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_crud_sql() -> Result<(), DatabaseError> {
         #[cfg(not(feature = "codegen_execute"))]
@@ -144,10 +144,16 @@ pub enum DatabaseError {
     ColumnsEmpty,
     #[error("unsupported statement: {0}")]
     UnsupportedStmt(String),
+    #[error("invalid placeholder: {0}")]
+    InvalidPlaceholder(String),
     #[error("invalid table: {0}")]
     InvalidTable(String),
     #[error("invalid column: {0}")]
     InvalidColumn(String),
+    #[error("password authentication failed for user: {0}")]
+    InvalidPassword(String),
+    #[error("permission denied: {0}")]
+    PermissionDenied(String),
     #[error("ambiguous column: {0}")]
     AmbiguousColumn(String),
     #[error("values length not match, expect {0}, got {1}")]
@@ -162,6 +168,14 @@ pub enum DatabaseError {
     UnsupportedCopySource(String),
     #[error("the {0} cannot support {1} for calculations")]
     UnsupportedBinaryOperator(LogicalType, BinaryOperator),
+    #[error("numeric overflow")]
+    Overflow,
+    #[error("division by zero")]
+    DivisionByZero,
+    #[error("the recursive query exceeds the max recursion depth: {0}")]
+    TooDeepRecursion(usize),
+    #[error("invalid argument of {0}: {1}")]
+    InvalidArgument(&'static str, String),
     #[error("can not compare two types: {0} and {1}")]
     Incomparable(LogicalType, LogicalType),
     #[error("transaction already exists")]
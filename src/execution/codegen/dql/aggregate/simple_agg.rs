@@ -1,6 +1,6 @@
 use crate::catalog::ColumnRef;
 use crate::execution::codegen::CodeGenerator;
-use crate::execution::volcano::dql::aggregate::{create_accumulators, Accumulator};
+use crate::execution::volcano::dql::aggregate::{create_accumulators, eval_agg_args, Accumulator};
 use crate::execution::ExecutorError;
 use crate::expression::ScalarExpression;
 use crate::impl_from_lua;
@@ -57,18 +57,15 @@ impl UserData for AggAccumulators {
                 return Ok(());
             }
 
-            let values: Vec<ValueRef> = agg_accumulators
+            let values: Vec<Vec<ValueRef>> = agg_accumulators
                 .agg_calls
                 .iter()
-                .map(|expr| match expr {
-                    ScalarExpression::AggCall { args, .. } => args[0].eval(&tuple),
-                    _ => unreachable!(),
-                })
+                .map(|expr| eval_agg_args(expr, &tuple))
                 .try_collect()
                 .unwrap();
 
-            for (acc, value) in agg_accumulators.accs.iter_mut().zip_eq(values.iter()) {
-                acc.update_value(value).unwrap();
+            for (acc, values) in agg_accumulators.accs.iter_mut().zip_eq(values.iter()) {
+                acc.update_values(values).unwrap();
             }
 
             Ok(())
@@ -70,7 +70,7 @@ impl AddColumn {
             unique_values,
             transaction
                 .table(table_name.clone())
-                .and_then(|table| table.get_unique_index(&col_id))
+                .and_then(|table| table.get_unique_index(&[col_id]))
                 .cloned(),
         ) {
             for (tuple_id, value) in unique_values {
@@ -0,0 +1,35 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::{BoxedExecutor, WriteExecutor};
+use crate::planner::operator::alter_user::AlterUserOperator;
+use crate::storage::Transaction;
+use crate::types::tuple::Tuple;
+use crate::types::tuple_builder::TupleBuilder;
+use futures_async_stream::try_stream;
+
+pub struct AlterUser {
+    op: AlterUserOperator,
+}
+
+impl From<AlterUserOperator> for AlterUser {
+    fn from(op: AlterUserOperator) -> Self {
+        AlterUser { op }
+    }
+}
+
+impl<T: Transaction> WriteExecutor<T> for AlterUser {
+    fn execute_mut(self, transaction: &mut T) -> BoxedExecutor {
+        self._execute(transaction)
+    }
+}
+
+impl AlterUser {
+    #[try_stream(boxed, ok = Tuple, error = DatabaseError)]
+    pub async fn _execute<T: Transaction>(self, transaction: &mut T) {
+        let AlterUserOperator { user } = self.op;
+        let user_name = user.name.clone();
+
+        transaction.alter_user(user)?;
+
+        yield TupleBuilder::build_result("ALTER USER SUCCESS".to_string(), user_name)?;
+    }
+}
@@ -0,0 +1,98 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::{build_read, BoxedExecutor, WriteExecutor};
+use crate::planner::operator::create_index::CreateIndexOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::index::Index;
+use crate::types::tuple::Tuple;
+use crate::types::tuple_builder::TupleBuilder;
+use crate::types::ColumnId;
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+
+pub struct CreateIndex {
+    op: CreateIndexOperator,
+    input: LogicalPlan,
+}
+
+impl From<(CreateIndexOperator, LogicalPlan)> for CreateIndex {
+    fn from((op, input): (CreateIndexOperator, LogicalPlan)) -> Self {
+        CreateIndex { op, input }
+    }
+}
+
+impl<T: Transaction> WriteExecutor<T> for CreateIndex {
+    fn execute_mut(self, transaction: &mut T) -> BoxedExecutor {
+        self._execute(transaction)
+    }
+}
+
+impl CreateIndex {
+    #[try_stream(boxed, ok = Tuple, error = DatabaseError)]
+    async fn _execute<T: Transaction>(self, transaction: &mut T) {
+        let CreateIndexOperator {
+            table_name,
+            index_name,
+            columns,
+            is_unique,
+            if_not_exists,
+        } = self.op;
+        let table = transaction
+            .table(table_name.clone())
+            .ok_or(DatabaseError::TableNotFound)?;
+
+        if table.get_index_by_name(&index_name).is_some() {
+            if if_not_exists {
+                return Ok(());
+            }
+            Err(DatabaseError::Duplicated("index", index_name.clone()))?;
+        }
+        let column_ids: Vec<ColumnId> = columns
+            .iter()
+            .map(|column| column.id().ok_or(DatabaseError::OwnerLessColumn))
+            .try_collect()?;
+        let positions: Vec<usize> = column_ids
+            .iter()
+            .map(|column_id| {
+                table
+                    .columns()
+                    .position(|column| column.id() == Some(*column_id))
+                    .ok_or_else(|| DatabaseError::NotFound("column", column_id.to_string()))
+            })
+            .try_collect()?;
+        let mut index_values = Vec::new();
+
+        // backfill the index with the existing tuples
+        #[for_await]
+        for tuple in build_read(self.input, transaction) {
+            let tuple: Tuple = tuple?;
+            let tuple_id = tuple.id.clone().ok_or(DatabaseError::PrimaryKeyNotFound)?;
+            let column_values = positions
+                .iter()
+                .map(|i| tuple.values[*i].clone())
+                .collect_vec();
+
+            // Tips: NULL is not unique, so it is not in the unique index
+            if is_unique && column_values.iter().any(|value| value.is_null()) {
+                continue;
+            }
+            index_values.push((column_values, tuple_id));
+        }
+        let index_id =
+            transaction.add_index_meta(&table_name, index_name, column_ids, is_unique)?;
+
+        for (column_values, tuple_id) in index_values {
+            transaction.add_index(
+                &table_name,
+                Index::new(index_id, column_values),
+                vec![tuple_id],
+                is_unique,
+            )?;
+        }
+
+        yield TupleBuilder::build_result(
+            "CREATE INDEX SUCCESS".to_string(),
+            format!("{}", table_name),
+        )?;
+    }
+}
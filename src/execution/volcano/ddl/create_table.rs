@@ -1,10 +1,13 @@
+use crate::catalog::Privilege;
 use crate::errors::DatabaseError;
 use crate::execution::volcano::{BoxedExecutor, WriteExecutor};
 use crate::planner::operator::create_table::CreateTableOperator;
 use crate::storage::Transaction;
 use crate::types::tuple::Tuple;
 use crate::types::tuple_builder::TupleBuilder;
+use crate::types::ColumnId;
 use futures_async_stream::try_stream;
+use itertools::Itertools;
 
 pub struct CreateTable {
     op: CreateTableOperator,
@@ -28,10 +31,39 @@ impl CreateTable {
         let CreateTableOperator {
             table_name,
             columns,
+            unique_keys,
             if_not_exists,
+            owner,
         } = self.op;
+        let is_exists = transaction.table(table_name.clone()).is_some();
         let _ = transaction.create_table(table_name.clone(), columns, if_not_exists)?;
 
+        if !is_exists {
+            for column_names in unique_keys {
+                let table = transaction
+                    .table(table_name.clone())
+                    .ok_or(DatabaseError::TableNotFound)?;
+                let column_ids: Vec<ColumnId> = column_names
+                    .iter()
+                    .map(|name| {
+                        table
+                            .get_column_id_by_name(name)
+                            .ok_or_else(|| DatabaseError::NotFound("column", name.clone()))
+                    })
+                    .try_collect()?;
+
+                let _ = transaction.add_index_meta(
+                    &table_name,
+                    format!("uk_{}", column_names.join("_")),
+                    column_ids,
+                    true,
+                )?;
+            }
+            if let Some(owner) = owner {
+                transaction.grant(&owner, &table_name, &Privilege::ALL)?;
+            }
+        }
+
         yield TupleBuilder::build_result(
             "CREATE TABLE SUCCESS".to_string(),
             format!("{}", table_name),
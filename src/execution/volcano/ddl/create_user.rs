@@ -0,0 +1,38 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::{BoxedExecutor, WriteExecutor};
+use crate::planner::operator::create_user::CreateUserOperator;
+use crate::storage::Transaction;
+use crate::types::tuple::Tuple;
+use crate::types::tuple_builder::TupleBuilder;
+use futures_async_stream::try_stream;
+
+pub struct CreateUser {
+    op: CreateUserOperator,
+}
+
+impl From<CreateUserOperator> for CreateUser {
+    fn from(op: CreateUserOperator) -> Self {
+        CreateUser { op }
+    }
+}
+
+impl<T: Transaction> WriteExecutor<T> for CreateUser {
+    fn execute_mut(self, transaction: &mut T) -> BoxedExecutor {
+        self._execute(transaction)
+    }
+}
+
+impl CreateUser {
+    #[try_stream(boxed, ok = Tuple, error = DatabaseError)]
+    pub async fn _execute<T: Transaction>(self, transaction: &mut T) {
+        let CreateUserOperator {
+            user,
+            if_not_exists,
+        } = self.op;
+        let user_name = user.name.clone();
+
+        transaction.create_user(user, if_not_exists)?;
+
+        yield TupleBuilder::build_result("CREATE USER SUCCESS".to_string(), user_name)?;
+    }
+}
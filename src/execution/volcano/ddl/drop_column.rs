@@ -36,6 +36,21 @@ impl DropColumn {
         let mut tuple_columns = None;
         let mut tuples = Vec::new();
 
+        // Tips: the composite index would lose its other columns silently, so it must be dropped first
+        if let Some(table) = transaction.table(table_name.clone()) {
+            if let Some(column_id) = table.get_column_id_by_name(column_name) {
+                if let Some(index_meta) = table
+                    .indexes_by_column(&column_id)
+                    .find(|index_meta| !index_meta.is_primary && index_meta.column_ids.len() > 1)
+                {
+                    Err(DatabaseError::InvalidColumn(format!(
+                        "column {} is used by the composite index {}, drop the index first",
+                        column_name, index_meta.name
+                    )))?;
+                }
+            }
+        }
+
         #[for_await]
         for tuple in build_read(self.input, transaction) {
             let mut tuple: Tuple = tuple?;
@@ -46,7 +61,7 @@ impl DropColumn {
                     .iter()
                     .enumerate()
                     .find(|(_, column)| column.name() == column_name)
-                    .map(|(i, column)| (i, column.desc.is_primary))
+                    .map(|(i, column)| (i, column.desc.is_primary()))
                 {
                     if is_primary {
                         Err(DatabaseError::InvalidColumn(
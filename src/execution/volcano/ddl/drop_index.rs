@@ -0,0 +1,35 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::{BoxedExecutor, WriteExecutor};
+use crate::planner::operator::drop_index::DropIndexOperator;
+use crate::storage::Transaction;
+use crate::types::tuple::Tuple;
+use futures_async_stream::try_stream;
+
+pub struct DropIndex {
+    op: DropIndexOperator,
+}
+
+impl From<DropIndexOperator> for DropIndex {
+    fn from(op: DropIndexOperator) -> Self {
+        DropIndex { op }
+    }
+}
+
+impl<T: Transaction> WriteExecutor<T> for DropIndex {
+    fn execute_mut(self, transaction: &mut T) -> BoxedExecutor {
+        self._execute(transaction)
+    }
+}
+
+impl DropIndex {
+    #[try_stream(boxed, ok = Tuple, error = DatabaseError)]
+    pub async fn _execute<T: Transaction>(self, transaction: &mut T) {
+        let DropIndexOperator {
+            table_name,
+            index_name,
+            if_exists,
+        } = self.op;
+
+        transaction.drop_index(&table_name, &index_name, if_exists)?;
+    }
+}
@@ -0,0 +1,34 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::{BoxedExecutor, WriteExecutor};
+use crate::planner::operator::drop_user::DropUserOperator;
+use crate::storage::Transaction;
+use crate::types::tuple::Tuple;
+use futures_async_stream::try_stream;
+
+pub struct DropUser {
+    op: DropUserOperator,
+}
+
+impl From<DropUserOperator> for DropUser {
+    fn from(op: DropUserOperator) -> Self {
+        DropUser { op }
+    }
+}
+
+impl<T: Transaction> WriteExecutor<T> for DropUser {
+    fn execute_mut(self, transaction: &mut T) -> BoxedExecutor {
+        self._execute(transaction)
+    }
+}
+
+impl DropUser {
+    #[try_stream(boxed, ok = Tuple, error = DatabaseError)]
+    pub async fn _execute<T: Transaction>(self, transaction: &mut T) {
+        let DropUserOperator {
+            user_name,
+            if_exists,
+        } = self.op;
+
+        transaction.drop_user(&user_name, if_exists)?;
+    }
+}
@@ -0,0 +1,46 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::{BoxedExecutor, WriteExecutor};
+use crate::planner::operator::grant::GrantOperator;
+use crate::storage::Transaction;
+use crate::types::tuple::Tuple;
+use crate::types::tuple_builder::TupleBuilder;
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+
+pub struct Grant {
+    op: GrantOperator,
+}
+
+impl From<GrantOperator> for Grant {
+    fn from(op: GrantOperator) -> Self {
+        Grant { op }
+    }
+}
+
+impl<T: Transaction> WriteExecutor<T> for Grant {
+    fn execute_mut(self, transaction: &mut T) -> BoxedExecutor {
+        self._execute(transaction)
+    }
+}
+
+impl Grant {
+    #[try_stream(boxed, ok = Tuple, error = DatabaseError)]
+    pub async fn _execute<T: Transaction>(self, transaction: &mut T) {
+        let GrantOperator {
+            privileges,
+            table_names,
+            user_names,
+        } = self.op;
+
+        for user_name in user_names.iter() {
+            for table_name in table_names.iter() {
+                transaction.grant(user_name, table_name, &privileges)?;
+            }
+        }
+
+        yield TupleBuilder::build_result(
+            "GRANT SUCCESS".to_string(),
+            user_names.iter().join(", "),
+        )?;
+    }
+}
@@ -0,0 +1,46 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::{BoxedExecutor, WriteExecutor};
+use crate::planner::operator::revoke::RevokeOperator;
+use crate::storage::Transaction;
+use crate::types::tuple::Tuple;
+use crate::types::tuple_builder::TupleBuilder;
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+
+pub struct Revoke {
+    op: RevokeOperator,
+}
+
+impl From<RevokeOperator> for Revoke {
+    fn from(op: RevokeOperator) -> Self {
+        Revoke { op }
+    }
+}
+
+impl<T: Transaction> WriteExecutor<T> for Revoke {
+    fn execute_mut(self, transaction: &mut T) -> BoxedExecutor {
+        self._execute(transaction)
+    }
+}
+
+impl Revoke {
+    #[try_stream(boxed, ok = Tuple, error = DatabaseError)]
+    pub async fn _execute<T: Transaction>(self, transaction: &mut T) {
+        let RevokeOperator {
+            privileges,
+            table_names,
+            user_names,
+        } = self.op;
+
+        for user_name in user_names.iter() {
+            for table_name in table_names.iter() {
+                transaction.revoke(user_name, table_name, &privileges)?;
+            }
+        }
+
+        yield TupleBuilder::build_result(
+            "REVOKE SUCCESS".to_string(),
+            user_names.iter().join(", "),
+        )?;
+    }
+}
@@ -95,7 +95,9 @@ impl Analyze {
 
         for (column_id, builder) in builders {
             let path = dir_path.join(column_id.unwrap().to_string());
-            let (histogram, sketch) = builder.build(DEFAULT_NUM_OF_BUCKETS)?;
+            // Tips: the small tables have fewer values than the default buckets
+            let number_of_buckets = DEFAULT_NUM_OF_BUCKETS.min(builder.values_len().max(1));
+            let (histogram, sketch) = builder.build(number_of_buckets)?;
 
             ColumnMeta::new(histogram, sketch).to_file(&path)?;
 
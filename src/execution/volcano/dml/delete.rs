@@ -1,6 +1,8 @@
 use crate::catalog::TableName;
 use crate::errors::DatabaseError;
+use crate::execution::volcano::dml::{target_tuple, Returning};
 use crate::execution::volcano::{build_read, BoxedExecutor, WriteExecutor};
+use crate::expression::ScalarExpression;
 use crate::planner::operator::delete::DeleteOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
@@ -8,15 +10,30 @@ use crate::types::index::Index;
 use crate::types::tuple::Tuple;
 use futures_async_stream::try_stream;
 use itertools::Itertools;
+use std::collections::HashSet;
 
 pub struct Delete {
     table_name: TableName,
+    returning: Vec<ScalarExpression>,
     input: LogicalPlan,
 }
 
 impl From<(DeleteOperator, LogicalPlan)> for Delete {
-    fn from((DeleteOperator { table_name, .. }, input): (DeleteOperator, LogicalPlan)) -> Self {
-        Delete { table_name, input }
+    fn from(
+        (
+            DeleteOperator {
+                table_name,
+                returning,
+                ..
+            },
+            input,
+        ): (DeleteOperator, LogicalPlan),
+    ) -> Self {
+        Delete {
+            table_name,
+            returning,
+            input,
+        }
     }
 }
 
@@ -29,57 +46,66 @@ impl<T: Transaction> WriteExecutor<T> for Delete {
 impl Delete {
     #[try_stream(boxed, ok = Tuple, error = DatabaseError)]
     async fn _execute<T: Transaction>(self, transaction: &mut T) {
-        let Delete { table_name, input } = self;
-        let option_index_metas = transaction.table(table_name.clone()).map(|table_catalog| {
-            table_catalog
-                .columns()
-                .enumerate()
-                .filter_map(|(i, col)| {
-                    col.desc
-                        .is_unique
-                        .then(|| {
-                            col.id().and_then(|col_id| {
-                                table_catalog
-                                    .get_unique_index(&col_id)
-                                    .map(|index_meta| (i, index_meta.clone()))
-                            })
-                        })
-                        .flatten()
+        let Delete {
+            table_name,
+            returning,
+            input,
+        } = self;
+        if let Some(table_catalog) = transaction.table(table_name.clone()).cloned() {
+            let index_metas: Vec<_> = table_catalog
+                .secondary_indexes()
+                .map(|index_meta| {
+                    Ok::<_, DatabaseError>((
+                        index_meta.clone(),
+                        table_catalog.index_positions(index_meta)?,
+                    ))
                 })
-                .collect_vec()
-        });
-
-        if let Some(index_metas) = option_index_metas {
+                .try_collect()?;
             let mut tuple_ids = Vec::new();
+            let mut deleted_ids = HashSet::new();
             let mut indexes = Vec::new();
+            let mut returning = Returning::new(returning);
 
             #[for_await]
             for tuple in build_read(input, transaction) {
-                let tuple: Tuple = tuple?;
+                let tuple = target_tuple(&table_catalog, tuple?)?;
+                let tuple_id = tuple.id.clone().ok_or(DatabaseError::PrimaryKeyNotFound)?;
 
-                for (i, index_meta) in index_metas.iter() {
-                    let value = &tuple.values[*i];
+                // the target tuple may be joined with several tuples
+                if !deleted_ids.insert(tuple_id.clone()) {
+                    continue;
+                }
 
-                    if !value.is_null() {
-                        let index = Index {
-                            id: index_meta.id,
-                            column_values: vec![value.clone()],
-                        };
+                for (index_meta, positions) in index_metas.iter() {
+                    let column_values = positions
+                        .iter()
+                        .map(|i| tuple.values[*i].clone())
+                        .collect_vec();
 
-                        indexes.push(index);
+                    if index_meta.is_unique && column_values.iter().any(|value| value.is_null()) {
+                        continue;
                     }
+                    indexes.push((
+                        Index::new(index_meta.id, column_values),
+                        tuple_id.clone(),
+                        index_meta.is_unique,
+                    ));
                 }
-
-                if let Some(tuple_id) = tuple.id {
-                    tuple_ids.push(tuple_id);
-                }
+                returning.push(&tuple)?;
+                tuple_ids.push(tuple_id);
             }
-            for index in indexes {
-                transaction.del_index(&table_name, &index)?;
+            for (index, tuple_id, is_unique) in indexes {
+                transaction.del_index(&table_name, &index, &tuple_id, is_unique)?;
             }
+            let count = tuple_ids.len();
+
             for tuple_id in tuple_ids {
                 transaction.delete(&table_name, tuple_id)?;
             }
+
+            for tuple in returning.finish(count)? {
+                yield tuple;
+            }
         }
     }
 }
@@ -1,14 +1,18 @@
-use crate::catalog::TableName;
+use crate::catalog::{ColumnRef, TableCatalog, TableName};
 use crate::errors::DatabaseError;
+use crate::execution::volcano::dml::{update_tuple, Returning};
 use crate::execution::volcano::{build_read, BoxedExecutor, WriteExecutor};
-use crate::planner::operator::insert::InsertOperator;
+use crate::expression::simplify::ConstantBinary;
+use crate::expression::ScalarExpression;
+use crate::planner::operator::insert::{InsertOperator, OnConflict, OnConflictAction};
 use crate::planner::LogicalPlan;
-use crate::storage::Transaction;
-use crate::types::index::Index;
-use crate::types::tuple::Tuple;
+use crate::storage::{Iter, Transaction};
+use crate::types::index::{Index, IndexMetaRef};
+use crate::types::tuple::{Tuple, TupleId};
 use crate::types::tuple_builder::TupleBuilder;
-use crate::types::value::DataValue;
+use crate::types::value::{DataValue, ValueRef};
 use futures_async_stream::try_stream;
+use itertools::Itertools;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -16,6 +20,9 @@ pub struct Insert {
     table_name: TableName,
     input: LogicalPlan,
     is_overwrite: bool,
+    columns: Option<Vec<ColumnRef>>,
+    on_conflict: Option<OnConflict>,
+    returning: Vec<ScalarExpression>,
 }
 
 impl From<(InsertOperator, LogicalPlan)> for Insert {
@@ -24,6 +31,9 @@ impl From<(InsertOperator, LogicalPlan)> for Insert {
             InsertOperator {
                 table_name,
                 is_overwrite,
+                columns,
+                on_conflict,
+                returning,
             },
             input,
         ): (InsertOperator, LogicalPlan),
@@ -32,6 +42,9 @@ impl From<(InsertOperator, LogicalPlan)> for Insert {
             table_name,
             input,
             is_overwrite,
+            columns,
+            on_conflict,
+            returning,
         }
     }
 }
@@ -49,14 +62,29 @@ impl Insert {
             table_name,
             input,
             is_overwrite,
+            columns,
+            on_conflict,
+            returning,
         } = self;
-        let mut primary_key_index = None;
-        let mut unique_values = HashMap::new();
         let mut tuple_values = Vec::new();
-        let mut tuple_schema_ref = None;
 
         if let Some(table_catalog) = transaction.table(table_name.clone()).cloned() {
-            let _ = tuple_schema_ref.get_or_insert_with(|| table_catalog.schema_ref().clone());
+            let tuple_builder = TupleBuilder::new(table_catalog.schema_ref());
+            let primary_keys = table_catalog
+                .primary_keys()?
+                .into_iter()
+                .map(|(i, _)| i)
+                .collect_vec();
+            let indexes: Vec<_> = table_catalog
+                .secondary_indexes()
+                .map(|index_meta| {
+                    Ok::<_, DatabaseError>((
+                        index_meta.clone(),
+                        table_catalog.index_positions(index_meta)?,
+                    ))
+                })
+                .try_collect()?;
+
             #[for_await]
             for tuple in build_read(input, transaction) {
                 let Tuple {
@@ -64,62 +92,199 @@ impl Insert {
                 } = tuple?;
                 let mut tuple_map = HashMap::new();
                 for (i, value) in values.into_iter().enumerate() {
-                    let column = &schema_ref[i];
+                    let column = match &columns {
+                        Some(columns) => &columns[i],
+                        None => &schema_ref[i],
+                    };
 
                     if let Some(column_id) = column.id() {
                         tuple_map.insert(column_id, value);
                     }
                 }
-                let primary_col_id = primary_key_index.get_or_insert_with(|| {
-                    schema_ref
-                        .iter()
-                        .find(|col| col.desc.is_primary)
-                        .map(|col| col.id().unwrap())
-                        .unwrap()
-                });
-                let tuple_id = tuple_map.get(primary_col_id).cloned().unwrap();
                 let mut values = Vec::with_capacity(table_catalog.columns_len());
 
                 for col in table_catalog.columns() {
-                    let value = tuple_map
-                        .remove(&col.id().unwrap())
-                        .or_else(|| col.default_value())
-                        .unwrap_or_else(|| Arc::new(DataValue::none(col.datatype())));
-
-                    if col.desc.is_unique && !value.is_null() {
-                        unique_values
-                            .entry(col.id())
-                            .or_insert_with(Vec::new)
-                            .push((tuple_id.clone(), value.clone()))
-                    }
+                    let value = match tuple_map.remove(&col.id().unwrap()) {
+                        // Tips: the values of `VALUES` have been checked and cast while binding,
+                        // so only the rows of `SELECT` are cast here, and casting checks their lengths
+                        Some(value) if value.logical_type() != *col.datatype() => {
+                            Arc::new(DataValue::clone(&value).cast(col.datatype())?)
+                        }
+                        Some(value) => value,
+                        None => col
+                            .default_value()
+                            .unwrap_or_else(|| Arc::new(DataValue::none(col.datatype()))),
+                    };
+
                     if value.is_null() && !col.nullable {
                         return Err(DatabaseError::NotNull);
                     }
                     values.push(value)
                 }
+                let tuple_id = Tuple::primary_key_id(
+                    primary_keys
+                        .iter()
+                        .map(|i| values[*i].clone())
+                        .collect_vec(),
+                )
+                .ok_or(DatabaseError::PrimaryKeyNotFound)?;
+
                 tuple_values.push((tuple_id, values));
             }
-            let tuple_schema_ref = tuple_schema_ref.ok_or(DatabaseError::ColumnsEmpty)?;
-            let tuple_builder = TupleBuilder::new(&tuple_schema_ref);
-
-            // Unique Index
-            for (col_id, values) in unique_values {
-                if let Some(index_meta) = table_catalog.get_unique_index(&col_id.unwrap()) {
-                    for (tuple_id, value) in values {
-                        let index = Index {
-                            id: index_meta.id,
-                            column_values: vec![value],
-                        };
-
-                        transaction.add_index(&table_name, index, vec![tuple_id], true)?;
+            let mut count = 0;
+            let mut returning = Returning::new(returning);
+
+            // Tips: the tuples are inserted one by one, so that the later ones conflict with the earlier ones
+            for (tuple_id, values) in tuple_values {
+                if let Some(OnConflict { index_meta, action }) = &on_conflict {
+                    let conflict_indexes = match index_meta {
+                        Some(index_meta) => vec![index_meta.clone()],
+                        None => table_catalog
+                            .indexes
+                            .iter()
+                            .filter(|meta| meta.is_unique || meta.is_primary)
+                            .cloned()
+                            .collect_vec(),
+                    };
+
+                    if let Some(tuple) = Self::conflict_tuple(
+                        transaction,
+                        &table_catalog,
+                        conflict_indexes,
+                        &tuple_id,
+                        &values,
+                    )? {
+                        if let OnConflictAction::DoUpdate {
+                            excluded_schema_ref,
+                            value_exprs,
+                            selection,
+                        } = action
+                        {
+                            // the existing tuple is followed by the excluded one
+                            let schema_ref = Arc::new(
+                                tuple
+                                    .schema_ref
+                                    .iter()
+                                    .chain(excluded_schema_ref.iter())
+                                    .cloned()
+                                    .collect_vec(),
+                            );
+                            let eval_tuple = Tuple {
+                                id: None,
+                                schema_ref,
+                                values: tuple.values.iter().chain(values.iter()).cloned().collect(),
+                            };
+
+                            if let Some(selection) = selection {
+                                if !matches!(
+                                    selection.eval(&eval_tuple)?.as_ref(),
+                                    DataValue::Boolean(Some(true))
+                                ) {
+                                    continue;
+                                }
+                            }
+                            let values: Vec<(usize, ValueRef)> = value_exprs
+                                .iter()
+                                .map(|(column, expr)| {
+                                    let i = table_catalog
+                                        .columns()
+                                        .position(|table_column| table_column.id() == column.id())
+                                        .ok_or_else(|| {
+                                            DatabaseError::NotFound(
+                                                "column",
+                                                column.name().to_string(),
+                                            )
+                                        })?;
+                                    let value = expr.eval(&eval_tuple)?;
+                                    value.check_len(column.datatype())?;
+                                    let value = DataValue::clone(&value).cast(column.datatype())?;
+
+                                    Ok::<_, DatabaseError>((i, Arc::new(value)))
+                                })
+                                .try_collect()?;
+
+                            update_tuple(
+                                transaction,
+                                &table_catalog,
+                                &indexes,
+                                tuple,
+                                values,
+                                &mut returning,
+                            )?;
+                            count += 1;
+                        }
+                        continue;
                     }
                 }
-            }
-            for (tuple_id, values) in tuple_values {
+
+                for (index_meta, positions) in indexes.iter() {
+                    let column_values = positions.iter().map(|i| values[*i].clone()).collect_vec();
+
+                    // Tips: NULL is not unique, so it is not in the unique index
+                    if index_meta.is_unique && column_values.iter().any(|value| value.is_null()) {
+                        continue;
+                    }
+                    transaction.add_index(
+                        &table_name,
+                        Index::new(index_meta.id, column_values),
+                        vec![tuple_id.clone()],
+                        index_meta.is_unique,
+                    )?;
+                }
                 let tuple = tuple_builder.build(Some(tuple_id), values)?;
 
+                returning.push(&tuple)?;
                 transaction.append(&table_name, tuple, is_overwrite)?;
+                count += 1;
+            }
+
+            for tuple in returning.finish(count)? {
+                yield tuple;
+            }
+        }
+    }
+
+    /// The existing tuple with the same values on any of the unique indexes
+    fn conflict_tuple<T: Transaction>(
+        transaction: &T,
+        table_catalog: &TableCatalog,
+        index_metas: Vec<IndexMetaRef>,
+        tuple_id: &TupleId,
+        values: &[ValueRef],
+    ) -> Result<Option<Tuple>, DatabaseError> {
+        for index_meta in index_metas {
+            let value = if index_meta.is_primary {
+                tuple_id.clone()
+            } else {
+                let mut column_values = table_catalog
+                    .index_positions(&index_meta)?
+                    .into_iter()
+                    .map(|i| values[i].clone())
+                    .collect_vec();
+
+                // Tips: NULL is not unique, so it never conflicts
+                if column_values.iter().any(|value| value.is_null()) {
+                    continue;
+                }
+                if column_values.len() == 1 {
+                    column_values.remove(0)
+                } else {
+                    Arc::new(DataValue::Tuple(Some(column_values)))
+                }
+            };
+            let mut iter = transaction.read_by_index(
+                table_catalog.name.clone(),
+                (None, Some(1)),
+                table_catalog.columns().cloned().enumerate().collect_vec(),
+                index_meta,
+                vec![ConstantBinary::Eq(value)],
+            )?;
+
+            if let Some(tuple) = iter.next_tuple()? {
+                return Ok(Some(tuple));
             }
         }
+
+        Ok(None)
     }
 }
@@ -4,3 +4,174 @@ pub(crate) mod copy_to_file;
 pub(crate) mod delete;
 pub(crate) mod insert;
 pub(crate) mod update;
+
+use crate::catalog::TableCatalog;
+use crate::errors::DatabaseError;
+use crate::expression::ScalarExpression;
+use crate::storage::Transaction;
+use crate::types::index::{Index, IndexMetaRef};
+use crate::types::tuple::{SchemaRef, Tuple};
+use crate::types::tuple_builder::TupleBuilder;
+use crate::types::value::ValueRef;
+use itertools::Itertools;
+use std::sync::Arc;
+
+/// Takes the tuple of the target table from the input of `UPDATE` and `DELETE`,
+/// which has no tuple id if it has been joined with the tables in `FROM` or `USING`.
+pub(crate) fn target_tuple(
+    table_catalog: &TableCatalog,
+    tuple: Tuple,
+) -> Result<Tuple, DatabaseError> {
+    if tuple.id.is_some() {
+        return Ok(tuple);
+    }
+    let values = table_catalog
+        .columns()
+        .map(|column| {
+            tuple
+                .schema_ref
+                .iter()
+                .position(|tuple_column| tuple_column.summary() == column.summary())
+                .map(|i| tuple.values[i].clone())
+                .ok_or_else(|| DatabaseError::NotFound("column", column.name().to_string()))
+        })
+        .try_collect::<_, Vec<_>, _>()?;
+    let id = Tuple::primary_key_id(
+        table_catalog
+            .primary_keys()?
+            .into_iter()
+            .map(|(i, _)| values[i].clone())
+            .collect_vec(),
+    );
+
+    Ok(Tuple {
+        id,
+        schema_ref: table_catalog.schema_ref().clone(),
+        values,
+    })
+}
+
+/// Sets the values at the positions of the tuple and writes it back,
+/// the tuple is moved to its new id and the indexes follow it if the primary key has changed.
+pub(crate) fn update_tuple<T: Transaction>(
+    transaction: &mut T,
+    table_catalog: &TableCatalog,
+    indexes: &[(IndexMetaRef, Vec<usize>)],
+    mut tuple: Tuple,
+    values: impl IntoIterator<Item = (usize, ValueRef)>,
+    returning: &mut Returning,
+) -> Result<(), DatabaseError> {
+    let table_name = &table_catalog.name;
+    let mut is_overwrite = true;
+    let mut is_primary_changed = false;
+    let old_tuple_id = tuple.id.clone().ok_or(DatabaseError::PrimaryKeyNotFound)?;
+    let old_values = tuple.values.clone();
+
+    for (i, value) in values {
+        let column = &tuple.schema_ref[i];
+
+        if value.is_null() && !column.nullable {
+            return Err(DatabaseError::NotNull);
+        }
+        if column.desc.is_primary() {
+            is_primary_changed = true;
+        }
+        tuple.values[i] = value;
+    }
+    if is_primary_changed {
+        tuple.id = Tuple::primary_key_id(
+            table_catalog
+                .primary_keys()?
+                .into_iter()
+                .map(|(i, _)| tuple.values[i].clone())
+                .collect_vec(),
+        );
+        transaction.delete(table_name, old_tuple_id.clone())?;
+        is_overwrite = false;
+    }
+    for (index_meta, positions) in indexes.iter() {
+        let old_column_values = positions
+            .iter()
+            .map(|i| old_values[*i].clone())
+            .collect_vec();
+        let column_values = positions
+            .iter()
+            .map(|i| tuple.values[*i].clone())
+            .collect_vec();
+
+        // the index still points to the old tuple id when the primary key has changed
+        if old_column_values == column_values && !is_primary_changed {
+            continue;
+        }
+        // Tips: NULL is not unique, so it is not in the unique index
+        let is_indexed = |values: &[ValueRef]| {
+            !index_meta.is_unique || !values.iter().any(|value| value.is_null())
+        };
+
+        if is_indexed(&old_column_values) {
+            transaction.del_index(
+                table_name,
+                &Index::new(index_meta.id, old_column_values),
+                &old_tuple_id,
+                index_meta.is_unique,
+            )?;
+        }
+        if is_indexed(&column_values) {
+            transaction.add_index(
+                table_name,
+                Index::new(index_meta.id, column_values),
+                vec![tuple.id.clone().unwrap()],
+                index_meta.is_unique,
+            )?;
+        }
+    }
+
+    returning.push(&tuple)?;
+    transaction.append(table_name, tuple, is_overwrite)
+}
+
+/// The output of `INSERT`, `UPDATE` and `DELETE`,
+/// which is either the tuples of `RETURNING` or the number of the affected rows.
+pub(crate) struct Returning {
+    exprs: Vec<ScalarExpression>,
+    schema_ref: SchemaRef,
+    tuples: Vec<Tuple>,
+}
+
+impl Returning {
+    pub(crate) fn new(exprs: Vec<ScalarExpression>) -> Self {
+        let schema_ref = Arc::new(exprs.iter().map(|expr| expr.output_column()).collect_vec());
+
+        Returning {
+            exprs,
+            schema_ref,
+            tuples: vec![],
+        }
+    }
+
+    /// Evaluates `RETURNING` on the tuple written or deleted by the statement
+    pub(crate) fn push(&mut self, tuple: &Tuple) -> Result<(), DatabaseError> {
+        if self.exprs.is_empty() {
+            return Ok(());
+        }
+        let values = self
+            .exprs
+            .iter()
+            .map(|expr| expr.eval(tuple))
+            .try_collect()?;
+
+        self.tuples.push(Tuple {
+            id: None,
+            schema_ref: self.schema_ref.clone(),
+            values,
+        });
+        Ok(())
+    }
+
+    pub(crate) fn finish(self, count: usize) -> Result<Vec<Tuple>, DatabaseError> {
+        if self.exprs.is_empty() {
+            return Ok(vec![TupleBuilder::build_affected_rows(count)?]);
+        }
+        Ok(self.tuples)
+    }
+}
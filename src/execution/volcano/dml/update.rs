@@ -1,28 +1,41 @@
-use crate::catalog::TableName;
+use crate::catalog::{ColumnRef, TableName};
 use crate::errors::DatabaseError;
+use crate::execution::volcano::dml::{target_tuple, update_tuple, Returning};
 use crate::execution::volcano::{build_read, BoxedExecutor, WriteExecutor};
+use crate::expression::ScalarExpression;
 use crate::planner::operator::update::UpdateOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
-use crate::types::index::Index;
 use crate::types::tuple::Tuple;
+use crate::types::value::{DataValue, ValueRef};
 use futures_async_stream::try_stream;
-use std::collections::HashMap;
+use itertools::Itertools;
+use std::collections::HashSet;
+use std::sync::Arc;
 
 pub struct Update {
     table_name: TableName,
+    value_exprs: Vec<(ColumnRef, ScalarExpression)>,
+    returning: Vec<ScalarExpression>,
     input: LogicalPlan,
-    values: LogicalPlan,
 }
 
-impl From<(UpdateOperator, LogicalPlan, LogicalPlan)> for Update {
+impl From<(UpdateOperator, LogicalPlan)> for Update {
     fn from(
-        (UpdateOperator { table_name }, input, values): (UpdateOperator, LogicalPlan, LogicalPlan),
+        (
+            UpdateOperator {
+                table_name,
+                value_exprs,
+                returning,
+            },
+            input,
+        ): (UpdateOperator, LogicalPlan),
     ) -> Self {
         Update {
             table_name,
+            value_exprs,
+            returning,
             input,
-            values,
         }
     }
 }
@@ -38,71 +51,72 @@ impl Update {
     pub async fn _execute<T: Transaction>(self, transaction: &mut T) {
         let Update {
             table_name,
+            value_exprs,
+            returning,
             input,
-            values,
         } = self;
 
         if let Some(table_catalog) = transaction.table(table_name.clone()).cloned() {
-            let mut value_map = HashMap::new();
+            let value_positions: Vec<usize> = value_exprs
+                .iter()
+                .map(|(column, _)| {
+                    table_catalog
+                        .columns()
+                        .position(|table_column| table_column.id() == column.id())
+                        .ok_or_else(|| DatabaseError::NotFound("column", column.name().to_string()))
+                })
+                .try_collect()?;
+            let mut tuple_ids = HashSet::new();
             let mut tuples = Vec::new();
 
-            // only once
-            #[for_await]
-            for tuple in build_read(values, transaction) {
-                let Tuple {
-                    schema_ref: columns,
-                    values,
-                    ..
-                } = tuple?;
-                for i in 0..columns.len() {
-                    value_map.insert(columns[i].id(), values[i].clone());
-                }
-            }
             #[for_await]
             for tuple in build_read(input, transaction) {
                 let tuple: Tuple = tuple?;
+                let values: Vec<ValueRef> = value_exprs
+                    .iter()
+                    .map(|(column, expr)| {
+                        let value = expr.eval(&tuple)?;
+                        value.check_len(column.datatype())?;
+                        let value = DataValue::clone(&value).cast(column.datatype())?;
 
-                tuples.push(tuple);
-            }
-
-            for mut tuple in tuples {
-                let mut is_overwrite = true;
+                        Ok::<ValueRef, DatabaseError>(Arc::new(value))
+                    })
+                    .try_collect()?;
+                let tuple = target_tuple(&table_catalog, tuple)?;
+                let tuple_id = tuple.id.clone().ok_or(DatabaseError::PrimaryKeyNotFound)?;
 
-                for (i, column) in tuple.schema_ref.iter().enumerate() {
-                    if let Some(value) = value_map.get(&column.id()) {
-                        if column.desc.is_primary {
-                            let old_key = tuple.id.replace(value.clone()).unwrap();
+                // Tips: the target tuple may be joined with several tuples, and only the first one is used
+                if tuple_ids.insert(tuple_id) {
+                    tuples.push((tuple, values));
+                }
+            }
 
-                            transaction.delete(&table_name, old_key)?;
-                            is_overwrite = false;
-                        }
-                        if column.desc.is_unique && value != &tuple.values[i] {
-                            if let Some(index_meta) =
-                                table_catalog.get_unique_index(&column.id().unwrap())
-                            {
-                                let mut index = Index {
-                                    id: index_meta.id,
-                                    column_values: vec![tuple.values[i].clone()],
-                                };
-                                transaction.del_index(&table_name, &index)?;
+            let indexes: Vec<_> = table_catalog
+                .secondary_indexes()
+                .map(|index_meta| {
+                    Ok::<_, DatabaseError>((
+                        index_meta.clone(),
+                        table_catalog.index_positions(index_meta)?,
+                    ))
+                })
+                .try_collect()?;
 
-                                if !value.is_null() {
-                                    index.column_values[0] = value.clone();
-                                    transaction.add_index(
-                                        &table_name,
-                                        index,
-                                        vec![tuple.id.clone().unwrap()],
-                                        true,
-                                    )?;
-                                }
-                            }
-                        }
+            let count = tuples.len();
+            let mut returning = Returning::new(returning);
 
-                        tuple.values[i] = value.clone();
-                    }
-                }
+            for (tuple, values) in tuples {
+                update_tuple(
+                    transaction,
+                    &table_catalog,
+                    &indexes,
+                    tuple,
+                    value_positions.iter().cloned().zip(values),
+                    &mut returning,
+                )?;
+            }
 
-                transaction.append(&table_name, tuple, is_overwrite)?;
+            for tuple in returning.finish(count)? {
+                yield tuple;
             }
         }
     }
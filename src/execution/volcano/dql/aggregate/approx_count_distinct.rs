@@ -0,0 +1,65 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::dql::aggregate::Accumulator;
+use crate::types::value::{DataValue, ValueRef};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// The bits of the hash which choose the register
+const PRECISION: u32 = 12;
+const REGISTERS: usize = 1 << PRECISION;
+
+/// Estimates the number of the distinct values which are not NULL by HyperLogLog
+pub struct HyperLogLogAccumulator {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLogAccumulator {
+    pub fn new() -> Self {
+        Self {
+            registers: vec![0; REGISTERS],
+        }
+    }
+}
+
+impl Accumulator for HyperLogLogAccumulator {
+    fn update_value(&mut self, value: &ValueRef) -> Result<(), DatabaseError> {
+        if value.is_null() {
+            return Ok(());
+        }
+        // Tips: the keys of `DefaultHasher::new` are fixed, so the hash is stable
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let index = (hash >> (64 - PRECISION)) as usize;
+        // the guard bit limits the rank when the rest bits are all zeros
+        let rank = ((hash << PRECISION) | (1 << (PRECISION - 1))).leading_zeros() as u8 + 1;
+        self.registers[index] = self.registers[index].max(rank);
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<ValueRef, DatabaseError> {
+        let m = REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|register| 2f64.powi(-(*register as i32)))
+            .sum();
+        let zeros = self
+            .registers
+            .iter()
+            .filter(|register| **register == 0)
+            .count();
+
+        let mut estimate = alpha * m * m / sum;
+        // the linear counting is more accurate for the small cardinalities
+        if estimate <= 2.5 * m && zeros > 0 {
+            estimate = m * (m / zeros as f64).ln();
+        }
+
+        Ok(Arc::new(DataValue::Int64(Some(estimate.round() as i64))))
+    }
+}
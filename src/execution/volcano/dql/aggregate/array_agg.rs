@@ -0,0 +1,29 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::dql::aggregate::Accumulator;
+use crate::types::value::{DataValue, ValueRef};
+use std::sync::Arc;
+
+/// Collects the values including the NULLs into a tuple, which is NULL if there is no row
+pub struct ArrayAggAccumulator {
+    values: Vec<ValueRef>,
+}
+
+impl ArrayAggAccumulator {
+    pub fn new() -> Self {
+        Self { values: vec![] }
+    }
+}
+
+impl Accumulator for ArrayAggAccumulator {
+    fn update_value(&mut self, value: &ValueRef) -> Result<(), DatabaseError> {
+        self.values.push(value.clone());
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<ValueRef, DatabaseError> {
+        Ok(Arc::new(DataValue::Tuple(
+            (!self.values.is_empty()).then(|| self.values.clone()),
+        )))
+    }
+}
@@ -0,0 +1,37 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::dql::aggregate::Accumulator;
+use crate::types::value::{DataValue, ValueRef};
+use std::sync::Arc;
+
+/// `BOOL_AND` if `is_and`, otherwise `BOOL_OR`, ignoring the NULLs
+pub struct BoolAccumulator {
+    result: Option<bool>,
+    is_and: bool,
+}
+
+impl BoolAccumulator {
+    pub fn new(is_and: bool) -> Self {
+        Self {
+            result: None,
+            is_and,
+        }
+    }
+}
+
+impl Accumulator for BoolAccumulator {
+    fn update_value(&mut self, value: &ValueRef) -> Result<(), DatabaseError> {
+        if let Some(value) = value.bool() {
+            self.result = Some(match self.result {
+                Some(result) if self.is_and => result && value,
+                Some(result) => result || value,
+                None => value,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<ValueRef, DatabaseError> {
+        Ok(Arc::new(DataValue::Boolean(self.result)))
+    }
+}
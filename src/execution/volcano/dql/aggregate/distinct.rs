@@ -0,0 +1,39 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::dql::aggregate::Accumulator;
+use crate::types::value::ValueRef;
+use ahash::RandomState;
+use std::collections::HashSet;
+
+/// Only updates the inner accumulator with the arguments which have not been seen,
+/// for the aggregates without the dedicated distinct accumulators
+pub struct DistinctAccumulator {
+    inner: Box<dyn Accumulator>,
+    distinct_values: HashSet<Vec<ValueRef>, RandomState>,
+}
+
+impl DistinctAccumulator {
+    pub fn new(inner: Box<dyn Accumulator>) -> Self {
+        Self {
+            inner,
+            distinct_values: HashSet::default(),
+        }
+    }
+}
+
+impl Accumulator for DistinctAccumulator {
+    fn update_value(&mut self, value: &ValueRef) -> Result<(), DatabaseError> {
+        self.update_values(&[value.clone()])
+    }
+
+    fn update_values(&mut self, values: &[ValueRef]) -> Result<(), DatabaseError> {
+        if self.distinct_values.insert(values.to_vec()) {
+            self.inner.update_values(values)?;
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<ValueRef, DatabaseError> {
+        self.inner.evaluate()
+    }
+}
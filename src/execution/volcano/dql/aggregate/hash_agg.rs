@@ -1,13 +1,15 @@
 use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
-use crate::execution::volcano::dql::aggregate::{create_accumulators, Accumulator};
+use crate::execution::volcano::dql::aggregate::{create_accumulators, eval_agg_args, Accumulator};
 use crate::execution::volcano::{build_read, BoxedExecutor, ReadExecutor};
+use crate::expression::agg::AggKind;
 use crate::expression::ScalarExpression;
 use crate::planner::operator::aggregate::AggregateOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
-use crate::types::tuple::Tuple;
-use crate::types::value::ValueRef;
+use crate::types::tuple::{SchemaRef, Tuple};
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
 use ahash::HashMap;
 use futures_async_stream::try_stream;
 use itertools::Itertools;
@@ -79,16 +81,10 @@ impl HashAggStatus {
         }
 
         // 2.1 evaluate agg exprs and collect the result values for later accumulators.
-        let values: Vec<ValueRef> = self
+        let values: Vec<Vec<ValueRef>> = self
             .agg_calls
             .iter()
-            .map(|expr| {
-                if let ScalarExpression::AggCall { args, .. } = expr {
-                    args[0].eval(&tuple)
-                } else {
-                    unreachable!()
-                }
-            })
+            .map(|expr| eval_agg_args(expr, &tuple))
             .try_collect()?;
 
         let group_keys: Vec<ValueRef> = self
@@ -97,14 +93,14 @@ impl HashAggStatus {
             .map(|expr| expr.eval(&tuple))
             .try_collect()?;
 
-        for (acc, value) in self
+        for (acc, values) in self
             .group_hash_accs
             .entry(group_keys)
             .or_insert_with(|| create_accumulators(&self.agg_calls))
             .iter_mut()
             .zip_eq(values.iter())
         {
-            acc.update_value(value)?;
+            acc.update_values(values)?;
         }
 
         Ok(())
@@ -131,6 +127,45 @@ impl HashAggStatus {
             })
             .try_collect()
     }
+
+    /// Counts the duplicates of the rows by grouping them on all the columns,
+    /// which are referenced by position as the rows may come from the different schemas.
+    pub(crate) fn with_row_count(schema_ref: &SchemaRef) -> Self {
+        let count_star = ScalarExpression::AggCall {
+            distinct: false,
+            kind: AggKind::Count,
+            args: vec![ScalarExpression::Constant(Arc::new(DataValue::Utf8(Some(
+                "*".to_string(),
+            ))))],
+            ty: LogicalType::Integer,
+        };
+        let groupby_exprs = schema_ref
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(pos, column)| ScalarExpression::Reference {
+                expr: Box::new(ScalarExpression::ColumnRef(column)),
+                pos,
+            })
+            .collect_vec();
+
+        HashAggStatus::new(vec![count_star], groupby_exprs)
+    }
+
+    /// The rows and their number of duplicates, see `HashAggStatus::with_row_count`
+    pub(crate) fn as_row_counts(&mut self) -> Result<Vec<(Vec<ValueRef>, usize)>, DatabaseError> {
+        self.as_tuples()?
+            .into_iter()
+            .map(|mut tuple| {
+                let count = match tuple.values.remove(0).as_ref() {
+                    DataValue::Int32(Some(count)) => *count as usize,
+                    _ => unreachable!(),
+                };
+
+                Ok((tuple.values, count))
+            })
+            .try_collect()
+    }
 }
 
 impl HashAggExecutor {
@@ -226,9 +261,11 @@ mod test {
                     ],
                 ],
                 schema_ref: Arc::new(t1_columns),
+                placeholders: vec![],
             }),
             childrens: vec![],
             physical_option: None,
+            estimated_rows: None,
             _output_schema_ref: None,
         };
 
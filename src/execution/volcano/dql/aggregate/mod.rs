@@ -1,20 +1,38 @@
+mod approx_count_distinct;
+mod array_agg;
 mod avg;
+mod boolean;
 mod count;
+mod distinct;
 pub mod hash_agg;
 mod min_max;
+mod percentile;
 pub mod simple_agg;
+mod string_agg;
 mod sum;
+mod user_defined;
+mod variance;
 
 use crate::errors::DatabaseError;
+use crate::execution::volcano::dql::aggregate::approx_count_distinct::HyperLogLogAccumulator;
+use crate::execution::volcano::dql::aggregate::array_agg::ArrayAggAccumulator;
 use crate::execution::volcano::dql::aggregate::avg::AvgAccumulator;
+use crate::execution::volcano::dql::aggregate::boolean::BoolAccumulator;
 use crate::execution::volcano::dql::aggregate::count::{
     CountAccumulator, DistinctCountAccumulator,
 };
+use crate::execution::volcano::dql::aggregate::distinct::DistinctAccumulator;
 use crate::execution::volcano::dql::aggregate::min_max::MinMaxAccumulator;
+use crate::execution::volcano::dql::aggregate::percentile::PercentileAccumulator;
+use crate::execution::volcano::dql::aggregate::string_agg::StringAggAccumulator;
 use crate::execution::volcano::dql::aggregate::sum::{DistinctSumAccumulator, SumAccumulator};
+use crate::execution::volcano::dql::aggregate::user_defined::UserDefinedAccumulator;
+use crate::execution::volcano::dql::aggregate::variance::VarianceAccumulator;
 use crate::expression::agg::AggKind;
 use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
 use crate::types::value::ValueRef;
+use itertools::Itertools;
 
 /// Tips: Idea for sqlrs
 /// An accumulator represents a stateful object that lives throughout the evaluation of multiple
@@ -23,11 +41,17 @@ pub trait Accumulator: Send + Sync {
     /// updates the accumulator's state from a vector of arrays.
     fn update_value(&mut self, value: &ValueRef) -> Result<(), DatabaseError>;
 
+    /// updates the accumulator's state from the values of all the arguments,
+    /// which are only needed by the aggregates taking more than one argument.
+    fn update_values(&mut self, values: &[ValueRef]) -> Result<(), DatabaseError> {
+        self.update_value(&values[0])
+    }
+
     /// returns its value based on its current state.
     fn evaluate(&self) -> Result<ValueRef, DatabaseError>;
 }
 
-fn create_accumulator(expr: &ScalarExpression) -> Box<dyn Accumulator> {
+pub(crate) fn create_accumulator(expr: &ScalarExpression) -> Box<dyn Accumulator> {
     if let ScalarExpression::AggCall {
         kind, ty, distinct, ..
     } = expr
@@ -40,6 +64,32 @@ fn create_accumulator(expr: &ScalarExpression) -> Box<dyn Accumulator> {
             (AggKind::Min, _) => Box::new(MinMaxAccumulator::new(ty, false)),
             (AggKind::Max, _) => Box::new(MinMaxAccumulator::new(ty, true)),
             (AggKind::Avg, _) => Box::new(AvgAccumulator::new(ty)),
+            (AggKind::StddevSamp, distinct) => {
+                distinct_or(*distinct, VarianceAccumulator::new(true, true))
+            }
+            (AggKind::StddevPop, distinct) => {
+                distinct_or(*distinct, VarianceAccumulator::new(false, true))
+            }
+            (AggKind::VarSamp, distinct) => {
+                distinct_or(*distinct, VarianceAccumulator::new(true, false))
+            }
+            (AggKind::VarPop, distinct) => {
+                distinct_or(*distinct, VarianceAccumulator::new(false, false))
+            }
+            (AggKind::BoolAnd, _) => Box::new(BoolAccumulator::new(true)),
+            (AggKind::BoolOr, _) => Box::new(BoolAccumulator::new(false)),
+            (AggKind::StringAgg, distinct) => distinct_or(*distinct, StringAggAccumulator::new()),
+            (AggKind::ArrayAgg, distinct) => distinct_or(*distinct, ArrayAggAccumulator::new()),
+            (AggKind::ApproxCountDistinct, _) => Box::new(HyperLogLogAccumulator::new()),
+            (AggKind::Median, distinct) => {
+                distinct_or(*distinct, PercentileAccumulator::new(Some(0.5)))
+            }
+            (AggKind::PercentileCont, distinct) => {
+                distinct_or(*distinct, PercentileAccumulator::new(None))
+            }
+            (AggKind::UserDefined(function), distinct) => {
+                distinct_or(*distinct, UserDefinedAccumulator::new(function))
+            }
         }
     } else {
         unreachable!(
@@ -49,6 +99,30 @@ fn create_accumulator(expr: &ScalarExpression) -> Box<dyn Accumulator> {
     }
 }
 
+/// Wraps the accumulator to skip the arguments seen before if the aggregate is distinct.
+fn distinct_or<A: Accumulator + 'static>(distinct: bool, acc: A) -> Box<dyn Accumulator> {
+    if distinct {
+        Box::new(DistinctAccumulator::new(Box::new(acc)))
+    } else {
+        Box::new(acc)
+    }
+}
+
+/// The values of the arguments of the aggregate for the tuple.
+pub(crate) fn eval_agg_args(
+    expr: &ScalarExpression,
+    tuple: &Tuple,
+) -> Result<Vec<ValueRef>, DatabaseError> {
+    if let ScalarExpression::AggCall { args, .. } = expr {
+        args.iter().map(|arg| arg.eval(tuple)).try_collect()
+    } else {
+        unreachable!(
+            "eval_agg_args called with non-aggregate expression {}",
+            expr
+        );
+    }
+}
+
 pub(crate) fn create_accumulators(exprs: &[ScalarExpression]) -> Vec<Box<dyn Accumulator>> {
     exprs.iter().map(create_accumulator).collect()
 }
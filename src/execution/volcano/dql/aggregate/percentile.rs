@@ -0,0 +1,67 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::dql::aggregate::Accumulator;
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+use ordered_float::OrderedFloat;
+use std::sync::Arc;
+
+/// The continuous percentile interpolated between the values which are not NULL,
+/// the fraction is fixed for `MEDIAN`, otherwise it is given by the second argument
+pub struct PercentileAccumulator {
+    values: Vec<f64>,
+    fraction: Option<f64>,
+}
+
+impl PercentileAccumulator {
+    pub fn new(fraction: Option<f64>) -> Self {
+        Self {
+            values: vec![],
+            fraction,
+        }
+    }
+}
+
+fn double(value: &ValueRef) -> Result<Option<f64>, DatabaseError> {
+    Ok(DataValue::clone(value).cast(&LogicalType::Double)?.double())
+}
+
+impl Accumulator for PercentileAccumulator {
+    fn update_value(&mut self, value: &ValueRef) -> Result<(), DatabaseError> {
+        if let Some(value) = double(value)? {
+            self.values.push(value);
+        }
+
+        Ok(())
+    }
+
+    fn update_values(&mut self, values: &[ValueRef]) -> Result<(), DatabaseError> {
+        if let (None, Some(fraction)) = (self.fraction, values.get(1)) {
+            if let Some(fraction) = double(fraction)? {
+                if !(0.0..=1.0).contains(&fraction) {
+                    return Err(DatabaseError::InvalidArgument(
+                        "percentile_cont",
+                        fraction.to_string(),
+                    ));
+                }
+                self.fraction = Some(fraction);
+            }
+        }
+
+        self.update_value(&values[0])
+    }
+
+    fn evaluate(&self) -> Result<ValueRef, DatabaseError> {
+        let Some(fraction) = self.fraction.filter(|_| !self.values.is_empty()) else {
+            return Ok(Arc::new(DataValue::Float64(None)));
+        };
+        let mut values = self.values.clone();
+        values.sort_by_key(|value| OrderedFloat(*value));
+
+        let pos = fraction * (values.len() - 1) as f64;
+        let (lower, upper) = (values[pos.floor() as usize], values[pos.ceil() as usize]);
+
+        Ok(Arc::new(DataValue::Float64(Some(
+            lower + (upper - lower) * (pos - pos.floor()),
+        ))))
+    }
+}
@@ -1,5 +1,5 @@
 use crate::errors::DatabaseError;
-use crate::execution::volcano::dql::aggregate::create_accumulators;
+use crate::execution::volcano::dql::aggregate::{create_accumulators, eval_agg_args};
 use crate::execution::volcano::{build_read, BoxedExecutor, ReadExecutor};
 use crate::expression::ScalarExpression;
 use crate::planner::operator::aggregate::AggregateOperator;
@@ -47,17 +47,14 @@ impl SimpleAggExecutor {
                     .collect_vec()
             });
 
-            let values: Vec<ValueRef> = self
+            let values: Vec<Vec<ValueRef>> = self
                 .agg_calls
                 .iter()
-                .map(|expr| match expr {
-                    ScalarExpression::AggCall { args, .. } => args[0].eval(&tuple),
-                    _ => unreachable!(),
-                })
+                .map(|expr| eval_agg_args(expr, &tuple))
                 .try_collect()?;
 
-            for (acc, value) in accs.iter_mut().zip_eq(values.iter()) {
-                acc.update_value(value)?;
+            for (acc, values) in accs.iter_mut().zip_eq(values.iter()) {
+                acc.update_values(values)?;
             }
         }
 
@@ -0,0 +1,54 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::dql::aggregate::Accumulator;
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+use std::sync::Arc;
+
+/// Concatenates the values which are not NULL,
+/// each of which is preceded by the separator of its row except the first one
+pub struct StringAggAccumulator {
+    result: Option<String>,
+}
+
+impl StringAggAccumulator {
+    pub fn new() -> Self {
+        Self { result: None }
+    }
+}
+
+fn utf8(value: &ValueRef) -> Result<Option<String>, DatabaseError> {
+    Ok(DataValue::clone(value)
+        .cast(&LogicalType::Varchar(None))?
+        .utf8())
+}
+
+impl Accumulator for StringAggAccumulator {
+    fn update_value(&mut self, value: &ValueRef) -> Result<(), DatabaseError> {
+        self.update_values(&[value.clone()])
+    }
+
+    fn update_values(&mut self, values: &[ValueRef]) -> Result<(), DatabaseError> {
+        let Some(value) = utf8(&values[0])? else {
+            return Ok(());
+        };
+
+        match &mut self.result {
+            Some(result) => {
+                // Tips: `GROUP_CONCAT` without the separator uses the comma
+                let separator = match values.get(1) {
+                    Some(separator) => utf8(separator)?.unwrap_or_default(),
+                    None => ",".to_string(),
+                };
+                result.push_str(&separator);
+                result.push_str(&value);
+            }
+            None => self.result = Some(value),
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<ValueRef, DatabaseError> {
+        Ok(Arc::new(DataValue::Utf8(self.result.clone())))
+    }
+}
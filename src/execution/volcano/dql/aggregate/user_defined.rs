@@ -0,0 +1,47 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::dql::aggregate::Accumulator;
+use crate::expression::agg::AggregateFunction;
+use crate::types::value::{DataValue, ValueRef};
+use itertools::Itertools;
+use std::sync::Arc;
+
+/// Keeps the state of the aggregate registered by `DataBaseBuilder::register_aggregate_function`
+pub struct UserDefinedAccumulator {
+    function: AggregateFunction,
+    state: DataValue,
+}
+
+impl UserDefinedAccumulator {
+    pub fn new(function: &AggregateFunction) -> Self {
+        Self {
+            function: function.clone(),
+            state: function.inner.init_state(),
+        }
+    }
+}
+
+impl Accumulator for UserDefinedAccumulator {
+    fn update_value(&mut self, value: &ValueRef) -> Result<(), DatabaseError> {
+        self.update_values(&[value.clone()])
+    }
+
+    fn update_values(&mut self, values: &[ValueRef]) -> Result<(), DatabaseError> {
+        let args: Vec<ValueRef> = values
+            .iter()
+            .zip_eq(self.function.summary().arg_types.iter())
+            .map(|(value, ty)| {
+                if &value.logical_type() == ty {
+                    Ok(value.clone())
+                } else {
+                    Ok::<_, DatabaseError>(Arc::new(DataValue::clone(value).cast(ty)?))
+                }
+            })
+            .try_collect()?;
+
+        self.function.inner.update(&mut self.state, &args)
+    }
+
+    fn evaluate(&self) -> Result<ValueRef, DatabaseError> {
+        Ok(Arc::new(self.function.inner.evaluate(&self.state)?))
+    }
+}
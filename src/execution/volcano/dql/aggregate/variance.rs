@@ -0,0 +1,55 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::dql::aggregate::Accumulator;
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+use std::sync::Arc;
+
+/// Computes the variance or the standard deviation by Welford's online algorithm
+pub struct VarianceAccumulator {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    is_sample: bool,
+    is_stddev: bool,
+}
+
+impl VarianceAccumulator {
+    pub fn new(is_sample: bool, is_stddev: bool) -> Self {
+        Self {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            is_sample,
+            is_stddev,
+        }
+    }
+}
+
+impl Accumulator for VarianceAccumulator {
+    fn update_value(&mut self, value: &ValueRef) -> Result<(), DatabaseError> {
+        if let Some(value) = DataValue::clone(value).cast(&LogicalType::Double)?.double() {
+            self.count += 1;
+            let delta = value - self.mean;
+            self.mean += delta / self.count as f64;
+            self.m2 += delta * (value - self.mean);
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<ValueRef, DatabaseError> {
+        let variance = match (self.is_sample, self.count) {
+            (true, 0 | 1) | (false, 0) => None,
+            (true, count) => Some(self.m2 / (count - 1) as f64),
+            (false, count) => Some(self.m2 / count as f64),
+        };
+
+        Ok(Arc::new(DataValue::Float64(variance.map(|variance| {
+            if self.is_stddev {
+                variance.sqrt()
+            } else {
+                variance
+            }
+        }))))
+    }
+}
@@ -48,7 +48,7 @@ impl Describe {
             Arc::new(ColumnCatalog::new_dummy("DEFAULT".to_string())),
         ]);
         let key_fn = |column: &ColumnCatalog| {
-            if column.desc.is_primary {
+            if column.desc.is_primary() {
                 PRIMARY_KEY_TYPE.clone()
             } else if column.desc.is_unique {
                 UNIQUE_KEY_TYPE.clone()
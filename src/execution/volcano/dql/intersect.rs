@@ -0,0 +1,79 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::dql::aggregate::hash_agg::HashAggStatus;
+use crate::execution::volcano::{build_read, BoxedExecutor, ReadExecutor};
+use crate::planner::operator::intersect::IntersectOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::tuple::Tuple;
+use crate::types::value::ValueRef;
+use ahash::HashMap;
+use futures_async_stream::try_stream;
+
+pub struct Intersect {
+    op: IntersectOperator,
+    left_input: LogicalPlan,
+    right_input: LogicalPlan,
+}
+
+impl From<(IntersectOperator, LogicalPlan, LogicalPlan)> for Intersect {
+    fn from((op, left_input, right_input): (IntersectOperator, LogicalPlan, LogicalPlan)) -> Self {
+        Intersect {
+            op,
+            left_input,
+            right_input,
+        }
+    }
+}
+
+impl<T: Transaction> ReadExecutor<T> for Intersect {
+    fn execute(self, transaction: &T) -> BoxedExecutor {
+        self._execute(transaction)
+    }
+}
+
+impl Intersect {
+    #[try_stream(boxed, ok = Tuple, error = DatabaseError)]
+    pub async fn _execute<T: Transaction>(self, transaction: &T) {
+        let Intersect {
+            op:
+                IntersectOperator {
+                    left_schema_ref,
+                    right_schema_ref,
+                    is_all,
+                },
+            left_input,
+            right_input,
+        } = self;
+
+        let mut left_status = HashAggStatus::with_row_count(&left_schema_ref);
+        let mut right_status = HashAggStatus::with_row_count(&right_schema_ref);
+
+        #[for_await]
+        for tuple in build_read(left_input, transaction) {
+            left_status.update(tuple?)?;
+        }
+        #[for_await]
+        for tuple in build_read(right_input, transaction) {
+            right_status.update(tuple?)?;
+        }
+        let right_counts: HashMap<Vec<ValueRef>, usize> =
+            right_status.as_row_counts()?.into_iter().collect();
+
+        for (values, left_count) in left_status.as_row_counts()? {
+            let right_count = right_counts.get(&values).cloned().unwrap_or(0);
+            let count = if is_all {
+                left_count.min(right_count)
+            } else {
+                left_count.min(right_count).min(1)
+            };
+
+            for _ in 0..count {
+                yield Tuple {
+                    id: None,
+                    schema_ref: left_schema_ref.clone(),
+                    values: values.clone(),
+                };
+            }
+        }
+    }
+}
@@ -1,6 +1,5 @@
-use crate::catalog::{ColumnCatalog, ColumnRef};
 use crate::errors::DatabaseError;
-use crate::execution::volcano::dql::join::joins_nullable;
+use crate::execution::volcano::dql::join::join_schema;
 use crate::execution::volcano::{build_read, BoxedExecutor, ReadExecutor};
 use crate::expression::ScalarExpression;
 use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
@@ -13,7 +12,7 @@ use futures::stream::BoxStream;
 use futures::{stream, StreamExt};
 use futures_async_stream::try_stream;
 use itertools::Itertools;
-use std::sync::Arc;
+use std::mem;
 
 pub struct HashJoin {
     on: JoinCondition,
@@ -74,30 +73,15 @@ impl HashJoinStatus {
             JoinCondition::None => unreachable!("HashJoin must has on condition"),
         };
 
-        let fn_process = |schema: &mut Vec<ColumnRef>, force_nullable| {
-            for column in schema.iter_mut() {
-                let mut temp = ColumnCatalog::clone(column);
-                temp.nullable = force_nullable;
-
-                *column = Arc::new(temp);
-            }
-        };
-        let (left_force_nullable, right_force_nullable) = joins_nullable(&ty);
         let left_schema_len = left_schema.len();
-
-        let mut join_schema = Vec::clone(left_schema);
-        fn_process(&mut join_schema, left_force_nullable);
-        let mut right_schema = Vec::clone(right_schema);
-        fn_process(&mut right_schema, right_force_nullable);
-
-        join_schema.append(&mut right_schema);
+        let full_schema_ref = join_schema(&ty, left_schema, right_schema);
 
         HashJoinStatus {
             ty,
             filter,
             build_map: Default::default(),
 
-            full_schema_ref: Arc::new(join_schema),
+            full_schema_ref,
             left_schema_len,
             on_left_keys,
             on_right_keys,
@@ -136,7 +120,42 @@ impl HashJoinStatus {
         let values = Self::eval_keys(on_right_keys, &tuple)?;
 
         let mut join_tuples = Vec::with_capacity(1);
-        if let Some((tuples, is_used)) = build_map.get_mut(&values) {
+        if matches!(ty, JoinType::LeftSemi | JoinType::LeftAnti) {
+            // NULL never equals to anything
+            if values.iter().any(|value| value.is_null()) {
+                return Ok(join_tuples);
+            }
+            if let Some((tuples, _)) = build_map.get_mut(&values) {
+                let mut unmatched_tuples = Vec::with_capacity(tuples.len());
+
+                // the matched left tuples are output by the semi join and discarded by the anti join,
+                // so each of them is matched at most once
+                for left_tuple in mem::take(tuples) {
+                    if !Self::is_matched(filter, full_schema_ref, &left_tuple, &tuple)? {
+                        unmatched_tuples.push(left_tuple);
+                    } else if *ty == JoinType::LeftSemi {
+                        join_tuples.push(left_tuple);
+                    }
+                }
+                *tuples = unmatched_tuples;
+            }
+        } else if matches!(ty, JoinType::RightSemi | JoinType::RightAnti) {
+            let mut is_matched = false;
+
+            if !values.iter().any(|value| value.is_null()) {
+                if let Some((tuples, _)) = build_map.get(&values) {
+                    for left_tuple in tuples {
+                        if Self::is_matched(filter, full_schema_ref, left_tuple, &tuple)? {
+                            is_matched = true;
+                            break;
+                        }
+                    }
+                }
+            }
+            if is_matched == (*ty == JoinType::RightSemi) {
+                join_tuples.push(tuple);
+            }
+        } else if let Some((tuples, is_used)) = build_map.get_mut(&values) {
             *is_used = true;
             join_tuples.reserve(tuples.len());
 
@@ -174,6 +193,35 @@ impl HashJoinStatus {
         Ok(join_tuples)
     }
 
+    /// Whether the left tuple and the right tuple satisfy the filter, which is used by the semi join
+    /// and the anti join.
+    pub(crate) fn is_matched(
+        filter: &Option<ScalarExpression>,
+        full_schema_ref: &SchemaRef,
+        left_tuple: &Tuple,
+        right_tuple: &Tuple,
+    ) -> Result<bool, DatabaseError> {
+        let Some(expr) = filter else {
+            return Ok(true);
+        };
+        let tuple = Tuple {
+            id: None,
+            schema_ref: full_schema_ref.clone(),
+            values: left_tuple
+                .values
+                .iter()
+                .chain(right_tuple.values.iter())
+                .cloned()
+                .collect_vec(),
+        };
+
+        match expr.eval(&tuple)?.as_ref() {
+            DataValue::Boolean(Some(is_matched)) => Ok(*is_matched),
+            DataValue::Boolean(None) => Ok(false),
+            _ => Err(DatabaseError::InvalidType),
+        }
+    }
+
     pub(crate) fn filter(
         mut tuple: Tuple,
         filter: &Option<ScalarExpression>,
@@ -215,11 +263,17 @@ impl HashJoinStatus {
             ..
         } = self;
 
-        matches!(ty, JoinType::Left | JoinType::Full).then(|| {
+        matches!(ty, JoinType::Left | JoinType::Full | JoinType::LeftAnti).then(|| {
+            let is_anti = *ty == JoinType::LeftAnti;
+
             stream::iter(
                 build_map
                     .drain()
-                    .filter_map(|(_, (mut left_tuples, is_used))| {
+                    .filter_map(move |(_, (mut left_tuples, is_used))| {
+                        // the left tuples that are matched have been discarded by the anti join
+                        if is_anti {
+                            return Some(left_tuples);
+                        }
                         if !is_used {
                             for tuple in left_tuples.iter_mut() {
                                 tuple.schema_ref = full_schema_ref.clone();
@@ -303,20 +357,16 @@ mod test {
     use crate::catalog::{ColumnCatalog, ColumnDesc};
     use crate::errors::DatabaseError;
     use crate::execution::volcano::dql::join::hash_join::HashJoin;
-    use crate::execution::volcano::dql::test::build_integers;
+    use crate::execution::volcano::dql::test::{build_integers, build_transaction};
     use crate::execution::volcano::{try_collect, ReadExecutor};
     use crate::expression::ScalarExpression;
     use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
     use crate::planner::operator::values::ValuesOperator;
     use crate::planner::operator::Operator;
     use crate::planner::LogicalPlan;
-    use crate::storage::kip::KipStorage;
-    use crate::storage::Storage;
-    use crate::types::tuple::create_table;
     use crate::types::value::DataValue;
     use crate::types::LogicalType;
     use std::sync::Arc;
-    use tempfile::TempDir;
 
     fn build_join_values() -> (
         Vec<(ScalarExpression, ScalarExpression)>,
@@ -362,9 +412,11 @@ mod test {
                     ],
                 ],
                 schema_ref: Arc::new(t1_columns),
+                placeholders: vec![],
             }),
             childrens: vec![],
             physical_option: None,
+            estimated_rows: None,
             _output_schema_ref: None,
         };
 
@@ -393,9 +445,11 @@ mod test {
                     ],
                 ],
                 schema_ref: Arc::new(t2_columns),
+                placeholders: vec![],
             }),
             childrens: vec![],
             physical_option: None,
+            estimated_rows: None,
             _output_schema_ref: None,
         };
 
@@ -404,9 +458,7 @@ mod test {
 
     #[tokio::test]
     async fn test_inner_join() -> Result<(), DatabaseError> {
-        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
-        let storage = KipStorage::new(temp_dir.path()).await?;
-        let transaction = storage.transaction().await?;
+        let (_temp_dir, transaction) = build_transaction().await?;
         let (keys, left, right) = build_join_values();
 
         let op = JoinOperator {
@@ -419,8 +471,6 @@ mod test {
         let mut executor = HashJoin::from((op, left, right)).execute(&transaction);
         let tuples = try_collect(&mut executor).await?;
 
-        println!("inner_test: \n{}", create_table(&tuples));
-
         assert_eq!(tuples.len(), 3);
 
         assert_eq!(
@@ -441,9 +491,7 @@ mod test {
 
     #[tokio::test]
     async fn test_left_join() -> Result<(), DatabaseError> {
-        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
-        let storage = KipStorage::new(temp_dir.path()).await?;
-        let transaction = storage.transaction().await?;
+        let (_temp_dir, transaction) = build_transaction().await?;
         let (keys, left, right) = build_join_values();
 
         let op = JoinOperator {
@@ -456,8 +504,6 @@ mod test {
         let mut executor = HashJoin::from((op, left, right)).execute(&transaction);
         let tuples = try_collect(&mut executor).await?;
 
-        println!("left_test: \n{}", create_table(&tuples));
-
         assert_eq!(tuples.len(), 4);
 
         assert_eq!(
@@ -482,9 +528,7 @@ mod test {
 
     #[tokio::test]
     async fn test_right_join() -> Result<(), DatabaseError> {
-        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
-        let storage = KipStorage::new(temp_dir.path()).await?;
-        let transaction = storage.transaction().await?;
+        let (_temp_dir, transaction) = build_transaction().await?;
         let (keys, left, right) = build_join_values();
 
         let op = JoinOperator {
@@ -497,8 +541,6 @@ mod test {
         let mut executor = HashJoin::from((op, left, right)).execute(&transaction);
         let tuples = try_collect(&mut executor).await?;
 
-        println!("right_test: \n{}", create_table(&tuples));
-
         assert_eq!(tuples.len(), 4);
 
         assert_eq!(
@@ -523,9 +565,7 @@ mod test {
 
     #[tokio::test]
     async fn test_full_join() -> Result<(), DatabaseError> {
-        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
-        let storage = KipStorage::new(temp_dir.path()).await?;
-        let transaction = storage.transaction().await?;
+        let (_temp_dir, transaction) = build_transaction().await?;
         let (keys, left, right) = build_join_values();
 
         let op = JoinOperator {
@@ -538,8 +578,6 @@ mod test {
         let mut executor = HashJoin::from((op, left, right)).execute(&transaction);
         let tuples = try_collect(&mut executor).await?;
 
-        println!("full_test: \n{}", create_table(&tuples));
-
         assert_eq!(tuples.len(), 5);
 
         assert_eq!(
@@ -565,4 +603,116 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_left_semi_join() -> Result<(), DatabaseError> {
+        let (_temp_dir, transaction) = build_transaction().await?;
+        let (keys, left, right) = build_join_values();
+
+        let op = JoinOperator {
+            on: JoinCondition::On {
+                on: keys,
+                filter: None,
+            },
+            join_type: JoinType::LeftSemi,
+        };
+        let mut executor = HashJoin::from((op, left, right)).execute(&transaction);
+        let tuples = try_collect(&mut executor).await?;
+
+        assert_eq!(tuples.len(), 2);
+
+        assert_eq!(
+            tuples[0].values,
+            build_integers(vec![Some(0), Some(2), Some(4)])
+        );
+        assert_eq!(
+            tuples[1].values,
+            build_integers(vec![Some(1), Some(3), Some(5)])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_left_anti_join() -> Result<(), DatabaseError> {
+        let (_temp_dir, transaction) = build_transaction().await?;
+        let (keys, left, right) = build_join_values();
+
+        let op = JoinOperator {
+            on: JoinCondition::On {
+                on: keys,
+                filter: None,
+            },
+            join_type: JoinType::LeftAnti,
+        };
+        let mut executor = HashJoin::from((op, left, right)).execute(&transaction);
+        let tuples = try_collect(&mut executor).await?;
+
+        assert_eq!(tuples.len(), 1);
+
+        assert_eq!(
+            tuples[0].values,
+            build_integers(vec![Some(3), Some(5), Some(7)])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_right_semi_join() -> Result<(), DatabaseError> {
+        let (_temp_dir, transaction) = build_transaction().await?;
+        let (keys, left, right) = build_join_values();
+
+        let op = JoinOperator {
+            on: JoinCondition::On {
+                on: keys,
+                filter: None,
+            },
+            join_type: JoinType::RightSemi,
+        };
+        let mut executor = HashJoin::from((op, left, right)).execute(&transaction);
+        let tuples = try_collect(&mut executor).await?;
+
+        assert_eq!(tuples.len(), 3);
+
+        assert_eq!(
+            tuples[0].values,
+            build_integers(vec![Some(0), Some(2), Some(4)])
+        );
+        assert_eq!(
+            tuples[1].values,
+            build_integers(vec![Some(1), Some(3), Some(5)])
+        );
+        assert_eq!(
+            tuples[2].values,
+            build_integers(vec![Some(1), Some(1), Some(1)])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_right_anti_join() -> Result<(), DatabaseError> {
+        let (_temp_dir, transaction) = build_transaction().await?;
+        let (keys, left, right) = build_join_values();
+
+        let op = JoinOperator {
+            on: JoinCondition::On {
+                on: keys,
+                filter: None,
+            },
+            join_type: JoinType::RightAnti,
+        };
+        let mut executor = HashJoin::from((op, left, right)).execute(&transaction);
+        let tuples = try_collect(&mut executor).await?;
+
+        assert_eq!(tuples.len(), 1);
+
+        assert_eq!(
+            tuples[0].values,
+            build_integers(vec![Some(4), Some(6), Some(8)])
+        );
+
+        Ok(())
+    }
 }
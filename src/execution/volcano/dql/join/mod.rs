@@ -1,6 +1,11 @@
+use crate::catalog::ColumnCatalog;
 use crate::planner::operator::join::JoinType;
+use crate::types::tuple::SchemaRef;
+use std::sync::Arc;
 
 pub(crate) mod hash_join;
+pub(crate) mod nested_loop_join;
+pub(crate) mod sort_merge_join;
 
 pub fn joins_nullable(join_type: &JoinType) -> (bool, bool) {
     match join_type {
@@ -9,5 +14,33 @@ pub fn joins_nullable(join_type: &JoinType) -> (bool, bool) {
         JoinType::Right => (true, false),
         JoinType::Full => (true, true),
         JoinType::Cross => (true, true),
+        JoinType::LeftSemi | JoinType::LeftAnti | JoinType::RightSemi | JoinType::RightAnti => {
+            (false, false)
+        }
     }
 }
+
+/// The schema of the joined tuples, whose columns are nullable on the side that may be padded with NULL.
+pub(crate) fn join_schema(
+    join_type: &JoinType,
+    left_schema: &SchemaRef,
+    right_schema: &SchemaRef,
+) -> SchemaRef {
+    let (left_force_nullable, right_force_nullable) = joins_nullable(join_type);
+    Arc::new(
+        [
+            (left_schema, left_force_nullable),
+            (right_schema, right_force_nullable),
+        ]
+        .into_iter()
+        .flat_map(|(schema, force_nullable)| {
+            schema.iter().map(move |column| {
+                let mut temp = ColumnCatalog::clone(column);
+                temp.nullable = force_nullable;
+
+                Arc::new(temp)
+            })
+        })
+        .collect(),
+    )
+}
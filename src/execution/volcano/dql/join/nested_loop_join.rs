@@ -0,0 +1,331 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::dql::join::hash_join::HashJoinStatus;
+use crate::execution::volcano::dql::join::join_schema;
+use crate::execution::volcano::{build_read, BoxedExecutor, ReadExecutor};
+use crate::expression::ScalarExpression;
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::tuple::{SchemaRef, Tuple};
+use crate::types::value::{ValueRef, NULL_VALUE};
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use std::mem;
+
+pub struct NestedLoopJoin {
+    on: JoinCondition,
+    ty: JoinType,
+    left_input: LogicalPlan,
+    right_input: LogicalPlan,
+}
+
+impl From<(JoinOperator, LogicalPlan, LogicalPlan)> for NestedLoopJoin {
+    fn from(
+        (JoinOperator { on, join_type, .. }, left_input, right_input): (
+            JoinOperator,
+            LogicalPlan,
+            LogicalPlan,
+        ),
+    ) -> Self {
+        NestedLoopJoin {
+            on,
+            ty: join_type,
+            left_input,
+            right_input,
+        }
+    }
+}
+
+impl<T: Transaction> ReadExecutor<T> for NestedLoopJoin {
+    fn execute(self, transaction: &T) -> BoxedExecutor {
+        self._execute(transaction)
+    }
+}
+
+/// Each of the right tuples is compared with all the left tuples that have been built,
+/// so the join condition can be any expression.
+pub(crate) struct NestedLoopJoinStatus {
+    ty: JoinType,
+    filter: Option<ScalarExpression>,
+    /// the left tuples with their keys and whether they have been matched
+    left_tuples: Vec<(Vec<ValueRef>, Tuple, bool)>,
+
+    full_schema_ref: SchemaRef,
+    on_left_keys: Vec<ScalarExpression>,
+    on_right_keys: Vec<ScalarExpression>,
+}
+
+impl NestedLoopJoinStatus {
+    pub(crate) fn new(
+        on: JoinCondition,
+        ty: JoinType,
+        left_schema: &SchemaRef,
+        right_schema: &SchemaRef,
+    ) -> Self {
+        let ((on_left_keys, on_right_keys), filter): (
+            (Vec<ScalarExpression>, Vec<ScalarExpression>),
+            _,
+        ) = match on {
+            JoinCondition::On { on, filter } => (on.into_iter().unzip(), filter),
+            JoinCondition::None => ((vec![], vec![]), None),
+        };
+
+        NestedLoopJoinStatus {
+            ty,
+            filter,
+            left_tuples: Vec::new(),
+
+            full_schema_ref: join_schema(&ty, left_schema, right_schema),
+            on_left_keys,
+            on_right_keys,
+        }
+    }
+
+    pub(crate) fn left_build(&mut self, tuple: Tuple) -> Result<(), DatabaseError> {
+        let values = Self::eval_keys(&self.on_left_keys, &tuple)?;
+        self.left_tuples.push((values, tuple, false));
+
+        Ok(())
+    }
+
+    pub(crate) fn right_probe(&mut self, tuple: Tuple) -> Result<Vec<Tuple>, DatabaseError> {
+        let NestedLoopJoinStatus {
+            ty,
+            filter,
+            left_tuples,
+            full_schema_ref,
+            on_right_keys,
+            ..
+        } = self;
+        let values = Self::eval_keys(on_right_keys, &tuple)?;
+        // NULL never equals to anything
+        let is_null = values.iter().any(|value| value.is_null());
+
+        let mut join_tuples = Vec::new();
+        let mut is_right_matched = false;
+
+        for (left_values, left_tuple, is_left_matched) in left_tuples.iter_mut() {
+            if is_null
+                || left_values != &values
+                || !HashJoinStatus::is_matched(filter, full_schema_ref, left_tuple, &tuple)?
+            {
+                continue;
+            }
+            match ty {
+                JoinType::LeftSemi => {
+                    if !*is_left_matched {
+                        join_tuples.push(left_tuple.clone());
+                    }
+                }
+                JoinType::LeftAnti | JoinType::RightSemi | JoinType::RightAnti => (),
+                _ => join_tuples.push(Tuple {
+                    id: None,
+                    schema_ref: full_schema_ref.clone(),
+                    values: left_tuple
+                        .values
+                        .iter()
+                        .chain(tuple.values.iter())
+                        .cloned()
+                        .collect_vec(),
+                }),
+            }
+            *is_left_matched = true;
+            is_right_matched = true;
+        }
+
+        match ty {
+            JoinType::Right | JoinType::Full if !is_right_matched => {
+                let empty_len = full_schema_ref.len() - tuple.values.len();
+
+                join_tuples.push(Tuple {
+                    id: None,
+                    schema_ref: full_schema_ref.clone(),
+                    values: (0..empty_len)
+                        .map(|_| NULL_VALUE.clone())
+                        .chain(tuple.values)
+                        .collect_vec(),
+                });
+            }
+            JoinType::RightSemi if is_right_matched => join_tuples.push(tuple),
+            JoinType::RightAnti if !is_right_matched => join_tuples.push(tuple),
+            _ => (),
+        }
+
+        Ok(join_tuples)
+    }
+
+    /// Takes all the left tuples that have been built, and outputs those that are not matched
+    /// for the left join, the full join and the left anti join.
+    pub(crate) fn build_drop(&mut self) -> Vec<Tuple> {
+        let NestedLoopJoinStatus {
+            ty,
+            left_tuples,
+            full_schema_ref,
+            ..
+        } = self;
+        let left_tuples = mem::take(left_tuples);
+
+        match ty {
+            JoinType::Left | JoinType::Full => left_tuples
+                .into_iter()
+                .filter(|(_, _, is_matched)| !is_matched)
+                .map(|(_, mut tuple, _)| {
+                    tuple.schema_ref = full_schema_ref.clone();
+                    tuple
+                        .values
+                        .resize(full_schema_ref.len(), NULL_VALUE.clone());
+
+                    tuple
+                })
+                .collect_vec(),
+            JoinType::LeftAnti => left_tuples
+                .into_iter()
+                .filter(|(_, _, is_matched)| !is_matched)
+                .map(|(_, tuple, _)| tuple)
+                .collect_vec(),
+            _ => vec![],
+        }
+    }
+
+    fn eval_keys(
+        on_keys: &[ScalarExpression],
+        tuple: &Tuple,
+    ) -> Result<Vec<ValueRef>, DatabaseError> {
+        on_keys.iter().map(|expr| expr.eval(tuple)).try_collect()
+    }
+}
+
+impl NestedLoopJoin {
+    #[try_stream(boxed, ok = Tuple, error = DatabaseError)]
+    pub async fn _execute<T: Transaction>(self, transaction: &T) {
+        let NestedLoopJoin {
+            on,
+            ty,
+            mut left_input,
+            mut right_input,
+        } = self;
+
+        let mut join_status = NestedLoopJoinStatus::new(
+            on,
+            ty,
+            left_input.output_schema(),
+            right_input.output_schema(),
+        );
+
+        #[for_await]
+        for tuple in build_read(left_input, transaction) {
+            let tuple: Tuple = tuple?;
+
+            join_status.left_build(tuple)?;
+        }
+
+        #[for_await]
+        for tuple in build_read(right_input, transaction) {
+            let tuple: Tuple = tuple?;
+
+            for tuple in join_status.right_probe(tuple)? {
+                yield tuple
+            }
+        }
+
+        for tuple in join_status.build_drop() {
+            yield tuple
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::catalog::{ColumnCatalog, ColumnDesc};
+    use crate::errors::DatabaseError;
+    use crate::execution::volcano::dql::join::nested_loop_join::NestedLoopJoin;
+    use crate::execution::volcano::dql::test::{build_integers, build_transaction};
+    use crate::execution::volcano::{try_collect, ReadExecutor};
+    use crate::expression::{BinaryOperator, ScalarExpression};
+    use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+    use crate::planner::operator::values::ValuesOperator;
+    use crate::planner::operator::Operator;
+    use crate::planner::LogicalPlan;
+    use crate::types::value::DataValue;
+    use crate::types::LogicalType;
+    use itertools::Itertools;
+    use std::sync::Arc;
+
+    fn build_values(name: &str, rows: Vec<Vec<i32>>) -> (LogicalPlan, ScalarExpression) {
+        let desc = ColumnDesc::new(LogicalType::Integer, false, false, None);
+        let column = Arc::new(ColumnCatalog::new(name.to_string(), true, desc));
+        let plan = LogicalPlan {
+            operator: Operator::Values(ValuesOperator {
+                rows: rows
+                    .into_iter()
+                    .map(|row| {
+                        row.into_iter()
+                            .map(|v| Arc::new(DataValue::Int32(Some(v))))
+                            .collect_vec()
+                    })
+                    .collect_vec(),
+                schema_ref: Arc::new(vec![column.clone()]),
+                placeholders: vec![],
+            }),
+            childrens: vec![],
+            physical_option: None,
+            estimated_rows: None,
+            _output_schema_ref: None,
+        };
+
+        (plan, ScalarExpression::ColumnRef(column))
+    }
+
+    #[tokio::test]
+    async fn test_theta_join() -> Result<(), DatabaseError> {
+        let (_temp_dir, transaction) = build_transaction().await?;
+        let (left, c1) = build_values("c1", vec![vec![1], vec![2], vec![3]]);
+        let (right, c2) = build_values("c2", vec![vec![2], vec![0]]);
+
+        // c1 < c2
+        let op = JoinOperator {
+            on: JoinCondition::On {
+                on: vec![],
+                filter: Some(ScalarExpression::Binary {
+                    op: BinaryOperator::Lt,
+                    left_expr: Box::new(c1),
+                    right_expr: Box::new(c2),
+                    ty: LogicalType::Boolean,
+                }),
+            },
+            join_type: JoinType::Full,
+        };
+        let mut executor = NestedLoopJoin::from((op, left, right)).execute(&transaction);
+        let tuples = try_collect(&mut executor).await?;
+
+        assert_eq!(tuples.len(), 4);
+        assert_eq!(tuples[0].values, build_integers(vec![Some(1), Some(2)]));
+        assert_eq!(tuples[1].values, build_integers(vec![None, Some(0)]));
+        assert_eq!(tuples[2].values, build_integers(vec![Some(2), None]));
+        assert_eq!(tuples[3].values, build_integers(vec![Some(3), None]));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cross_join() -> Result<(), DatabaseError> {
+        let (_temp_dir, transaction) = build_transaction().await?;
+        let (left, _) = build_values("c1", vec![vec![1], vec![2]]);
+        let (right, _) = build_values("c2", vec![vec![3], vec![4]]);
+
+        let op = JoinOperator {
+            on: JoinCondition::None,
+            join_type: JoinType::Cross,
+        };
+        let mut executor = NestedLoopJoin::from((op, left, right)).execute(&transaction);
+        let tuples = try_collect(&mut executor).await?;
+
+        assert_eq!(tuples.len(), 4);
+        assert_eq!(tuples[0].values, build_integers(vec![Some(1), Some(3)]));
+        assert_eq!(tuples[1].values, build_integers(vec![Some(2), Some(3)]));
+        assert_eq!(tuples[2].values, build_integers(vec![Some(1), Some(4)]));
+        assert_eq!(tuples[3].values, build_integers(vec![Some(2), Some(4)]));
+
+        Ok(())
+    }
+}
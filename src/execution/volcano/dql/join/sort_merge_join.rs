@@ -0,0 +1,313 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::dql::join::nested_loop_join::NestedLoopJoinStatus;
+use crate::execution::volcano::{build_read, BoxedExecutor, ReadExecutor};
+use crate::expression::ScalarExpression;
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::tuple::Tuple;
+use futures_async_stream::try_stream;
+use std::cmp::Ordering;
+
+pub struct SortMergeJoin {
+    on: JoinCondition,
+    ty: JoinType,
+    left_input: LogicalPlan,
+    right_input: LogicalPlan,
+}
+
+impl From<(JoinOperator, LogicalPlan, LogicalPlan)> for SortMergeJoin {
+    fn from(
+        (JoinOperator { on, join_type, .. }, left_input, right_input): (
+            JoinOperator,
+            LogicalPlan,
+            LogicalPlan,
+        ),
+    ) -> Self {
+        SortMergeJoin {
+            on,
+            ty: join_type,
+            left_input,
+            right_input,
+        }
+    }
+}
+
+impl<T: Transaction> ReadExecutor<T> for SortMergeJoin {
+    fn execute(self, transaction: &T) -> BoxedExecutor {
+        self._execute(transaction)
+    }
+}
+
+/// The tuples sorted by the memcomparable keys of the join, and the tuples whose keys contain NULL,
+/// which never match anything.
+struct SortedTuples {
+    tuples: Vec<(Vec<u8>, Tuple)>,
+    null_tuples: Vec<Tuple>,
+}
+
+impl SortedTuples {
+    fn new(on_keys: &[ScalarExpression], tuples: Vec<Tuple>) -> Result<Self, DatabaseError> {
+        let mut sorted_tuples = Vec::with_capacity(tuples.len());
+        let mut null_tuples = Vec::new();
+
+        'outer: for tuple in tuples {
+            let mut key = Vec::new();
+
+            for expr in on_keys {
+                let value = expr.eval(&tuple)?;
+
+                if value.is_null() {
+                    null_tuples.push(tuple);
+                    continue 'outer;
+                }
+                value.memcomparable_encode(&mut key)?;
+            }
+            sorted_tuples.push((key, tuple));
+        }
+        // the tuples from the index scan or the primary key are already in order
+        if !sorted_tuples.windows(2).all(|pair| pair[0].0 <= pair[1].0) {
+            sorted_tuples.sort_by(|(key_1, _), (key_2, _)| key_1.cmp(key_2));
+        }
+
+        Ok(SortedTuples {
+            tuples: sorted_tuples,
+            null_tuples,
+        })
+    }
+
+    /// The length of the tuples from `start` that have the same key.
+    fn group_len(&self, start: usize) -> usize {
+        let key = &self.tuples[start].0;
+
+        self.tuples[start..]
+            .iter()
+            .take_while(|(other_key, _)| other_key == key)
+            .count()
+    }
+}
+
+impl SortMergeJoin {
+    #[try_stream(boxed, ok = Tuple, error = DatabaseError)]
+    pub async fn _execute<T: Transaction>(self, transaction: &T) {
+        let SortMergeJoin {
+            on,
+            ty,
+            mut left_input,
+            mut right_input,
+        } = self;
+        let (on_left_keys, on_right_keys, filter): (Vec<_>, Vec<_>, _) = match on {
+            JoinCondition::On { on, filter } => {
+                let (on_left_keys, on_right_keys) = on.into_iter().unzip();
+
+                (on_left_keys, on_right_keys, filter)
+            }
+            JoinCondition::None => unreachable!("SortMergeJoin must has on condition"),
+        };
+        // the keys of the tuples in the same group are equal,
+        // so only the filter needs to be checked by the nested loop
+        let mut join_status = NestedLoopJoinStatus::new(
+            JoinCondition::On { on: vec![], filter },
+            ty,
+            left_input.output_schema(),
+            right_input.output_schema(),
+        );
+
+        let mut left_tuples = Vec::new();
+        #[for_await]
+        for tuple in build_read(left_input, transaction) {
+            left_tuples.push(tuple?);
+        }
+        let mut right_tuples = Vec::new();
+        #[for_await]
+        for tuple in build_read(right_input, transaction) {
+            right_tuples.push(tuple?);
+        }
+        let left = SortedTuples::new(&on_left_keys, left_tuples)?;
+        let right = SortedTuples::new(&on_right_keys, right_tuples)?;
+
+        // merge phase
+        let (mut left_i, mut right_i) = (0, 0);
+        while left_i < left.tuples.len() || right_i < right.tuples.len() {
+            let ordering = match (left.tuples.get(left_i), right.tuples.get(right_i)) {
+                (Some((left_key, _)), Some((right_key, _))) => left_key.cmp(right_key),
+                (Some(_), None) => Ordering::Less,
+                (None, _) => Ordering::Greater,
+            };
+            let (left_len, right_len) = match ordering {
+                Ordering::Less => (1, 0),
+                Ordering::Greater => (0, 1),
+                Ordering::Equal => (left.group_len(left_i), right.group_len(right_i)),
+            };
+
+            for (_, tuple) in &left.tuples[left_i..left_i + left_len] {
+                join_status.left_build(tuple.clone())?;
+            }
+            for (_, tuple) in &right.tuples[right_i..right_i + right_len] {
+                for tuple in join_status.right_probe(tuple.clone())? {
+                    yield tuple
+                }
+            }
+            for tuple in join_status.build_drop() {
+                yield tuple
+            }
+            left_i += left_len;
+            right_i += right_len;
+        }
+
+        for tuple in left.null_tuples {
+            join_status.left_build(tuple)?;
+        }
+        for tuple in join_status.build_drop() {
+            yield tuple
+        }
+        for tuple in right.null_tuples {
+            for tuple in join_status.right_probe(tuple)? {
+                yield tuple
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::catalog::{ColumnCatalog, ColumnDesc};
+    use crate::errors::DatabaseError;
+    use crate::execution::volcano::dql::join::sort_merge_join::SortMergeJoin;
+    use crate::execution::volcano::dql::test::{build_integers, build_transaction};
+    use crate::execution::volcano::{try_collect, ReadExecutor};
+    use crate::expression::ScalarExpression;
+    use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+    use crate::planner::operator::values::ValuesOperator;
+    use crate::planner::operator::Operator;
+    use crate::planner::LogicalPlan;
+    use crate::types::LogicalType;
+    use itertools::Itertools;
+    use std::sync::Arc;
+
+    fn build_values(
+        names: [&str; 2],
+        rows: Vec<[Option<i32>; 2]>,
+    ) -> (LogicalPlan, ScalarExpression) {
+        let desc = ColumnDesc::new(LogicalType::Integer, false, false, None);
+        let columns = names
+            .iter()
+            .map(|name| Arc::new(ColumnCatalog::new(name.to_string(), true, desc.clone())))
+            .collect_vec();
+        let key = ScalarExpression::ColumnRef(columns[0].clone());
+        let plan = LogicalPlan {
+            operator: Operator::Values(ValuesOperator {
+                rows: rows
+                    .into_iter()
+                    .map(|row| build_integers(row.to_vec()))
+                    .collect_vec(),
+                schema_ref: Arc::new(columns),
+                placeholders: vec![],
+            }),
+            childrens: vec![],
+            physical_option: None,
+            estimated_rows: None,
+            _output_schema_ref: None,
+        };
+
+        (plan, key)
+    }
+
+    fn build_join(join_type: JoinType) -> SortMergeJoin {
+        let (left, c1) = build_values(
+            ["c1", "c2"],
+            vec![
+                [Some(3), Some(0)],
+                [Some(1), Some(1)],
+                [None, Some(2)],
+                [Some(1), Some(3)],
+            ],
+        );
+        let (right, c3) = build_values(
+            ["c3", "c4"],
+            vec![[Some(0), Some(4)], [Some(1), Some(5)], [Some(2), Some(6)]],
+        );
+
+        SortMergeJoin::from((
+            JoinOperator {
+                on: JoinCondition::On {
+                    on: vec![(c1, c3)],
+                    filter: None,
+                },
+                join_type,
+            },
+            left,
+            right,
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_inner_join() -> Result<(), DatabaseError> {
+        let (_temp_dir, transaction) = build_transaction().await?;
+
+        let mut executor = build_join(JoinType::Inner).execute(&transaction);
+        let tuples = try_collect(&mut executor).await?;
+
+        assert_eq!(tuples.len(), 2);
+        assert_eq!(
+            tuples[0].values,
+            build_integers(vec![Some(1), Some(1), Some(1), Some(5)])
+        );
+        assert_eq!(
+            tuples[1].values,
+            build_integers(vec![Some(1), Some(3), Some(1), Some(5)])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_full_join() -> Result<(), DatabaseError> {
+        let (_temp_dir, transaction) = build_transaction().await?;
+
+        let mut executor = build_join(JoinType::Full).execute(&transaction);
+        let tuples = try_collect(&mut executor).await?;
+
+        assert_eq!(tuples.len(), 6);
+        assert_eq!(
+            tuples[0].values,
+            build_integers(vec![None, None, Some(0), Some(4)])
+        );
+        assert_eq!(
+            tuples[1].values,
+            build_integers(vec![Some(1), Some(1), Some(1), Some(5)])
+        );
+        assert_eq!(
+            tuples[2].values,
+            build_integers(vec![Some(1), Some(3), Some(1), Some(5)])
+        );
+        assert_eq!(
+            tuples[3].values,
+            build_integers(vec![None, None, Some(2), Some(6)])
+        );
+        assert_eq!(
+            tuples[4].values,
+            build_integers(vec![Some(3), Some(0), None, None])
+        );
+        assert_eq!(
+            tuples[5].values,
+            build_integers(vec![None, Some(2), None, None])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_left_anti_join() -> Result<(), DatabaseError> {
+        let (_temp_dir, transaction) = build_transaction().await?;
+
+        let mut executor = build_join(JoinType::LeftAnti).execute(&transaction);
+        let tuples = try_collect(&mut executor).await?;
+
+        assert_eq!(tuples.len(), 2);
+        assert_eq!(tuples[0].values, build_integers(vec![Some(3), Some(0)]));
+        assert_eq!(tuples[1].values, build_integers(vec![None, Some(2)]));
+
+        Ok(())
+    }
+}
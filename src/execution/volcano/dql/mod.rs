@@ -1,23 +1,41 @@
 pub(crate) mod aggregate;
 pub(crate) mod describe;
 pub(crate) mod dummy;
+pub(crate) mod except;
 pub(crate) mod explain;
 pub(crate) mod filter;
 pub(crate) mod index_scan;
+pub(crate) mod intersect;
 pub(crate) mod join;
 pub(crate) mod limit;
 pub(crate) mod projection;
+pub(crate) mod recursive_cte;
 pub(crate) mod seq_scan;
 pub(crate) mod show_table;
 pub(crate) mod sort;
+pub(crate) mod table_function;
 pub(crate) mod union;
 pub(crate) mod values;
+pub(crate) mod window;
 
 #[cfg(test)]
 pub(crate) mod test {
+    use crate::errors::DatabaseError;
+    use crate::storage::kip::{KipStorage, KipTransaction};
+    use crate::storage::Storage;
     use crate::types::value::{DataValue, ValueRef};
     use itertools::Itertools;
     use std::sync::Arc;
+    use tempfile::TempDir;
+
+    /// The transaction on the storage in the temporary directory, which is removed once it is dropped
+    pub(crate) async fn build_transaction() -> Result<(TempDir, KipTransaction), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = KipStorage::new(temp_dir.path()).await?;
+        let transaction = storage.transaction().await?;
+
+        Ok((temp_dir, transaction))
+    }
 
     pub(crate) fn build_integers(ints: Vec<Option<i32>>) -> Vec<ValueRef> {
         ints.into_iter()
@@ -0,0 +1,105 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::{build_read, BoxedExecutor, ReadExecutor};
+use crate::planner::operator::recursive_cte::RecursiveCteOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::tuple::{SchemaRef, Tuple};
+use crate::types::value::ValueRef;
+use futures_async_stream::try_stream;
+use std::collections::HashSet;
+use std::mem;
+
+pub struct RecursiveCte {
+    op: RecursiveCteOperator,
+    anchor_input: LogicalPlan,
+    recursive_input: LogicalPlan,
+}
+
+impl From<(RecursiveCteOperator, LogicalPlan, LogicalPlan)> for RecursiveCte {
+    fn from(
+        (op, anchor_input, recursive_input): (RecursiveCteOperator, LogicalPlan, LogicalPlan),
+    ) -> Self {
+        RecursiveCte {
+            op,
+            anchor_input,
+            recursive_input,
+        }
+    }
+}
+
+impl<T: Transaction> ReadExecutor<T> for RecursiveCte {
+    fn execute(self, transaction: &T) -> BoxedExecutor {
+        self._execute(transaction)
+    }
+}
+
+impl RecursiveCte {
+    #[try_stream(boxed, ok = Tuple, error = DatabaseError)]
+    pub async fn _execute<T: Transaction>(self, transaction: &T) {
+        let RecursiveCte {
+            op:
+                RecursiveCteOperator {
+                    schema_ref,
+                    is_distinct,
+                    max_recursion_depth,
+                    ..
+                },
+            anchor_input,
+            recursive_input,
+        } = self;
+        let mut seen = HashSet::new();
+        let mut working_rows = Vec::new();
+        let mut depth = 0;
+
+        #[for_await]
+        for tuple in build_read(anchor_input, transaction) {
+            let values = tuple?.values;
+
+            if !is_distinct || seen.insert(values.clone()) {
+                working_rows.push(values.clone());
+
+                yield Tuple {
+                    id: None,
+                    schema_ref: schema_ref.clone(),
+                    values,
+                };
+            }
+        }
+        // the recursive term is evaluated over the rows of the last iteration until it produces none
+        while !working_rows.is_empty() {
+            if depth == max_recursion_depth {
+                Err(DatabaseError::TooDeepRecursion(max_recursion_depth))?;
+            }
+            depth += 1;
+            let mut plan = recursive_input.clone();
+            Self::fill_working_table(&mut plan, &schema_ref, &mem::take(&mut working_rows));
+
+            #[for_await]
+            for tuple in build_read(plan, transaction) {
+                let values = tuple?.values;
+
+                if !is_distinct || seen.insert(values.clone()) {
+                    working_rows.push(values.clone());
+
+                    yield Tuple {
+                        id: None,
+                        schema_ref: schema_ref.clone(),
+                        values,
+                    };
+                }
+            }
+        }
+    }
+
+    fn fill_working_table(plan: &mut LogicalPlan, schema_ref: &SchemaRef, rows: &[Vec<ValueRef>]) {
+        if RecursiveCteOperator::is_working_table(&plan.operator, schema_ref) {
+            if let Operator::Values(op) = &mut plan.operator {
+                op.rows = rows.to_vec();
+            }
+        }
+        for child in plan.childrens.iter_mut() {
+            Self::fill_working_table(child, schema_ref, rows);
+        }
+    }
+}
@@ -33,6 +33,31 @@ pub(crate) fn radix_sort<T>(mut tuples: Vec<(T, Vec<u8>)>) -> Vec<T> {
     Vec::new()
 }
 
+/// Encodes the memcomparable key of the tuple, by which the tuples are sorted in the order of the fields.
+pub(crate) fn sort_key(sort_fields: &[SortField], tuple: &Tuple) -> Result<Vec<u8>, DatabaseError> {
+    let mut full_key = Vec::new();
+
+    for SortField {
+        expr,
+        nulls_first,
+        asc,
+    } in sort_fields
+    {
+        let mut key = Vec::new();
+
+        expr.eval(tuple)?.memcomparable_encode(&mut key)?;
+        key.push(if *nulls_first { u8::MIN } else { u8::MAX });
+
+        if !asc {
+            for byte in key.iter_mut() {
+                *byte ^= 0xFF;
+            }
+        }
+        full_key.extend(key);
+    }
+    Ok(full_key)
+}
+
 pub(crate) fn sort(
     sort_fields: &[SortField],
     tuples: Vec<Tuple>,
@@ -40,27 +65,9 @@ pub(crate) fn sort(
     let tuples_with_keys: Vec<(Tuple, Vec<u8>)> = tuples
         .into_iter()
         .map(|tuple| {
-            let mut full_key = Vec::new();
-
-            for SortField {
-                expr,
-                nulls_first,
-                asc,
-            } in sort_fields
-            {
-                let mut key = Vec::new();
-
-                expr.eval(&tuple)?.memcomparable_encode(&mut key)?;
-                key.push(if *nulls_first { u8::MIN } else { u8::MAX });
-
-                if !asc {
-                    for byte in key.iter_mut() {
-                        *byte ^= 0xFF;
-                    }
-                }
-                full_key.extend(key);
-            }
-            Ok::<(Tuple, Vec<u8>), DatabaseError>((tuple, full_key))
+            let key = sort_key(sort_fields, &tuple)?;
+
+            Ok::<(Tuple, Vec<u8>), DatabaseError>((tuple, key))
         })
         .try_collect()?;
 
@@ -0,0 +1,64 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::{BoxedExecutor, ReadExecutor};
+use crate::planner::operator::table_function::TableFunctionOperator;
+use crate::storage::Transaction;
+use crate::types::tuple::Tuple;
+use crate::types::value::{DataValue, ValueRef};
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use std::sync::Arc;
+
+pub struct TableFunction {
+    op: TableFunctionOperator,
+}
+
+impl From<TableFunctionOperator> for TableFunction {
+    fn from(op: TableFunctionOperator) -> Self {
+        TableFunction { op }
+    }
+}
+
+impl<T: Transaction> ReadExecutor<T> for TableFunction {
+    fn execute(self, _: &T) -> BoxedExecutor {
+        self._execute()
+    }
+}
+
+impl TableFunction {
+    #[try_stream(boxed, ok = Tuple, error = DatabaseError)]
+    pub async fn _execute(self) {
+        let TableFunctionOperator {
+            function,
+            schema_ref,
+            ..
+        } = self.op;
+        // Tips: the arguments do not reference any column
+        let empty_tuple = Tuple {
+            id: None,
+            schema_ref: Arc::new(vec![]),
+            values: vec![],
+        };
+        let args: Vec<ValueRef> = function
+            .args
+            .iter()
+            .zip_eq(function.summary().arg_types.iter())
+            .map(|(arg, ty)| {
+                let value = arg.eval(&empty_tuple)?;
+
+                if &value.logical_type() == ty {
+                    Ok(value)
+                } else {
+                    Ok::<_, DatabaseError>(Arc::new(DataValue::clone(&value).cast(ty)?))
+                }
+            })
+            .try_collect()?;
+
+        for values in function.inner.eval(&args)? {
+            yield Tuple {
+                id: None,
+                schema_ref: schema_ref.clone(),
+                values: values?,
+            };
+        }
+    }
+}
@@ -24,7 +24,9 @@ impl<T: Transaction> ReadExecutor<T> for Values {
 impl Values {
     #[try_stream(boxed, ok = Tuple, error = DatabaseError)]
     pub async fn _execute(self) {
-        let ValuesOperator { schema_ref, rows } = self.op;
+        let ValuesOperator {
+            schema_ref, rows, ..
+        } = self.op;
 
         for values in rows {
             yield Tuple {
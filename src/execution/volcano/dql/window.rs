@@ -0,0 +1,297 @@
+use crate::errors::DatabaseError;
+use crate::execution::volcano::dql::aggregate::create_accumulator;
+use crate::execution::volcano::dql::sort::{radix_sort, sort_key};
+use crate::execution::volcano::{build_read, BoxedExecutor, ReadExecutor};
+use crate::expression::window::{WindowFrame, WindowFrameBound, WindowFrameUnits, WindowKind};
+use crate::expression::ScalarExpression;
+use crate::planner::operator::window::WindowOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::tuple::Tuple;
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+use ahash::RandomState;
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub struct Window {
+    window_calls: Vec<ScalarExpression>,
+    input: LogicalPlan,
+}
+
+impl From<(WindowOperator, LogicalPlan)> for Window {
+    fn from((WindowOperator { window_calls }, input): (WindowOperator, LogicalPlan)) -> Self {
+        Window {
+            window_calls,
+            input,
+        }
+    }
+}
+
+impl<T: Transaction> ReadExecutor<T> for Window {
+    fn execute(self, transaction: &T) -> BoxedExecutor {
+        self._execute(transaction)
+    }
+}
+
+impl Window {
+    #[try_stream(boxed, ok = Tuple, error = DatabaseError)]
+    pub async fn _execute<T: Transaction>(self, transaction: &T) {
+        let Window {
+            window_calls,
+            input,
+        } = self;
+        let mut tuples: Vec<Tuple> = vec![];
+
+        #[for_await]
+        for tuple in build_read(input, transaction) {
+            tuples.push(tuple?);
+        }
+        let mut window_values = vec![Vec::with_capacity(window_calls.len()); tuples.len()];
+
+        for window_call in window_calls.iter() {
+            for (values, value) in window_values
+                .iter_mut()
+                .zip(Self::eval_window_call(window_call, &tuples)?)
+            {
+                values.push(value);
+            }
+        }
+        let schema_ref = tuples.first().map(|tuple| {
+            let mut columns = Vec::clone(&tuple.schema_ref);
+            columns.extend(window_calls.iter().map(|expr| expr.output_column()));
+            Arc::new(columns)
+        });
+
+        for (mut tuple, values) in tuples.into_iter().zip(window_values) {
+            tuple.values.extend(values);
+            if let Some(schema_ref) = &schema_ref {
+                tuple.schema_ref = schema_ref.clone();
+            }
+
+            yield tuple;
+        }
+    }
+
+    /// Computes the window function for each tuple, the results are in the order of the tuples.
+    fn eval_window_call(
+        window_call: &ScalarExpression,
+        tuples: &[Tuple],
+    ) -> Result<Vec<ValueRef>, DatabaseError> {
+        let ScalarExpression::WindowCall {
+            kind,
+            args,
+            partition_by,
+            order_by,
+            frame,
+            ty,
+        } = window_call
+        else {
+            unreachable!("the window operator only holds the window functions")
+        };
+        let mut partitions: HashMap<Vec<ValueRef>, Vec<usize>, RandomState> = HashMap::default();
+
+        for (i, tuple) in tuples.iter().enumerate() {
+            let key: Vec<ValueRef> = partition_by
+                .iter()
+                .map(|expr| expr.eval(tuple))
+                .try_collect()?;
+
+            partitions.entry(key).or_default().push(i);
+        }
+        let null = Arc::new(DataValue::none(ty));
+        let mut results = vec![null; tuples.len()];
+
+        for rows in partitions.into_values() {
+            let rows_with_keys: Vec<_> = rows
+                .into_iter()
+                .map(|i| {
+                    let key = sort_key(order_by, &tuples[i])?;
+
+                    Ok::<_, DatabaseError>(((i, key.clone()), key))
+                })
+                .try_collect()?;
+            // the radix sort is stable, so the peers keep the order of the input
+            let rows = radix_sort(rows_with_keys);
+            let partition = Partition::new(tuples, &rows);
+
+            for (pos, value) in partition
+                .eval(kind, args, frame, ty)?
+                .into_iter()
+                .enumerate()
+            {
+                results[rows[pos].0] = value;
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+/// The sorted rows of a partition, the rows with the same ORDER BY key are peers.
+struct Partition<'a> {
+    tuples: Vec<&'a Tuple>,
+    // the positions of the first peer and after the last peer of each row
+    peers: Vec<(usize, usize)>,
+}
+
+impl<'a> Partition<'a> {
+    fn new(tuples: &'a [Tuple], rows: &[(usize, Vec<u8>)]) -> Self {
+        let mut peers = Vec::with_capacity(rows.len());
+        let mut start = 0;
+
+        while start < rows.len() {
+            let end = (start..rows.len())
+                .find(|&pos| rows[pos].1 != rows[start].1)
+                .unwrap_or(rows.len());
+
+            peers.extend((start..end).map(|_| (start, end)));
+            start = end;
+        }
+
+        Partition {
+            tuples: rows.iter().map(|(i, _)| &tuples[*i]).collect_vec(),
+            peers,
+        }
+    }
+
+    fn eval(
+        &self,
+        kind: &WindowKind,
+        args: &[ScalarExpression],
+        frame: &WindowFrame,
+        ty: &LogicalType,
+    ) -> Result<Vec<ValueRef>, DatabaseError> {
+        let len = self.tuples.len();
+        let int64 = |v: usize| Arc::new(DataValue::Int64(Some(v as i64)));
+
+        Ok(match kind {
+            WindowKind::RowNumber => (0..len).map(|pos| int64(pos + 1)).collect_vec(),
+            WindowKind::Rank => self
+                .peers
+                .iter()
+                .map(|(start, _)| int64(start + 1))
+                .collect_vec(),
+            WindowKind::DenseRank => {
+                let mut rank = 0;
+
+                (0..len)
+                    .map(|pos| {
+                        if self.peers[pos].0 == pos {
+                            rank += 1;
+                        }
+                        int64(rank)
+                    })
+                    .collect_vec()
+            }
+            WindowKind::Lag | WindowKind::Lead => {
+                let mut values = Vec::with_capacity(len);
+
+                for (pos, tuple) in self.tuples.iter().enumerate() {
+                    let offset = match args.get(1) {
+                        Some(expr) => {
+                            let offset = expr.eval(tuple)?;
+
+                            match DataValue::clone(&offset).cast(&LogicalType::Bigint)? {
+                                DataValue::Int64(Some(offset)) => offset,
+                                _ => {
+                                    values.push(Arc::new(DataValue::none(ty)));
+                                    continue;
+                                }
+                            }
+                        }
+                        None => 1,
+                    };
+                    let target = if matches!(kind, WindowKind::Lag) {
+                        pos as i64 - offset
+                    } else {
+                        pos as i64 + offset
+                    };
+                    let value = if (0..len as i64).contains(&target) {
+                        args[0].eval(self.tuples[target as usize])?
+                    } else if let Some(default) = args.get(2) {
+                        default.eval(tuple)?
+                    } else {
+                        Arc::new(DataValue::none(ty))
+                    };
+                    values.push(value);
+                }
+                values
+            }
+            WindowKind::FirstValue | WindowKind::LastValue | WindowKind::Agg(_) => {
+                let arg_values: Vec<ValueRef> = self
+                    .tuples
+                    .iter()
+                    .map(|tuple| args[0].eval(tuple))
+                    .try_collect()?;
+                let mut values: Vec<ValueRef> = Vec::with_capacity(len);
+                let mut last_frame = None;
+
+                for pos in 0..len {
+                    let (start, end) = self.frame_at(frame, pos);
+                    // the rows of the same frame, such as the peers, share the result
+                    if last_frame == Some((start, end)) {
+                        if let Some(value) = values.last().cloned() {
+                            values.push(value);
+                            continue;
+                        }
+                    }
+                    last_frame = Some((start, end));
+
+                    let frame_values = if start < end {
+                        &arg_values[start..end]
+                    } else {
+                        &[]
+                    };
+                    let value = match kind {
+                        WindowKind::FirstValue => frame_values.first().cloned(),
+                        WindowKind::LastValue => frame_values.last().cloned(),
+                        WindowKind::Agg(agg_kind) => {
+                            let mut accumulator = create_accumulator(&ScalarExpression::AggCall {
+                                distinct: false,
+                                kind: agg_kind.clone(),
+                                args: args.to_vec(),
+                                ty: *ty,
+                            });
+                            for value in frame_values {
+                                accumulator.update_value(value)?;
+                            }
+                            Some(accumulator.evaluate()?)
+                        }
+                        _ => unreachable!(),
+                    };
+                    values.push(value.unwrap_or_else(|| Arc::new(DataValue::none(ty))));
+                }
+                values
+            }
+        })
+    }
+
+    /// The positions of the first row and after the last row of the frame.
+    fn frame_at(&self, frame: &WindowFrame, pos: usize) -> (usize, usize) {
+        let len = self.tuples.len();
+        let (peer_start, peer_end) = self.peers[pos];
+        let is_rows = frame.units == WindowFrameUnits::Rows;
+
+        let start = match frame.start {
+            WindowFrameBound::UnboundedPreceding => 0,
+            WindowFrameBound::Preceding(offset) => pos.saturating_sub(offset as usize),
+            WindowFrameBound::CurrentRow if is_rows => pos,
+            WindowFrameBound::CurrentRow => peer_start,
+            WindowFrameBound::Following(offset) => pos.saturating_add(offset as usize).min(len),
+            WindowFrameBound::UnboundedFollowing => len,
+        };
+        let end = match frame.end {
+            WindowFrameBound::UnboundedPreceding => 0,
+            WindowFrameBound::Preceding(offset) => (pos + 1).saturating_sub(offset as usize),
+            WindowFrameBound::CurrentRow if is_rows => pos + 1,
+            WindowFrameBound::CurrentRow => peer_end,
+            WindowFrameBound::Following(offset) => pos.saturating_add(offset as usize + 1).min(len),
+            WindowFrameBound::UnboundedFollowing => len,
+        };
+
+        (start, end)
+    }
+}
@@ -3,9 +3,16 @@ pub(crate) mod dml;
 pub(crate) mod dql;
 
 use crate::errors::DatabaseError;
+use crate::execution::volcano::ddl::alter_user::AlterUser;
+use crate::execution::volcano::ddl::create_index::CreateIndex;
 use crate::execution::volcano::ddl::create_table::CreateTable;
+use crate::execution::volcano::ddl::create_user::CreateUser;
 use crate::execution::volcano::ddl::drop_column::DropColumn;
+use crate::execution::volcano::ddl::drop_index::DropIndex;
 use crate::execution::volcano::ddl::drop_table::DropTable;
+use crate::execution::volcano::ddl::drop_user::DropUser;
+use crate::execution::volcano::ddl::grant::Grant;
+use crate::execution::volcano::ddl::revoke::Revoke;
 use crate::execution::volcano::ddl::truncate::Truncate;
 use crate::execution::volcano::dml::analyze::Analyze;
 use crate::execution::volcano::dml::copy_from_file::CopyFromFile;
@@ -16,17 +23,24 @@ use crate::execution::volcano::dql::aggregate::hash_agg::HashAggExecutor;
 use crate::execution::volcano::dql::aggregate::simple_agg::SimpleAggExecutor;
 use crate::execution::volcano::dql::describe::Describe;
 use crate::execution::volcano::dql::dummy::Dummy;
+use crate::execution::volcano::dql::except::Except;
 use crate::execution::volcano::dql::explain::Explain;
 use crate::execution::volcano::dql::filter::Filter;
 use crate::execution::volcano::dql::index_scan::IndexScan;
+use crate::execution::volcano::dql::intersect::Intersect;
 use crate::execution::volcano::dql::join::hash_join::HashJoin;
+use crate::execution::volcano::dql::join::nested_loop_join::NestedLoopJoin;
+use crate::execution::volcano::dql::join::sort_merge_join::SortMergeJoin;
 use crate::execution::volcano::dql::limit::Limit;
 use crate::execution::volcano::dql::projection::Projection;
+use crate::execution::volcano::dql::recursive_cte::RecursiveCte;
 use crate::execution::volcano::dql::seq_scan::SeqScan;
 use crate::execution::volcano::dql::show_table::ShowTables;
 use crate::execution::volcano::dql::sort::Sort;
+use crate::execution::volcano::dql::table_function::TableFunction;
 use crate::execution::volcano::dql::union::Union;
 use crate::execution::volcano::dql::values::Values;
+use crate::execution::volcano::dql::window::Window;
 use crate::planner::operator::{Operator, PhysicalOption};
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
@@ -74,7 +88,15 @@ pub fn build_read<T: Transaction>(plan: LogicalPlan, transaction: &T) -> BoxedEx
             let left_input = childrens.remove(0);
             let right_input = childrens.remove(0);
 
-            HashJoin::from((op, left_input, right_input)).execute(transaction)
+            match plan.physical_option {
+                Some(PhysicalOption::NestedLoopJoin) => {
+                    NestedLoopJoin::from((op, left_input, right_input)).execute(transaction)
+                }
+                Some(PhysicalOption::SortMergeJoin) => {
+                    SortMergeJoin::from((op, left_input, right_input)).execute(transaction)
+                }
+                _ => HashJoin::from((op, left_input, right_input)).execute(transaction),
+            }
         }
         Operator::Project(op) => {
             let input = childrens.remove(0);
@@ -103,6 +125,12 @@ pub fn build_read<T: Transaction>(plan: LogicalPlan, transaction: &T) -> BoxedEx
             Limit::from((op, input)).execute(transaction)
         }
         Operator::Values(op) => Values::from(op).execute(transaction),
+        Operator::TableFunction(op) => TableFunction::from(op).execute(transaction),
+        Operator::Window(op) => {
+            let input = childrens.remove(0);
+
+            Window::from((op, input)).execute(transaction)
+        }
         Operator::Show => ShowTables.execute(transaction),
         Operator::Explain => {
             let input = childrens.remove(0);
@@ -116,6 +144,24 @@ pub fn build_read<T: Transaction>(plan: LogicalPlan, transaction: &T) -> BoxedEx
 
             Union::from((left_input, right_input)).execute(transaction)
         }
+        Operator::Intersect(op) => {
+            let left_input = childrens.remove(0);
+            let right_input = childrens.remove(0);
+
+            Intersect::from((op, left_input, right_input)).execute(transaction)
+        }
+        Operator::Except(op) => {
+            let left_input = childrens.remove(0);
+            let right_input = childrens.remove(0);
+
+            Except::from((op, left_input, right_input)).execute(transaction)
+        }
+        Operator::RecursiveCte(op) => {
+            let anchor_input = childrens.remove(0);
+            let recursive_input = childrens.remove(0);
+
+            RecursiveCte::from((op, anchor_input, recursive_input)).execute(transaction)
+        }
         _ => unreachable!(),
     }
 }
@@ -125,6 +171,7 @@ pub fn build_write<T: Transaction>(plan: LogicalPlan, transaction: &mut T) -> Bo
         operator,
         mut childrens,
         physical_option,
+        estimated_rows,
         _output_schema_ref: _out_schema_ref,
     } = plan;
 
@@ -136,9 +183,8 @@ pub fn build_write<T: Transaction>(plan: LogicalPlan, transaction: &mut T) -> Bo
         }
         Operator::Update(op) => {
             let input = childrens.remove(0);
-            let values = childrens.remove(0);
 
-            Update::from((op, input, values)).execute_mut(transaction)
+            Update::from((op, input)).execute_mut(transaction)
         }
         Operator::Delete(op) => {
             let input = childrens.remove(0);
@@ -155,6 +201,17 @@ pub fn build_write<T: Transaction>(plan: LogicalPlan, transaction: &mut T) -> Bo
         }
         Operator::CreateTable(op) => CreateTable::from(op).execute_mut(transaction),
         Operator::DropTable(op) => DropTable::from(op).execute_mut(transaction),
+        Operator::CreateIndex(op) => {
+            let input = childrens.remove(0);
+
+            CreateIndex::from((op, input)).execute_mut(transaction)
+        }
+        Operator::DropIndex(op) => DropIndex::from(op).execute_mut(transaction),
+        Operator::CreateUser(op) => CreateUser::from(op).execute_mut(transaction),
+        Operator::AlterUser(op) => AlterUser::from(op).execute_mut(transaction),
+        Operator::DropUser(op) => DropUser::from(op).execute_mut(transaction),
+        Operator::Grant(op) => Grant::from(op).execute_mut(transaction),
+        Operator::Revoke(op) => Revoke::from(op).execute_mut(transaction),
         Operator::Truncate(op) => Truncate::from(op).execute_mut(transaction),
         Operator::CopyFromFile(op) => CopyFromFile::from(op).execute_mut(transaction),
         #[warn(unused_assignments)]
@@ -171,6 +228,7 @@ pub fn build_write<T: Transaction>(plan: LogicalPlan, transaction: &mut T) -> Bo
                 operator,
                 childrens,
                 physical_option,
+                estimated_rows,
                 _output_schema_ref: _out_schema_ref,
             },
             transaction,
@@ -1,12 +1,35 @@
-use serde::{Deserialize, Serialize};
+use crate::errors::DatabaseError;
+use crate::expression::function::FunctionSummary;
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AggKind {
     Avg,
     Max,
     Min,
     Sum,
     Count,
+    StddevSamp,
+    StddevPop,
+    VarSamp,
+    VarPop,
+    BoolAnd,
+    BoolOr,
+    /// `STRING_AGG` and `GROUP_CONCAT`
+    StringAgg,
+    ArrayAgg,
+    /// Estimated by HyperLogLog
+    ApproxCountDistinct,
+    Median,
+    /// `percentile_cont(expr, fraction)`, the standard `WITHIN GROUP (ORDER BY expr)` is not supported
+    PercentileCont,
+    /// The aggregate registered by `DataBaseBuilder::register_aggregate_function`
+    UserDefined(AggregateFunction),
 }
 
 impl AggKind {
@@ -17,6 +40,73 @@ impl AggKind {
             AggKind::Min => false,
             AggKind::Sum => true,
             AggKind::Count => true,
+            AggKind::StddevSamp => true,
+            AggKind::StddevPop => true,
+            AggKind::VarSamp => true,
+            AggKind::VarPop => true,
+            AggKind::BoolAnd => false,
+            AggKind::BoolOr => false,
+            AggKind::StringAgg => true,
+            AggKind::ArrayAgg => true,
+            AggKind::ApproxCountDistinct => false,
+            AggKind::Median => true,
+            AggKind::PercentileCont => true,
+            AggKind::UserDefined(_) => true,
         }
     }
 }
+
+impl fmt::Display for AggKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            AggKind::UserDefined(function) => write!(f, "{}", function.summary().name),
+            kind => write!(f, "{:?}", kind),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AggregateFunction {
+    pub(crate) inner: Arc<dyn AggregateFunctionImpl>,
+}
+
+impl PartialEq for AggregateFunction {
+    fn eq(&self, other: &Self) -> bool {
+        self.summary() == other.summary()
+    }
+}
+
+impl Eq for AggregateFunction {}
+
+impl Hash for AggregateFunction {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.summary().hash(state);
+    }
+}
+
+impl AggregateFunction {
+    pub fn summary(&self) -> &FunctionSummary {
+        self.inner.summary()
+    }
+}
+
+/// The aggregate function whose state of each group is kept as a `DataValue`,
+/// e.g. the `DataValue::Tuple` of the values seen for a percentile.
+pub trait AggregateFunctionImpl: Debug + Send + Sync {
+    /// The state of the group which has not aggregated any row.
+    fn init_state(&self) -> DataValue;
+
+    /// Aggregates the arguments of a row into the state,
+    /// whose values have been cast to the argument types of the summary.
+    fn update(&self, state: &mut DataValue, args: &[ValueRef]) -> Result<(), DatabaseError>;
+
+    /// Combines the state aggregated from the other rows of the same group into the state.
+    fn merge(&self, state: &mut DataValue, other: &DataValue) -> Result<(), DatabaseError>;
+
+    /// The result of the group.
+    fn evaluate(&self, state: &DataValue) -> Result<DataValue, DatabaseError>;
+
+    fn return_type(&self) -> &LogicalType;
+
+    fn summary(&self) -> &FunctionSummary;
+}
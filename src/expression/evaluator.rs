@@ -1,6 +1,6 @@
 use crate::errors::DatabaseError;
 use crate::expression::function::ScalarFunction;
-use crate::expression::{AliasType, ScalarExpression};
+use crate::expression::{AliasType, BinaryOperator, ScalarExpression};
 use crate::types::tuple::Tuple;
 use crate::types::value::{DataValue, ValueRef};
 use crate::types::LogicalType;
@@ -30,6 +30,9 @@ impl ScalarExpression {
     pub fn eval(&self, tuple: &Tuple) -> Result<ValueRef, DatabaseError> {
         match self {
             ScalarExpression::Constant(val) => Ok(val.clone()),
+            ScalarExpression::Placeholder { value, .. } => {
+                Ok(value.clone().unwrap_or_else(|| NULL_VALUE.clone()))
+            }
             ScalarExpression::ColumnRef(col) => {
                 let value = tuple
                     .schema_ref
@@ -114,7 +117,7 @@ impl ScalarExpression {
 
                 Ok(Arc::new(DataValue::unary_op(&value, op)?))
             }
-            ScalarExpression::AggCall { .. } => {
+            ScalarExpression::AggCall { .. } | ScalarExpression::WindowCall { .. } => {
                 unreachable!("must use `NormalizationRuleImpl::ExpressionRemapper`")
             }
             ScalarExpression::Between {
@@ -181,6 +184,44 @@ impl ScalarExpression {
             ScalarExpression::Function(ScalarFunction { inner, args, .. }) => Ok(Arc::new(
                 inner.eval(args, tuple)?.cast(inner.return_type())?,
             )),
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ty,
+            } => {
+                let operand_value = operand_expr
+                    .as_ref()
+                    .map(|expr| expr.eval(tuple))
+                    .transpose()?;
+                let mut result = None;
+
+                for (when_expr, then_expr) in expr_pairs {
+                    let when_value = when_expr.eval(tuple)?;
+                    let is_matched = match &operand_value {
+                        Some(operand_value) => matches!(
+                            DataValue::binary_op(operand_value, &when_value, &BinaryOperator::Eq)?,
+                            DataValue::Boolean(Some(true))
+                        ),
+                        None => matches!(when_value.as_ref(), DataValue::Boolean(Some(true))),
+                    };
+                    if is_matched {
+                        result = Some(then_expr.eval(tuple)?);
+                        break;
+                    }
+                }
+                let value = match (result, else_expr) {
+                    (Some(value), _) => value,
+                    (None, Some(else_expr)) => else_expr.eval(tuple)?,
+                    (None, None) => NULL_VALUE.clone(),
+                };
+
+                if value.logical_type() != *ty {
+                    Ok(Arc::new(DataValue::clone(&value).cast(ty)?))
+                } else {
+                    Ok(value)
+                }
+            }
             ScalarExpression::Empty => unreachable!(),
         }
     }
@@ -0,0 +1,86 @@
+use crate::errors::DatabaseError;
+use crate::expression::function::{BuiltinEval, BuiltinFunction, FunctionSummary};
+use crate::expression::BinaryOperator;
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+
+pub(super) fn resolve(summary: &FunctionSummary) -> Result<Option<BuiltinFunction>, DatabaseError> {
+    let (return_type, eval): (LogicalType, BuiltinEval) =
+        match (summary.name.as_str(), summary.arg_types.as_slice()) {
+            ("coalesce", tys) if !tys.is_empty() => (LogicalType::common_type(tys)?, coalesce),
+            ("ifnull" | "nvl", tys @ [_, _]) => (LogicalType::common_type(tys)?, coalesce),
+            ("nullif", tys @ [ty, _]) => {
+                // checks that the arguments are comparable
+                let _ = LogicalType::common_type(tys)?;
+
+                (*ty, nullif)
+            }
+            ("greatest", tys) if !tys.is_empty() => (LogicalType::common_type(tys)?, greatest),
+            ("least", tys) if !tys.is_empty() => (LogicalType::common_type(tys)?, least),
+            ("if", [LogicalType::Boolean | LogicalType::SqlNull, tys @ ..]) if tys.len() == 2 => {
+                (LogicalType::common_type(tys)?, if_else)
+            }
+            _ => return Ok(None),
+        };
+
+    Ok(Some(BuiltinFunction::new(summary, return_type, None, eval)))
+}
+
+/// The first argument which is not NULL
+fn coalesce(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(args
+        .iter()
+        .find(|value| !value.is_null())
+        .map(|value| value.as_ref().clone())
+        .unwrap_or(DataValue::Null))
+}
+
+/// NULL if the arguments are equal, otherwise the first argument
+fn nullif(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    if let DataValue::Boolean(Some(true)) =
+        DataValue::binary_op(&args[0], &args[1], &BinaryOperator::Eq)?
+    {
+        return Ok(DataValue::Null);
+    }
+    Ok(DataValue::clone(&args[0]))
+}
+
+fn greatest(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    extremum(args, BinaryOperator::Gt)
+}
+
+fn least(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    extremum(args, BinaryOperator::Lt)
+}
+
+/// The argument which is `op` all the others, ignoring the NULLs
+fn extremum(args: &[ValueRef], op: BinaryOperator) -> Result<DataValue, DatabaseError> {
+    let mut result: Option<&ValueRef> = None;
+
+    for value in args.iter().filter(|value| !value.is_null()) {
+        let is_replaced = match result {
+            Some(result) => matches!(
+                DataValue::binary_op(value, result, &op)?,
+                DataValue::Boolean(Some(true))
+            ),
+            None => true,
+        };
+        if is_replaced {
+            result = Some(value);
+        }
+    }
+    Ok(result
+        .map(|value| value.as_ref().clone())
+        .unwrap_or(DataValue::Null))
+}
+
+/// The second argument if the condition is true, otherwise the third one
+fn if_else(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let value = if let DataValue::Boolean(Some(true)) = args[0].as_ref() {
+        &args[1]
+    } else {
+        &args[2]
+    };
+
+    Ok(DataValue::clone(value))
+}
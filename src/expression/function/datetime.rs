@@ -0,0 +1,195 @@
+use crate::errors::DatabaseError;
+use crate::expression::function::{
+    datetime, is_string, is_temporal, utf8, BuiltinEval, BuiltinFunction, FunctionSummary,
+};
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, Timelike};
+
+pub(super) fn resolve(summary: &FunctionSummary) -> Result<Option<BuiltinFunction>, DatabaseError> {
+    let increasing = Some(vec![Some(true)]);
+    let (return_type, monotonicity, eval): (LogicalType, _, BuiltinEval) =
+        match (summary.name.as_str(), summary.arg_types.as_slice()) {
+            ("now" | "current_timestamp" | "localtimestamp", []) => {
+                (LogicalType::DateTime, None, now)
+            }
+            ("current_date", []) => (LogicalType::Date, None, current_date),
+            ("date_trunc", [field, ty]) if is_string(field) && is_temporal(ty) => (
+                LogicalType::DateTime,
+                Some(vec![None, Some(true)]),
+                date_trunc,
+            ),
+            ("date_part", [field, ty]) if is_string(field) && is_temporal(ty) => {
+                (LogicalType::Bigint, None, date_part)
+            }
+            ("year", [ty]) if is_temporal(ty) => (LogicalType::Integer, increasing, year),
+            ("month", [ty]) if is_temporal(ty) => (LogicalType::Integer, None, month),
+            ("day" | "dayofmonth", [ty]) if is_temporal(ty) => (LogicalType::Integer, None, day),
+            ("hour", [ty]) if is_temporal(ty) => (LogicalType::Integer, None, hour),
+            ("minute", [ty]) if is_temporal(ty) => (LogicalType::Integer, None, minute),
+            ("second", [ty]) if is_temporal(ty) => (LogicalType::Integer, None, second),
+            ("date_format", [ty, format]) if is_temporal(ty) && is_string(format) => {
+                (LogicalType::Varchar(None), None, date_format)
+            }
+            ("to_date", [ty, format]) if is_string(ty) && is_string(format) => {
+                (LogicalType::Date, None, to_date)
+            }
+            ("to_timestamp", [ty, format]) if is_string(ty) && is_string(format) => {
+                (LogicalType::DateTime, None, to_timestamp)
+            }
+            _ => return Ok(None),
+        };
+
+    Ok(Some(BuiltinFunction::new(
+        summary,
+        return_type,
+        monotonicity,
+        eval,
+    )))
+}
+
+/// The current local date and time, which is read on each evaluation
+fn now(_: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Date64(Some(
+        Local::now().naive_local().and_utc().timestamp(),
+    )))
+}
+
+fn current_date(_: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Date32(Some(
+        Local::now().date_naive().num_days_from_ce(),
+    )))
+}
+
+/// Truncates the date time to the precision of the field
+fn date_trunc(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let (Some(field), Some(datetime)) = (utf8(&args[0])?, datetime(&args[1])?) else {
+        return Ok(DataValue::Date64(None));
+    };
+    let date = datetime.date();
+    let first_day = |month: u32| NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap();
+
+    let (date, hour, minute) = match field.to_lowercase().as_str() {
+        "year" => (first_day(1), 0, 0),
+        "quarter" => (first_day(date.month0() / 3 * 3 + 1), 0, 0),
+        "month" => (first_day(date.month()), 0, 0),
+        "week" => (
+            date - Duration::days(date.weekday().num_days_from_monday() as i64),
+            0,
+            0,
+        ),
+        "day" => (date, 0, 0),
+        "hour" => (date, datetime.hour(), 0),
+        "minute" => (date, datetime.hour(), datetime.minute()),
+        // Tips: the date time is stored in seconds
+        "second" => return Ok(DataValue::Date64(Some(datetime.and_utc().timestamp()))),
+        _ => return Err(DatabaseError::InvalidArgument("date_trunc", field)),
+    };
+    let truncated = date.and_hms_opt(hour, minute, 0).unwrap();
+
+    Ok(DataValue::Date64(Some(truncated.and_utc().timestamp())))
+}
+
+/// The subfield of the date time, such as `year` or `dow`(the day of the week with Sunday as 0)
+fn date_part(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let (Some(field), Some(datetime)) = (utf8(&args[0])?, datetime(&args[1])?) else {
+        return Ok(DataValue::Int64(None));
+    };
+
+    let part = match field.to_lowercase().as_str() {
+        "year" => datetime.year() as i64,
+        "quarter" => (datetime.month0() / 3 + 1) as i64,
+        "month" => datetime.month() as i64,
+        "week" => datetime.iso_week().week() as i64,
+        "day" => datetime.day() as i64,
+        "dow" => datetime.weekday().num_days_from_sunday() as i64,
+        "doy" => datetime.ordinal() as i64,
+        "hour" => datetime.hour() as i64,
+        "minute" => datetime.minute() as i64,
+        "second" => datetime.second() as i64,
+        "epoch" => datetime.and_utc().timestamp(),
+        _ => return Err(DatabaseError::InvalidArgument("date_part", field)),
+    };
+
+    Ok(DataValue::Int64(Some(part)))
+}
+
+fn year(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Int32(datetime(&args[0])?.map(|v| v.year())))
+}
+
+fn month(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Int32(
+        datetime(&args[0])?.map(|v| v.month() as i32),
+    ))
+}
+
+fn day(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Int32(
+        datetime(&args[0])?.map(|v| v.day() as i32),
+    ))
+}
+
+fn hour(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Int32(
+        datetime(&args[0])?.map(|v| v.hour() as i32),
+    ))
+}
+
+fn minute(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Int32(
+        datetime(&args[0])?.map(|v| v.minute() as i32),
+    ))
+}
+
+fn second(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Int32(
+        datetime(&args[0])?.map(|v| v.second() as i32),
+    ))
+}
+
+/// Checks the `strftime` format, as the formatting of the invalid one panics
+fn check_format(function_name: &'static str, format: &str) -> Result<(), DatabaseError> {
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return Err(DatabaseError::InvalidArgument(
+            function_name,
+            format.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Formats the date time with the `strftime` format, e.g. `%Y-%m-%d %H:%M:%S`
+fn date_format(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let (Some(datetime), Some(format)) = (datetime(&args[0])?, utf8(&args[1])?) else {
+        return Ok(DataValue::Utf8(None));
+    };
+    check_format("date_format", &format)?;
+
+    Ok(DataValue::Utf8(Some(datetime.format(&format).to_string())))
+}
+
+fn to_date(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let (Some(s), Some(format)) = (utf8(&args[0])?, utf8(&args[1])?) else {
+        return Ok(DataValue::Date32(None));
+    };
+    check_format("to_date", &format)?;
+
+    Ok(DataValue::Date32(Some(
+        NaiveDate::parse_from_str(&s, &format)?.num_days_from_ce(),
+    )))
+}
+
+fn to_timestamp(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let (Some(s), Some(format)) = (utf8(&args[0])?, utf8(&args[1])?) else {
+        return Ok(DataValue::Date64(None));
+    };
+    check_format("to_timestamp", &format)?;
+
+    Ok(DataValue::Date64(Some(
+        NaiveDateTime::parse_from_str(&s, &format)?
+            .and_utc()
+            .timestamp(),
+    )))
+}
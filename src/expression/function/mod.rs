@@ -0,0 +1,235 @@
+use crate::errors::DatabaseError;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+use chrono::NaiveDateTime;
+use itertools::Itertools;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+mod conditional;
+mod datetime;
+mod numeric;
+mod string;
+pub mod table;
+
+/// for `datafusion`
+/// - `None` unknown monotonicity or non-monotonicity
+/// - `Some(true)` monotonically increasing
+/// - `Some(false)` monotonically decreasing
+pub type FuncMonotonicity = Vec<Option<bool>>;
+
+#[derive(Debug, Clone)]
+pub struct ScalarFunction {
+    pub(crate) args: Vec<ScalarExpression>,
+    pub(crate) inner: Arc<dyn ScalarFunctionImpl>,
+}
+
+impl PartialEq for ScalarFunction {
+    fn eq(&self, other: &Self) -> bool {
+        self.summary() == other.summary()
+    }
+}
+
+impl Eq for ScalarFunction {}
+
+impl Hash for ScalarFunction {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.summary().hash(state);
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct FunctionSummary {
+    pub(crate) name: String,
+    pub(crate) arg_types: Vec<LogicalType>,
+}
+
+pub trait ScalarFunctionImpl: Debug + Send + Sync {
+    fn eval(&self, args: &[ScalarExpression], tuple: &Tuple) -> Result<DataValue, DatabaseError>;
+
+    // TODO: Exploiting monotonicity when optimizing `ScalarFunctionImpl::monotonicity()`
+    fn monotonicity(&self) -> Option<FuncMonotonicity>;
+
+    fn return_type(&self) -> &LogicalType;
+
+    fn summary(&self) -> &FunctionSummary;
+}
+
+impl ScalarFunction {
+    pub fn summary(&self) -> &FunctionSummary {
+        self.inner.summary()
+    }
+}
+
+type BuiltinEval = fn(&[ValueRef]) -> Result<DataValue, DatabaseError>;
+
+type BuiltinResolver = fn(&FunctionSummary) -> Result<Option<BuiltinFunction>, DatabaseError>;
+
+/// The functions registered by default, whose return types are resolved by the types of the arguments
+#[derive(Debug)]
+pub(crate) struct BuiltinFunction {
+    summary: FunctionSummary,
+    return_type: LogicalType,
+    monotonicity: Option<FuncMonotonicity>,
+    eval: BuiltinEval,
+}
+
+impl BuiltinFunction {
+    fn new(
+        summary: &FunctionSummary,
+        return_type: LogicalType,
+        monotonicity: Option<FuncMonotonicity>,
+        eval: BuiltinEval,
+    ) -> Self {
+        BuiltinFunction {
+            summary: summary.clone(),
+            return_type,
+            monotonicity,
+            eval,
+        }
+    }
+
+    /// Resolves the built-in function with the name and the argument types of the summary,
+    /// `None` if there is no such function or the arguments do not fit it
+    pub(crate) fn resolve(
+        summary: &FunctionSummary,
+    ) -> Result<Option<Arc<dyn ScalarFunctionImpl>>, DatabaseError> {
+        let resolvers: [BuiltinResolver; 4] = [
+            string::resolve,
+            numeric::resolve,
+            datetime::resolve,
+            conditional::resolve,
+        ];
+
+        for resolver in resolvers {
+            if let Some(function) = resolver(summary)? {
+                return Ok(Some(Arc::new(function)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl ScalarFunctionImpl for BuiltinFunction {
+    fn eval(&self, args: &[ScalarExpression], tuple: &Tuple) -> Result<DataValue, DatabaseError> {
+        let values: Vec<ValueRef> = args.iter().map(|arg| arg.eval(tuple)).try_collect()?;
+
+        (self.eval)(&values)
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        self.monotonicity.clone()
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &self.return_type
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
+
+fn is_string(ty: &LogicalType) -> bool {
+    matches!(ty, LogicalType::Varchar(_) | LogicalType::SqlNull)
+}
+
+fn is_integer(ty: &LogicalType) -> bool {
+    ty.is_signed_numeric() || ty.is_unsigned_numeric() || matches!(ty, LogicalType::SqlNull)
+}
+
+fn is_number(ty: &LogicalType) -> bool {
+    ty.is_numeric() || matches!(ty, LogicalType::Decimal(_, _) | LogicalType::SqlNull)
+}
+
+fn is_temporal(ty: &LogicalType) -> bool {
+    matches!(
+        ty,
+        LogicalType::Date | LogicalType::DateTime | LogicalType::Varchar(_) | LogicalType::SqlNull
+    )
+}
+
+fn utf8(value: &ValueRef) -> Result<Option<String>, DatabaseError> {
+    Ok(DataValue::clone(value)
+        .cast(&LogicalType::Varchar(None))?
+        .utf8())
+}
+
+fn int(value: &ValueRef) -> Result<Option<i64>, DatabaseError> {
+    Ok(DataValue::clone(value).cast(&LogicalType::Bigint)?.i64())
+}
+
+fn double(value: &ValueRef) -> Result<Option<f64>, DatabaseError> {
+    Ok(DataValue::clone(value).cast(&LogicalType::Double)?.double())
+}
+
+fn datetime(value: &ValueRef) -> Result<Option<NaiveDateTime>, DatabaseError> {
+    Ok(DataValue::clone(value)
+        .cast(&LogicalType::DateTime)?
+        .datetime())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::DatabaseError;
+    use crate::expression::function::{BuiltinFunction, FuncMonotonicity, FunctionSummary};
+    use crate::types::LogicalType;
+
+    fn resolve(
+        name: &str,
+        arg_types: Vec<LogicalType>,
+    ) -> Result<Option<(LogicalType, Option<FuncMonotonicity>)>, DatabaseError> {
+        let summary = FunctionSummary {
+            name: name.to_string(),
+            arg_types,
+        };
+
+        Ok(BuiltinFunction::resolve(&summary)?
+            .map(|function| (*function.return_type(), function.monotonicity())))
+    }
+
+    #[test]
+    fn test_resolve_builtin() -> Result<(), DatabaseError> {
+        assert_eq!(
+            resolve("upper", vec![LogicalType::Varchar(Some(10))])?,
+            Some((LogicalType::Varchar(None), None))
+        );
+        assert_eq!(resolve("upper", vec![LogicalType::Integer])?, None);
+        assert_eq!(
+            resolve("round", vec![LogicalType::Decimal(Some(10), Some(2))])?,
+            Some((LogicalType::Decimal(None, None), Some(vec![Some(true)])))
+        );
+        assert_eq!(
+            resolve("abs", vec![LogicalType::Smallint])?,
+            Some((LogicalType::Smallint, None))
+        );
+        assert_eq!(
+            resolve(
+                "date_trunc",
+                vec![LogicalType::Varchar(None), LogicalType::Date]
+            )?,
+            Some((LogicalType::DateTime, Some(vec![None, Some(true)])))
+        );
+        assert_eq!(
+            resolve(
+                "coalesce",
+                vec![
+                    LogicalType::SqlNull,
+                    LogicalType::Integer,
+                    LogicalType::Bigint
+                ]
+            )?,
+            Some((LogicalType::Bigint, None))
+        );
+        assert!(resolve(
+            "greatest",
+            vec![LogicalType::Integer, LogicalType::Varchar(None)]
+        )
+        .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,311 @@
+use crate::errors::DatabaseError;
+use crate::expression::function::{
+    double, int, is_integer, is_number, BuiltinEval, BuiltinFunction, FunctionSummary,
+};
+use crate::expression::BinaryOperator;
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+use rand::Rng;
+use rust_decimal::prelude::Signed;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::f64::consts;
+
+macro_rules! double_function {
+    ($name:ident, $f:expr) => {
+        fn $name(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+            Ok(DataValue::Float64(double(&args[0])?.map($f)))
+        }
+    };
+}
+
+macro_rules! checked_abs {
+    ($value:expr) => {
+        $value
+            .map(|v| v.checked_abs().ok_or(DatabaseError::Overflow))
+            .transpose()?
+    };
+}
+
+macro_rules! round_integer {
+    ($variant:ident, $value:expr, $ty:ty, $factor:expr, $mode:expr) => {
+        DataValue::$variant(
+            $value
+                .map(|v| <$ty>::try_from($mode.round_i128(v as i128, $factor)))
+                .transpose()?,
+        )
+    };
+}
+
+pub(super) fn resolve(summary: &FunctionSummary) -> Result<Option<BuiltinFunction>, DatabaseError> {
+    let increasing = Some(vec![Some(true)]);
+    let (return_type, monotonicity, eval): (LogicalType, _, BuiltinEval) =
+        match (summary.name.as_str(), summary.arg_types.as_slice()) {
+            ("abs", [ty]) if is_number(ty) => (same_type(ty), None, abs),
+            ("sign", [ty]) if is_number(ty) => (same_type(ty), increasing, sign),
+            ("ceil" | "ceiling", [ty]) if is_number(ty) => (rounded_type(ty), increasing, ceil),
+            ("floor", [ty]) if is_number(ty) => (rounded_type(ty), increasing, floor),
+            ("round", [ty]) if is_number(ty) => (rounded_type(ty), increasing, round),
+            ("round", [ty, scale]) if is_number(ty) && is_integer(scale) => {
+                (rounded_type(ty), Some(vec![Some(true), None]), round)
+            }
+            ("trunc" | "truncate", [ty]) if is_number(ty) => (rounded_type(ty), increasing, trunc),
+            ("trunc" | "truncate", [ty, scale]) if is_number(ty) && is_integer(scale) => {
+                (rounded_type(ty), Some(vec![Some(true), None]), trunc)
+            }
+            ("mod", [left, right]) if is_number(left) && is_number(right) => {
+                (LogicalType::max_logical_type(left, right)?, None, modulo)
+            }
+            ("sqrt", [ty]) if is_number(ty) => (LogicalType::Double, increasing, sqrt),
+            ("cbrt", [ty]) if is_number(ty) => (LogicalType::Double, increasing, cbrt),
+            ("exp", [ty]) if is_number(ty) => (LogicalType::Double, increasing, exp),
+            ("ln", [ty]) if is_number(ty) => (LogicalType::Double, increasing, ln),
+            ("log10", [ty]) | ("log", [ty]) if is_number(ty) => {
+                (LogicalType::Double, increasing, log10)
+            }
+            ("log2", [ty]) if is_number(ty) => (LogicalType::Double, increasing, log2),
+            ("log", [base, ty]) if is_number(base) && is_number(ty) => {
+                (LogicalType::Double, None, log)
+            }
+            ("power" | "pow", [base, exponent]) if is_number(base) && is_number(exponent) => {
+                (LogicalType::Double, None, power)
+            }
+            ("sin", [ty]) if is_number(ty) => (LogicalType::Double, None, sin),
+            ("cos", [ty]) if is_number(ty) => (LogicalType::Double, None, cos),
+            ("tan", [ty]) if is_number(ty) => (LogicalType::Double, None, tan),
+            ("asin", [ty]) if is_number(ty) => (LogicalType::Double, increasing, asin),
+            ("acos", [ty]) if is_number(ty) => (LogicalType::Double, Some(vec![Some(false)]), acos),
+            ("atan", [ty]) if is_number(ty) => (LogicalType::Double, increasing, atan),
+            ("atan2", [y, x]) if is_number(y) && is_number(x) => (LogicalType::Double, None, atan2),
+            ("degrees", [ty]) if is_number(ty) => (LogicalType::Double, increasing, degrees),
+            ("radians", [ty]) if is_number(ty) => (LogicalType::Double, increasing, radians),
+            ("pi", []) => (LogicalType::Double, None, pi),
+            ("random", []) => (LogicalType::Double, None, random),
+            _ => return Ok(None),
+        };
+
+    Ok(Some(BuiltinFunction::new(
+        summary,
+        return_type,
+        monotonicity,
+        eval,
+    )))
+}
+
+/// The type of the argument, which is `Double` for `NULL`
+fn same_type(ty: &LogicalType) -> LogicalType {
+    match ty {
+        LogicalType::SqlNull => LogicalType::Double,
+        ty => *ty,
+    }
+}
+
+/// Same as [`same_type`], except that the precision of the decimal is unknown after rounding
+fn rounded_type(ty: &LogicalType) -> LogicalType {
+    match ty {
+        LogicalType::Decimal(_, _) => LogicalType::Decimal(None, None),
+        ty => same_type(ty),
+    }
+}
+
+fn abs(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(match args[0].as_ref() {
+        DataValue::Int8(v) => DataValue::Int8(checked_abs!(v)),
+        DataValue::Int16(v) => DataValue::Int16(checked_abs!(v)),
+        DataValue::Int32(v) => DataValue::Int32(checked_abs!(v)),
+        DataValue::Int64(v) => DataValue::Int64(checked_abs!(v)),
+        DataValue::Float32(v) => DataValue::Float32(v.map(f32::abs)),
+        DataValue::Float64(v) => DataValue::Float64(v.map(f64::abs)),
+        DataValue::Decimal(v) => DataValue::Decimal(v.map(|v| v.abs())),
+        value => value.clone(),
+    })
+}
+
+fn sign(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let float_sign = |v: f64| if v == 0.0 { 0.0 } else { v.signum() };
+
+    Ok(match args[0].as_ref() {
+        DataValue::Int8(v) => DataValue::Int8(v.map(i8::signum)),
+        DataValue::Int16(v) => DataValue::Int16(v.map(i16::signum)),
+        DataValue::Int32(v) => DataValue::Int32(v.map(i32::signum)),
+        DataValue::Int64(v) => DataValue::Int64(v.map(i64::signum)),
+        DataValue::UInt8(v) => DataValue::UInt8(v.map(|v| v.min(1))),
+        DataValue::UInt16(v) => DataValue::UInt16(v.map(|v| v.min(1))),
+        DataValue::UInt32(v) => DataValue::UInt32(v.map(|v| v.min(1))),
+        DataValue::UInt64(v) => DataValue::UInt64(v.map(|v| v.min(1))),
+        DataValue::Float32(v) => DataValue::Float32(v.map(|v| float_sign(v as f64) as f32)),
+        DataValue::Float64(v) => DataValue::Float64(v.map(float_sign)),
+        DataValue::Decimal(v) => DataValue::Decimal(v.map(|v| v.signum())),
+        value => value.clone(),
+    })
+}
+
+#[derive(Clone, Copy)]
+enum Rounding {
+    Ceil,
+    Floor,
+    Round,
+    Trunc,
+}
+
+impl Rounding {
+    fn round_f64(&self, v: f64) -> f64 {
+        match self {
+            Rounding::Ceil => v.ceil(),
+            Rounding::Floor => v.floor(),
+            Rounding::Round => v.round(),
+            Rounding::Trunc => v.trunc(),
+        }
+    }
+
+    /// Rounds the integer to a multiple of the factor
+    fn round_i128(&self, v: i128, factor: i128) -> i128 {
+        let (quotient, remainder) = (v / factor, v % factor);
+        let quotient = match self {
+            Rounding::Ceil if remainder > 0 => quotient + 1,
+            Rounding::Floor if remainder < 0 => quotient - 1,
+            Rounding::Round if remainder.abs() * 2 >= factor => quotient + v.signum(),
+            _ => quotient,
+        };
+
+        quotient * factor
+    }
+
+    fn strategy(&self) -> RoundingStrategy {
+        match self {
+            Rounding::Ceil => RoundingStrategy::ToPositiveInfinity,
+            Rounding::Floor => RoundingStrategy::ToNegativeInfinity,
+            Rounding::Round => RoundingStrategy::MidpointAwayFromZero,
+            Rounding::Trunc => RoundingStrategy::ToZero,
+        }
+    }
+
+    /// Rounds the value to `scale` decimal places, which are on the left of the decimal point if negative
+    fn eval(&self, value: &DataValue, scale: i64) -> Result<DataValue, DatabaseError> {
+        // Tips: the values of the types have no more than 38 digits
+        let scale = scale.clamp(-38, 38) as i32;
+        let factor = 10_i128.pow(scale.unsigned_abs());
+
+        Ok(match value {
+            DataValue::Float32(v) => {
+                DataValue::Float32(v.map(|v| self.round_float(v as f64, scale) as f32))
+            }
+            DataValue::Float64(v) => DataValue::Float64(v.map(|v| self.round_float(v, scale))),
+            DataValue::Decimal(v) => DataValue::Decimal(
+                v.map(|v| self.round_decimal(v, scale, factor))
+                    .transpose()?,
+            ),
+            value if scale >= 0 => value.clone(),
+            DataValue::Int8(v) => round_integer!(Int8, v, i8, factor, self),
+            DataValue::Int16(v) => round_integer!(Int16, v, i16, factor, self),
+            DataValue::Int32(v) => round_integer!(Int32, v, i32, factor, self),
+            DataValue::Int64(v) => round_integer!(Int64, v, i64, factor, self),
+            DataValue::UInt8(v) => round_integer!(UInt8, v, u8, factor, self),
+            DataValue::UInt16(v) => round_integer!(UInt16, v, u16, factor, self),
+            DataValue::UInt32(v) => round_integer!(UInt32, v, u32, factor, self),
+            DataValue::UInt64(v) => round_integer!(UInt64, v, u64, factor, self),
+            value => value.clone(),
+        })
+    }
+
+    fn round_float(&self, v: f64, scale: i32) -> f64 {
+        let factor = 10_f64.powi(scale);
+
+        self.round_f64(v * factor) / factor
+    }
+
+    fn round_decimal(
+        &self,
+        v: Decimal,
+        scale: i32,
+        factor: i128,
+    ) -> Result<Decimal, DatabaseError> {
+        if scale >= 0 {
+            return Ok(v.round_dp_with_strategy(scale as u32, self.strategy()));
+        }
+        let factor = Decimal::try_from_i128_with_scale(factor, 0)?;
+
+        (v / factor)
+            .round_dp_with_strategy(0, self.strategy())
+            .checked_mul(factor)
+            .ok_or(DatabaseError::Overflow)
+    }
+}
+
+fn ceil(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Rounding::Ceil.eval(&args[0], 0)
+}
+
+fn floor(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Rounding::Floor.eval(&args[0], 0)
+}
+
+fn round(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    rounding_with_scale(args, Rounding::Round)
+}
+
+fn trunc(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    rounding_with_scale(args, Rounding::Trunc)
+}
+
+fn rounding_with_scale(args: &[ValueRef], rounding: Rounding) -> Result<DataValue, DatabaseError> {
+    let scale = match args.get(1) {
+        Some(value) => match int(value)? {
+            Some(scale) => scale,
+            None => return Ok(DataValue::none(&args[0].logical_type())),
+        },
+        None => 0,
+    };
+
+    rounding.eval(&args[0], scale)
+}
+
+fn modulo(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    DataValue::binary_op(&args[0], &args[1], &BinaryOperator::Modulo)
+}
+
+double_function!(sqrt, f64::sqrt);
+double_function!(cbrt, f64::cbrt);
+double_function!(exp, f64::exp);
+double_function!(ln, f64::ln);
+double_function!(log10, f64::log10);
+double_function!(log2, f64::log2);
+double_function!(sin, f64::sin);
+double_function!(cos, f64::cos);
+double_function!(tan, f64::tan);
+double_function!(asin, f64::asin);
+double_function!(acos, f64::acos);
+double_function!(atan, f64::atan);
+double_function!(degrees, f64::to_degrees);
+double_function!(radians, f64::to_radians);
+
+fn log(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let (Some(base), Some(v)) = (double(&args[0])?, double(&args[1])?) else {
+        return Ok(DataValue::Float64(None));
+    };
+
+    Ok(DataValue::Float64(Some(v.log(base))))
+}
+
+fn power(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let (Some(base), Some(exponent)) = (double(&args[0])?, double(&args[1])?) else {
+        return Ok(DataValue::Float64(None));
+    };
+
+    Ok(DataValue::Float64(Some(base.powf(exponent))))
+}
+
+fn atan2(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let (Some(y), Some(x)) = (double(&args[0])?, double(&args[1])?) else {
+        return Ok(DataValue::Float64(None));
+    };
+
+    Ok(DataValue::Float64(Some(y.atan2(x))))
+}
+
+fn pi(_: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Float64(Some(consts::PI)))
+}
+
+fn random(_: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Float64(Some(rand::thread_rng().gen())))
+}
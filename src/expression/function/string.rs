@@ -0,0 +1,323 @@
+use crate::errors::DatabaseError;
+use crate::expression::function::{
+    int, is_integer, is_string, utf8, BuiltinEval, BuiltinFunction, FunctionSummary,
+};
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+use itertools::Itertools;
+use std::iter;
+
+pub(super) fn resolve(summary: &FunctionSummary) -> Result<Option<BuiltinFunction>, DatabaseError> {
+    let (return_type, eval): (LogicalType, BuiltinEval) =
+        match (summary.name.as_str(), summary.arg_types.as_slice()) {
+            ("upper" | "ucase", [ty]) if is_string(ty) => (LogicalType::Varchar(None), upper),
+            ("lower" | "lcase", [ty]) if is_string(ty) => (LogicalType::Varchar(None), lower),
+            ("length" | "char_length" | "character_length", [ty]) if is_string(ty) => {
+                (LogicalType::Integer, length)
+            }
+            ("octet_length", [ty]) if is_string(ty) => (LogicalType::Integer, octet_length),
+            ("btrim", [ty]) | ("btrim", [ty, LogicalType::Varchar(_) | LogicalType::SqlNull])
+                if is_string(ty) =>
+            {
+                (LogicalType::Varchar(None), btrim)
+            }
+            ("ltrim", [ty]) | ("ltrim", [ty, LogicalType::Varchar(_) | LogicalType::SqlNull])
+                if is_string(ty) =>
+            {
+                (LogicalType::Varchar(None), ltrim)
+            }
+            ("rtrim", [ty]) | ("rtrim", [ty, LogicalType::Varchar(_) | LogicalType::SqlNull])
+                if is_string(ty) =>
+            {
+                (LogicalType::Varchar(None), rtrim)
+            }
+            ("substr", [ty, start]) if is_string(ty) && is_integer(start) => {
+                (LogicalType::Varchar(None), substr)
+            }
+            ("substr", [ty, start, len])
+                if is_string(ty) && is_integer(start) && is_integer(len) =>
+            {
+                (LogicalType::Varchar(None), substr)
+            }
+            ("replace", [ty, from, to]) if is_string(ty) && is_string(from) && is_string(to) => {
+                (LogicalType::Varchar(None), replace)
+            }
+            ("repeat", [ty, n]) if is_string(ty) && is_integer(n) => {
+                (LogicalType::Varchar(None), repeat)
+            }
+            ("reverse", [ty]) if is_string(ty) => (LogicalType::Varchar(None), reverse),
+            ("concat", tys) if !tys.is_empty() && !tys.contains(&LogicalType::Tuple) => {
+                (LogicalType::Varchar(None), concat)
+            }
+            ("concat_ws", [sep, tys @ ..])
+                if is_string(sep) && !tys.is_empty() && !tys.contains(&LogicalType::Tuple) =>
+            {
+                (LogicalType::Varchar(None), concat_ws)
+            }
+            ("lpad", [ty, n]) if is_string(ty) && is_integer(n) => {
+                (LogicalType::Varchar(None), lpad)
+            }
+            ("lpad", [ty, n, fill]) if is_string(ty) && is_integer(n) && is_string(fill) => {
+                (LogicalType::Varchar(None), lpad)
+            }
+            ("rpad", [ty, n]) if is_string(ty) && is_integer(n) => {
+                (LogicalType::Varchar(None), rpad)
+            }
+            ("rpad", [ty, n, fill]) if is_string(ty) && is_integer(n) && is_string(fill) => {
+                (LogicalType::Varchar(None), rpad)
+            }
+            ("strpos", [ty, sub]) if is_string(ty) && is_string(sub) => {
+                (LogicalType::Integer, strpos)
+            }
+            ("starts_with", [ty, prefix]) if is_string(ty) && is_string(prefix) => {
+                (LogicalType::Boolean, starts_with)
+            }
+            ("ascii", [ty]) if is_string(ty) => (LogicalType::Integer, ascii),
+            ("chr", [ty]) if is_integer(ty) => (LogicalType::Varchar(None), chr),
+            ("split_part", [ty, delimiter, n])
+                if is_string(ty) && is_string(delimiter) && is_integer(n) =>
+            {
+                (LogicalType::Varchar(None), split_part)
+            }
+            _ => return Ok(None),
+        };
+
+    Ok(Some(BuiltinFunction::new(summary, return_type, None, eval)))
+}
+
+fn upper(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Utf8(utf8(&args[0])?.map(|s| s.to_uppercase())))
+}
+
+fn lower(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Utf8(utf8(&args[0])?.map(|s| s.to_lowercase())))
+}
+
+fn length(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Int32(
+        utf8(&args[0])?.map(|s| s.chars().count() as i32),
+    ))
+}
+
+fn octet_length(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Int32(utf8(&args[0])?.map(|s| s.len() as i32)))
+}
+
+fn btrim(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    trim(args, true, true)
+}
+
+fn ltrim(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    trim(args, true, false)
+}
+
+fn rtrim(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    trim(args, false, true)
+}
+
+/// Removes the characters of the second argument, which are the spaces if absent, from the string
+fn trim(args: &[ValueRef], leading: bool, trailing: bool) -> Result<DataValue, DatabaseError> {
+    let chars = match args.get(1) {
+        Some(value) => match utf8(value)? {
+            Some(chars) => chars.chars().collect_vec(),
+            None => return Ok(DataValue::Utf8(None)),
+        },
+        None => vec![' '],
+    };
+
+    Ok(DataValue::Utf8(utf8(&args[0])?.map(|s| {
+        let mut s = s.as_str();
+        if leading {
+            s = s.trim_start_matches(chars.as_slice());
+        }
+        if trailing {
+            s = s.trim_end_matches(chars.as_slice());
+        }
+        s.to_string()
+    })))
+}
+
+/// The characters from the position `start` counted from 1, with the length `len` if given
+fn substr(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let (Some(s), Some(start)) = (utf8(&args[0])?, int(&args[1])?) else {
+        return Ok(DataValue::Utf8(None));
+    };
+    let end = match args.get(2) {
+        Some(value) => match int(value)? {
+            Some(len) => Some(start.saturating_add(len.max(0))),
+            None => return Ok(DataValue::Utf8(None)),
+        },
+        None => None,
+    };
+
+    Ok(DataValue::Utf8(Some(
+        s.chars()
+            .enumerate()
+            .filter(|(i, _)| {
+                let pos = *i as i64 + 1;
+
+                pos >= start && end.map_or(true, |end| pos < end)
+            })
+            .map(|(_, c)| c)
+            .collect(),
+    )))
+}
+
+fn replace(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let (Some(s), Some(from), Some(to)) = (utf8(&args[0])?, utf8(&args[1])?, utf8(&args[2])?)
+    else {
+        return Ok(DataValue::Utf8(None));
+    };
+
+    Ok(DataValue::Utf8(Some(s.replace(&from, &to))))
+}
+
+fn repeat(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let (Some(s), Some(n)) = (utf8(&args[0])?, int(&args[1])?) else {
+        return Ok(DataValue::Utf8(None));
+    };
+
+    Ok(DataValue::Utf8(Some(s.repeat(n.max(0) as usize))))
+}
+
+fn reverse(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Utf8(
+        utf8(&args[0])?.map(|s| s.chars().rev().collect()),
+    ))
+}
+
+/// Concatenates the arguments, ignoring the NULLs
+fn concat(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let mut result = String::new();
+
+    for value in args {
+        if let Some(s) = utf8(value)? {
+            result.push_str(&s);
+        }
+    }
+    Ok(DataValue::Utf8(Some(result)))
+}
+
+/// Concatenates the arguments after the first one with it as the separator, ignoring the NULLs
+fn concat_ws(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let Some(separator) = utf8(&args[0])? else {
+        return Ok(DataValue::Utf8(None));
+    };
+    let mut strings = Vec::with_capacity(args.len() - 1);
+
+    for value in &args[1..] {
+        if let Some(s) = utf8(value)? {
+            strings.push(s);
+        }
+    }
+    Ok(DataValue::Utf8(Some(strings.join(&separator))))
+}
+
+fn lpad(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    pad(args, true)
+}
+
+fn rpad(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    pad(args, false)
+}
+
+/// Fills up the string to the length with the characters of the third argument, which are the spaces if absent,
+/// or truncates it if it is already longer than the length
+fn pad(args: &[ValueRef], is_left: bool) -> Result<DataValue, DatabaseError> {
+    let (Some(s), Some(len)) = (utf8(&args[0])?, int(&args[1])?) else {
+        return Ok(DataValue::Utf8(None));
+    };
+    let fill = match args.get(2) {
+        Some(value) => match utf8(value)? {
+            Some(fill) => fill,
+            None => return Ok(DataValue::Utf8(None)),
+        },
+        None => " ".to_string(),
+    };
+    let len = len.max(0) as usize;
+    let chars_len = s.chars().count();
+
+    if chars_len >= len || fill.is_empty() {
+        return Ok(DataValue::Utf8(Some(s.chars().take(len).collect())));
+    }
+    let padding: String = iter::repeat(fill.chars())
+        .flatten()
+        .take(len - chars_len)
+        .collect();
+
+    Ok(DataValue::Utf8(Some(if is_left {
+        padding + &s
+    } else {
+        s + &padding
+    })))
+}
+
+/// The position of the substring counted from 1, 0 if not found
+fn strpos(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let (Some(s), Some(sub)) = (utf8(&args[0])?, utf8(&args[1])?) else {
+        return Ok(DataValue::Int32(None));
+    };
+
+    Ok(DataValue::Int32(Some(
+        s.find(&sub)
+            .map(|i| s[..i].chars().count() as i32 + 1)
+            .unwrap_or(0),
+    )))
+}
+
+fn starts_with(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let (Some(s), Some(prefix)) = (utf8(&args[0])?, utf8(&args[1])?) else {
+        return Ok(DataValue::Boolean(None));
+    };
+
+    Ok(DataValue::Boolean(Some(s.starts_with(&prefix))))
+}
+
+fn ascii(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    Ok(DataValue::Int32(
+        utf8(&args[0])?.map(|s| s.chars().next().map(|c| c as i32).unwrap_or(0)),
+    ))
+}
+
+fn chr(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let Some(code) = int(&args[0])? else {
+        return Ok(DataValue::Utf8(None));
+    };
+    let c = u32::try_from(code)
+        .ok()
+        .and_then(char::from_u32)
+        .ok_or_else(|| DatabaseError::InvalidArgument("chr", code.to_string()))?;
+
+    Ok(DataValue::Utf8(Some(c.to_string())))
+}
+
+/// The `n`th field of the string split by the delimiter, counted from the end if `n` is negative
+fn split_part(args: &[ValueRef]) -> Result<DataValue, DatabaseError> {
+    let (Some(s), Some(delimiter), Some(n)) = (utf8(&args[0])?, utf8(&args[1])?, int(&args[2])?)
+    else {
+        return Ok(DataValue::Utf8(None));
+    };
+    if n == 0 {
+        return Err(DatabaseError::InvalidArgument(
+            "split_part",
+            "field position must not be zero".to_string(),
+        ));
+    }
+    let fields = if delimiter.is_empty() {
+        vec![s.as_str()]
+    } else {
+        s.split(delimiter.as_str()).collect_vec()
+    };
+    let field = if n > 0 {
+        fields.get(n as usize - 1)
+    } else {
+        fields
+            .len()
+            .checked_sub(n.unsigned_abs() as usize)
+            .and_then(|i| fields.get(i))
+    };
+
+    Ok(DataValue::Utf8(Some(
+        field.map(|field| field.to_string()).unwrap_or_default(),
+    )))
+}
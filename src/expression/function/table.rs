@@ -0,0 +1,254 @@
+use crate::catalog::{ColumnCatalog, ColumnDesc};
+use crate::errors::DatabaseError;
+use crate::expression::function::{int, is_integer, FunctionSummary};
+use crate::expression::ScalarExpression;
+use crate::types::tuple::SchemaRef;
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+use itertools::Itertools;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::iter;
+use std::sync::Arc;
+
+/// The rows produced by the table function, whose values follow its output columns
+pub type TableFunctionRows = Box<dyn Iterator<Item = Result<Vec<ValueRef>, DatabaseError>> + Send>;
+
+#[derive(Debug, Clone)]
+pub struct TableFunction {
+    pub(crate) args: Vec<ScalarExpression>,
+    pub(crate) inner: Arc<dyn TableFunctionImpl>,
+}
+
+impl PartialEq for TableFunction {
+    fn eq(&self, other: &Self) -> bool {
+        self.summary() == other.summary() && self.args == other.args
+    }
+}
+
+impl Eq for TableFunction {}
+
+impl Hash for TableFunction {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.summary().hash(state);
+        self.args.hash(state);
+    }
+}
+
+impl TableFunction {
+    pub fn summary(&self) -> &FunctionSummary {
+        self.inner.summary()
+    }
+}
+
+/// The function used in `FROM` as a table, e.g. `SELECT * FROM generate_series(1, 10)`
+pub trait TableFunctionImpl: Debug + Send + Sync {
+    /// The rows of the arguments, whose values have been cast to the argument types of the summary
+    fn eval(&self, args: &[ValueRef]) -> Result<TableFunctionRows, DatabaseError>;
+
+    /// The columns of the rows, which are renamed by the column aliases of the table if any
+    fn output_schema(&self) -> &SchemaRef;
+
+    fn summary(&self) -> &FunctionSummary;
+}
+
+type BuiltinTableEval = fn(&[ValueRef]) -> Result<TableFunctionRows, DatabaseError>;
+
+/// The table functions registered by default
+#[derive(Debug)]
+pub(crate) struct BuiltinTableFunction {
+    summary: FunctionSummary,
+    schema_ref: SchemaRef,
+    eval: BuiltinTableEval,
+}
+
+impl BuiltinTableFunction {
+    /// Resolves the built-in table function with the name and the arguments,
+    /// `None` if there is no such function or the arguments do not fit it
+    pub(crate) fn resolve(
+        summary: &FunctionSummary,
+        args: &[ScalarExpression],
+    ) -> Result<Option<Arc<dyn TableFunctionImpl>>, DatabaseError> {
+        let (ty, eval): (LogicalType, BuiltinTableEval) =
+            match (summary.name.as_str(), summary.arg_types.as_slice()) {
+                ("generate_series", tys @ ([_, _] | [_, _, _])) if tys.iter().all(is_integer) => {
+                    let ty = match LogicalType::common_type(tys)? {
+                        LogicalType::SqlNull => LogicalType::Bigint,
+                        ty => ty,
+                    };
+                    (ty, generate_series)
+                }
+                ("unnest", [LogicalType::Tuple]) => {
+                    // Tips: the type of the elements is only known for the tuple expression
+                    let ScalarExpression::Tuple(exprs) = &args[0] else {
+                        return Ok(None);
+                    };
+                    let tys = exprs
+                        .iter()
+                        .map(ScalarExpression::return_type)
+                        .collect_vec();
+
+                    (LogicalType::common_type(&tys)?, unnest)
+                }
+                _ => return Ok(None),
+            };
+        let column = ColumnCatalog::new(
+            summary.name.clone(),
+            true,
+            ColumnDesc::new(ty, false, false, None),
+        );
+
+        Ok(Some(Arc::new(BuiltinTableFunction {
+            summary: summary.clone(),
+            schema_ref: Arc::new(vec![Arc::new(column)]),
+            eval,
+        })))
+    }
+}
+
+impl TableFunctionImpl for BuiltinTableFunction {
+    fn eval(&self, args: &[ValueRef]) -> Result<TableFunctionRows, DatabaseError> {
+        let types = self
+            .schema_ref
+            .iter()
+            .map(|column| *column.datatype())
+            .collect_vec();
+
+        Ok(Box::new((self.eval)(args)?.map(move |values| {
+            values?
+                .into_iter()
+                .zip_eq(types.iter())
+                .map(|(value, ty)| {
+                    if &value.logical_type() == ty {
+                        Ok(value)
+                    } else {
+                        Ok::<_, DatabaseError>(Arc::new(DataValue::clone(&value).cast(ty)?))
+                    }
+                })
+                .try_collect::<_, Vec<_>, _>()
+        })))
+    }
+
+    fn output_schema(&self) -> &SchemaRef {
+        &self.schema_ref
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
+
+/// The integers from `start` to `stop` by `step`, which is 1 by default
+fn generate_series(args: &[ValueRef]) -> Result<TableFunctionRows, DatabaseError> {
+    let step = match args.get(2) {
+        Some(step) => int(step)?,
+        None => Some(1),
+    };
+    let (Some(start), Some(stop), Some(step)) = (int(&args[0])?, int(&args[1])?, step) else {
+        return Ok(Box::new(iter::empty()));
+    };
+    if step == 0 {
+        return Err(DatabaseError::InvalidArgument(
+            "generate_series",
+            "step size cannot equal zero".to_string(),
+        ));
+    }
+    let mut next = Some(start);
+
+    Ok(Box::new(iter::from_fn(move || {
+        let value = next.filter(|value| {
+            if step > 0 {
+                *value <= stop
+            } else {
+                *value >= stop
+            }
+        })?;
+        next = value.checked_add(step);
+
+        Some(Ok(vec![Arc::new(DataValue::Int64(Some(value)))]))
+    })))
+}
+
+/// Expands the tuple to a row per element
+fn unnest(args: &[ValueRef]) -> Result<TableFunctionRows, DatabaseError> {
+    let values = match args[0].as_ref() {
+        DataValue::Tuple(Some(values)) => values.clone(),
+        _ => vec![],
+    };
+
+    Ok(Box::new(values.into_iter().map(|value| Ok(vec![value]))))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::DatabaseError;
+    use crate::expression::function::table::BuiltinTableFunction;
+    use crate::expression::function::FunctionSummary;
+    use crate::types::value::{DataValue, ValueRef};
+    use crate::types::LogicalType;
+    use itertools::Itertools;
+    use std::sync::Arc;
+
+    fn generate_series(args: Vec<DataValue>) -> Result<Vec<DataValue>, DatabaseError> {
+        let summary = FunctionSummary {
+            name: "generate_series".to_string(),
+            arg_types: args.iter().map(DataValue::logical_type).collect_vec(),
+        };
+        let function = BuiltinTableFunction::resolve(&summary, &[])?.unwrap();
+        let args: Vec<ValueRef> = args.into_iter().map(Arc::new).collect_vec();
+
+        function
+            .eval(&args)?
+            .map(|values| Ok::<_, DatabaseError>(DataValue::clone(&values?[0])))
+            .try_collect()
+    }
+
+    #[test]
+    fn test_generate_series() -> Result<(), DatabaseError> {
+        assert_eq!(
+            generate_series(vec![DataValue::Int32(Some(1)), DataValue::Int32(Some(3))])?,
+            vec![
+                DataValue::Int32(Some(1)),
+                DataValue::Int32(Some(2)),
+                DataValue::Int32(Some(3)),
+            ]
+        );
+        assert_eq!(
+            generate_series(vec![
+                DataValue::Int64(Some(10)),
+                DataValue::Int32(Some(1)),
+                DataValue::Int32(Some(-4)),
+            ])?,
+            vec![
+                DataValue::Int64(Some(10)),
+                DataValue::Int64(Some(6)),
+                DataValue::Int64(Some(2)),
+            ]
+        );
+        assert_eq!(
+            generate_series(vec![
+                DataValue::Int64(Some(i64::MAX - 1)),
+                DataValue::Int64(Some(i64::MAX)),
+                DataValue::Int64(Some(5)),
+            ])?,
+            vec![DataValue::Int64(Some(i64::MAX - 1))]
+        );
+        assert!(generate_series(vec![DataValue::Int32(Some(3)), DataValue::Null])?.is_empty());
+        assert!(generate_series(vec![
+            DataValue::Int32(Some(1)),
+            DataValue::Int32(Some(3)),
+            DataValue::Int32(Some(0)),
+        ])
+        .is_err());
+        assert!(BuiltinTableFunction::resolve(
+            &FunctionSummary {
+                name: "generate_series".to_string(),
+                arg_types: vec![LogicalType::Varchar(None), LogicalType::Integer],
+            },
+            &[]
+        )?
+        .is_none());
+
+        Ok(())
+    }
+}
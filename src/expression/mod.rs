@@ -8,8 +8,11 @@ use std::{fmt, mem};
 use sqlparser::ast::{BinaryOperator as SqlBinaryOperator, UnaryOperator as SqlUnaryOperator};
 
 use self::agg::AggKind;
+use self::window::{WindowFrame, WindowKind};
 use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef};
+use crate::errors::DatabaseError;
 use crate::expression::function::ScalarFunction;
+use crate::planner::operator::sort::SortField;
 use crate::types::value::ValueRef;
 use crate::types::LogicalType;
 
@@ -18,6 +21,7 @@ mod evaluator;
 pub mod function;
 pub mod simplify;
 pub mod value_compute;
+pub mod window;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum AliasType {
@@ -86,6 +90,28 @@ pub enum ScalarExpression {
     },
     Tuple(Vec<ScalarExpression>),
     Function(ScalarFunction),
+    WindowCall {
+        kind: WindowKind,
+        args: Vec<ScalarExpression>,
+        partition_by: Vec<ScalarExpression>,
+        order_by: Vec<SortField>,
+        frame: WindowFrame,
+        ty: LogicalType,
+    },
+    /// `CASE [operand] WHEN ... THEN ... [ELSE ...] END`, the `WHEN` expressions are compared with
+    /// the operand if any, otherwise they are the conditions
+    CaseWhen {
+        operand_expr: Option<Box<ScalarExpression>>,
+        expr_pairs: Vec<(ScalarExpression, ScalarExpression)>,
+        else_expr: Option<Box<ScalarExpression>>,
+        ty: LogicalType,
+    },
+    /// The `$n` parameter of the prepared statement, `index` is `n - 1`,
+    /// and its value is bound when the statement is executed
+    Placeholder {
+        index: usize,
+        value: Option<ValueRef>,
+    },
 }
 
 impl ScalarExpression {
@@ -167,7 +193,8 @@ impl ScalarExpression {
             ScalarExpression::Empty => unreachable!(),
             ScalarExpression::Constant(_)
             | ScalarExpression::ColumnRef(_)
-            | ScalarExpression::Reference { .. } => (),
+            | ScalarExpression::Reference { .. }
+            | ScalarExpression::Placeholder { .. } => (),
             ScalarExpression::Tuple(exprs) => {
                 for expr in exprs {
                     expr.try_reference(output_exprs);
@@ -178,7 +205,252 @@ impl ScalarExpression {
                     expr.try_reference(output_exprs);
                 }
             }
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                for expr in args
+                    .iter_mut()
+                    .chain(partition_by.iter_mut())
+                    .chain(order_by.iter_mut().map(|field| &mut field.expr))
+                {
+                    expr.try_reference(output_exprs);
+                }
+            }
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ..
+            } => {
+                for expr in operand_expr
+                    .iter_mut()
+                    .map(Box::as_mut)
+                    .chain(
+                        expr_pairs
+                            .iter_mut()
+                            .flat_map(|(when_expr, then_expr)| [when_expr, then_expr]),
+                    )
+                    .chain(else_expr.iter_mut().map(Box::as_mut))
+                {
+                    expr.try_reference(output_exprs);
+                }
+            }
+        }
+    }
+
+    /// The reverse of `try_reference`, which restores the referenced expressions
+    pub fn unpack_reference(&mut self) {
+        match self {
+            ScalarExpression::Reference { expr, .. } => {
+                expr.unpack_reference();
+                *self = mem::replace(expr.as_mut(), ScalarExpression::Empty);
+            }
+            ScalarExpression::Alias { expr, .. }
+            | ScalarExpression::TypeCast { expr, .. }
+            | ScalarExpression::IsNull { expr, .. }
+            | ScalarExpression::Unary { expr, .. } => expr.unpack_reference(),
+            ScalarExpression::Binary {
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                left_expr.unpack_reference();
+                right_expr.unpack_reference();
+            }
+            ScalarExpression::AggCall { args, .. }
+            | ScalarExpression::Tuple(args)
+            | ScalarExpression::Function(ScalarFunction { args, .. }) => {
+                for arg in args {
+                    arg.unpack_reference();
+                }
+            }
+            ScalarExpression::In { expr, args, .. } => {
+                expr.unpack_reference();
+                for arg in args {
+                    arg.unpack_reference();
+                }
+            }
+            ScalarExpression::Between {
+                expr,
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                expr.unpack_reference();
+                left_expr.unpack_reference();
+                right_expr.unpack_reference();
+            }
+            ScalarExpression::SubString {
+                expr,
+                for_expr,
+                from_expr,
+            } => {
+                expr.unpack_reference();
+                for expr in for_expr.iter_mut().chain(from_expr.iter_mut()) {
+                    expr.unpack_reference();
+                }
+            }
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                for expr in args
+                    .iter_mut()
+                    .chain(partition_by.iter_mut())
+                    .chain(order_by.iter_mut().map(|field| &mut field.expr))
+                {
+                    expr.unpack_reference();
+                }
+            }
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ..
+            } => {
+                for expr in operand_expr
+                    .iter_mut()
+                    .map(Box::as_mut)
+                    .chain(
+                        expr_pairs
+                            .iter_mut()
+                            .flat_map(|(when_expr, then_expr)| [when_expr, then_expr]),
+                    )
+                    .chain(else_expr.iter_mut().map(Box::as_mut))
+                {
+                    expr.unpack_reference();
+                }
+            }
+            ScalarExpression::Constant(_)
+            | ScalarExpression::ColumnRef(_)
+            | ScalarExpression::Placeholder { .. }
+            | ScalarExpression::Empty => (),
+        }
+    }
+
+    /// Bind the values of the `$n` parameters to the placeholders,
+    /// and returns whether there is any placeholder in the expression.
+    pub fn bind_placeholders(&mut self, params: &[ValueRef]) -> Result<bool, DatabaseError> {
+        let mut has_placeholder = false;
+
+        match self {
+            ScalarExpression::Placeholder { index, value } => {
+                let param = params
+                    .get(*index)
+                    .ok_or_else(|| DatabaseError::InvalidPlaceholder(format!("${}", *index + 1)))?;
+                let _ = value.replace(param.clone());
+
+                has_placeholder = true;
+            }
+            ScalarExpression::Alias { expr, .. }
+            | ScalarExpression::TypeCast { expr, .. }
+            | ScalarExpression::IsNull { expr, .. }
+            | ScalarExpression::Unary { expr, .. }
+            | ScalarExpression::Reference { expr, .. } => {
+                has_placeholder = expr.bind_placeholders(params)?;
+            }
+            ScalarExpression::Binary {
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                has_placeholder |= left_expr.bind_placeholders(params)?;
+                has_placeholder |= right_expr.bind_placeholders(params)?;
+            }
+            ScalarExpression::AggCall { args, .. }
+            | ScalarExpression::Tuple(args)
+            | ScalarExpression::Function(ScalarFunction { args, .. }) => {
+                for arg in args {
+                    has_placeholder |= arg.bind_placeholders(params)?;
+                }
+            }
+            ScalarExpression::In { expr, args, .. } => {
+                has_placeholder |= expr.bind_placeholders(params)?;
+                for arg in args {
+                    has_placeholder |= arg.bind_placeholders(params)?;
+                }
+            }
+            ScalarExpression::Between {
+                expr,
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                has_placeholder |= expr.bind_placeholders(params)?;
+                has_placeholder |= left_expr.bind_placeholders(params)?;
+                has_placeholder |= right_expr.bind_placeholders(params)?;
+            }
+            ScalarExpression::SubString {
+                expr,
+                for_expr,
+                from_expr,
+            } => {
+                has_placeholder |= expr.bind_placeholders(params)?;
+                for expr in for_expr.iter_mut().chain(from_expr.iter_mut()) {
+                    has_placeholder |= expr.bind_placeholders(params)?;
+                }
+            }
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                for expr in args
+                    .iter_mut()
+                    .chain(partition_by.iter_mut())
+                    .chain(order_by.iter_mut().map(|field| &mut field.expr))
+                {
+                    has_placeholder |= expr.bind_placeholders(params)?;
+                }
+            }
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ..
+            } => {
+                for expr in operand_expr
+                    .iter_mut()
+                    .map(Box::as_mut)
+                    .chain(
+                        expr_pairs
+                            .iter_mut()
+                            .flat_map(|(when_expr, then_expr)| [when_expr, then_expr]),
+                    )
+                    .chain(else_expr.iter_mut().map(Box::as_mut))
+                {
+                    has_placeholder |= expr.bind_placeholders(params)?;
+                }
+            }
+            ScalarExpression::Constant(_)
+            | ScalarExpression::ColumnRef(_)
+            | ScalarExpression::Empty => (),
         }
+
+        Ok(has_placeholder)
+    }
+
+    /// The operand, the `WHEN` and `THEN` expressions and the `ELSE` expression of `CASE`
+    pub(crate) fn case_when_exprs<'a>(
+        operand_expr: &'a Option<Box<ScalarExpression>>,
+        expr_pairs: &'a [(ScalarExpression, ScalarExpression)],
+        else_expr: &'a Option<Box<ScalarExpression>>,
+    ) -> impl Iterator<Item = &'a ScalarExpression> {
+        operand_expr
+            .iter()
+            .map(Box::as_ref)
+            .chain(
+                expr_pairs
+                    .iter()
+                    .flat_map(|(when_expr, then_expr)| [when_expr, then_expr]),
+            )
+            .chain(else_expr.iter().map(Box::as_ref))
     }
 
     pub fn has_count_star(&self) -> bool {
@@ -196,7 +468,9 @@ impl ScalarExpression {
             | ScalarExpression::Function(ScalarFunction { args, .. }) => {
                 args.iter().any(Self::has_count_star)
             }
-            ScalarExpression::Constant(_) | ScalarExpression::ColumnRef(_) => false,
+            ScalarExpression::Constant(_)
+            | ScalarExpression::ColumnRef(_)
+            | ScalarExpression::Placeholder { .. } => false,
             ScalarExpression::In { expr, args, .. } => {
                 expr.has_count_star() || args.iter().any(Self::has_count_star)
             }
@@ -224,6 +498,24 @@ impl ScalarExpression {
             ScalarExpression::Empty => unreachable!(),
             ScalarExpression::Reference { expr, .. } => expr.has_count_star(),
             ScalarExpression::Tuple(args) => args.iter().any(Self::has_count_star),
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => args
+                .iter()
+                .chain(partition_by.iter())
+                .chain(order_by.iter().map(|field| &field.expr))
+                .any(Self::has_count_star),
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ..
+            } => {
+                Self::case_when_exprs(operand_expr, expr_pairs, else_expr).any(Self::has_count_star)
+            }
         }
     }
 
@@ -231,6 +523,10 @@ impl ScalarExpression {
         match self {
             Self::Constant(v) => v.logical_type(),
             Self::ColumnRef(col) => *col.datatype(),
+            Self::Placeholder { value, .. } => value
+                .as_ref()
+                .map(|value| value.logical_type())
+                .unwrap_or(LogicalType::SqlNull),
             Self::Binary {
                 ty: return_type, ..
             } => *return_type,
@@ -242,6 +538,12 @@ impl ScalarExpression {
             } => *return_type,
             Self::AggCall {
                 ty: return_type, ..
+            }
+            | Self::WindowCall {
+                ty: return_type, ..
+            }
+            | Self::CaseWhen {
+                ty: return_type, ..
             } => *return_type,
             Self::IsNull { .. } | Self::In { .. } | ScalarExpression::Between { .. } => {
                 LogicalType::Boolean
@@ -322,7 +624,33 @@ impl ScalarExpression {
                         columns_collect(from_expr, vec, only_column_ref);
                     }
                 }
-                ScalarExpression::Constant(_) => (),
+                ScalarExpression::WindowCall {
+                    args,
+                    partition_by,
+                    order_by,
+                    ..
+                } => {
+                    for expr in args
+                        .iter()
+                        .chain(partition_by.iter())
+                        .chain(order_by.iter().map(|field| &field.expr))
+                    {
+                        columns_collect(expr, vec, only_column_ref)
+                    }
+                }
+                ScalarExpression::CaseWhen {
+                    operand_expr,
+                    expr_pairs,
+                    else_expr,
+                    ..
+                } => {
+                    for expr in
+                        ScalarExpression::case_when_exprs(operand_expr, expr_pairs, else_expr)
+                    {
+                        columns_collect(expr, vec, only_column_ref)
+                    }
+                }
+                ScalarExpression::Constant(_) | ScalarExpression::Placeholder { .. } => (),
                 ScalarExpression::Reference { .. } | ScalarExpression::Empty => unreachable!(),
             }
         }
@@ -336,7 +664,7 @@ impl ScalarExpression {
     pub fn has_agg_call(&self) -> bool {
         match self {
             ScalarExpression::AggCall { .. } => true,
-            ScalarExpression::Constant(_) => false,
+            ScalarExpression::Constant(_) | ScalarExpression::Placeholder { .. } => false,
             ScalarExpression::ColumnRef(_) => false,
             ScalarExpression::Alias { expr, .. } => expr.has_agg_call(),
             ScalarExpression::TypeCast { expr, .. } => expr.has_agg_call(),
@@ -376,6 +704,81 @@ impl ScalarExpression {
             | ScalarExpression::Function(ScalarFunction { args, .. }) => {
                 args.iter().any(Self::has_agg_call)
             }
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => args
+                .iter()
+                .chain(partition_by.iter())
+                .chain(order_by.iter().map(|field| &field.expr))
+                .any(Self::has_agg_call),
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ..
+            } => Self::case_when_exprs(operand_expr, expr_pairs, else_expr).any(Self::has_agg_call),
+        }
+    }
+
+    pub fn has_window_call(&self) -> bool {
+        match self {
+            ScalarExpression::WindowCall { .. } => true,
+            ScalarExpression::Constant(_)
+            | ScalarExpression::ColumnRef(_)
+            | ScalarExpression::Placeholder { .. } => false,
+            ScalarExpression::Alias { expr, .. }
+            | ScalarExpression::TypeCast { expr, .. }
+            | ScalarExpression::IsNull { expr, .. }
+            | ScalarExpression::Unary { expr, .. } => expr.has_window_call(),
+            ScalarExpression::Binary {
+                left_expr,
+                right_expr,
+                ..
+            } => left_expr.has_window_call() || right_expr.has_window_call(),
+            ScalarExpression::In { expr, args, .. } => {
+                expr.has_window_call() || args.iter().any(Self::has_window_call)
+            }
+            ScalarExpression::Between {
+                expr,
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                expr.has_window_call()
+                    || left_expr.has_window_call()
+                    || right_expr.has_window_call()
+            }
+            ScalarExpression::SubString {
+                expr,
+                for_expr,
+                from_expr,
+            } => {
+                expr.has_window_call()
+                    || matches!(
+                        for_expr.as_ref().map(|expr| expr.has_window_call()),
+                        Some(true)
+                    )
+                    || matches!(
+                        from_expr.as_ref().map(|expr| expr.has_window_call()),
+                        Some(true)
+                    )
+            }
+            ScalarExpression::Reference { .. } | ScalarExpression::Empty => unreachable!(),
+            ScalarExpression::AggCall { args, .. }
+            | ScalarExpression::Tuple(args)
+            | ScalarExpression::Function(ScalarFunction { args, .. }) => {
+                args.iter().any(Self::has_window_call)
+            }
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ..
+            } => Self::case_when_exprs(operand_expr, expr_pairs, else_expr)
+                .any(Self::has_window_call),
         }
     }
 
@@ -383,6 +786,8 @@ impl ScalarExpression {
         match self {
             ScalarExpression::Constant(value) => format!("{}", value),
             ScalarExpression::ColumnRef(col) => col.full_name(),
+            // Tips: the name is kept after binding, which the other operators reference
+            ScalarExpression::Placeholder { index, .. } => format!("${}", index + 1),
             ScalarExpression::Alias { alias, expr } => match alias {
                 AliasType::Name(alias) => alias.to_string(),
                 AliasType::Expr(alias_expr) => {
@@ -424,7 +829,7 @@ impl ScalarExpression {
                     }
                 };
                 format!(
-                    "{:?}({}{})",
+                    "{}({}{})",
                     kind,
                     op(kind.allow_distinct(), *distinct),
                     args_str
@@ -483,6 +888,62 @@ impl ScalarExpression {
                 let args_str = args.iter().map(|expr| expr.output_name()).join(", ");
                 format!("{}({})", inner.summary().name, args_str)
             }
+            ScalarExpression::WindowCall {
+                kind,
+                args,
+                partition_by,
+                order_by,
+                frame,
+                ..
+            } => {
+                let args_str = args.iter().map(|expr| expr.output_name()).join(", ");
+                let mut over = Vec::new();
+
+                if !partition_by.is_empty() {
+                    over.push(format!(
+                        "Partition By {}",
+                        partition_by
+                            .iter()
+                            .map(|expr| expr.output_name())
+                            .join(", ")
+                    ));
+                }
+                if !order_by.is_empty() {
+                    over.push(format!(
+                        "Order By {}",
+                        order_by.iter().map(|field| format!("{}", field)).join(", ")
+                    ));
+                }
+                if kind.use_frame() {
+                    over.push(format!("{}", frame));
+                }
+
+                format!("{}({}) Over ({})", kind, args_str, over.join(" "))
+            }
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ..
+            } => {
+                let mut output_name = "case".to_string();
+
+                if let Some(expr) = operand_expr {
+                    output_name += &format!(" {}", expr.output_name());
+                }
+                for (when_expr, then_expr) in expr_pairs {
+                    output_name += &format!(
+                        " when {} then {}",
+                        when_expr.output_name(),
+                        then_expr.output_name()
+                    );
+                }
+                if let Some(expr) = else_expr {
+                    output_name += &format!(" else {}", expr.output_name());
+                }
+
+                format!("{} end", output_name)
+            }
         }
     }
 
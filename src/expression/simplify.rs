@@ -431,6 +431,16 @@ impl ScalarExpression {
             | ScalarExpression::Function(ScalarFunction { args, .. }) => args
                 .iter()
                 .any(|expr| expr.exist_column(table_name, col_id)),
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => args
+                .iter()
+                .chain(partition_by.iter())
+                .chain(order_by.iter().map(|field| &field.expr))
+                .any(|expr| expr.exist_column(table_name, col_id)),
             ScalarExpression::In { expr, args, .. } => {
                 expr.exist_column(table_name, col_id)
                     || args
@@ -462,14 +472,24 @@ impl ScalarExpression {
                         .map(|expr| expr.exist_column(table_name, col_id))
                         == Some(true)
             }
-            ScalarExpression::Constant(_) => false,
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ..
+            } => Self::case_when_exprs(operand_expr, expr_pairs, else_expr)
+                .any(|expr| expr.exist_column(table_name, col_id)),
+            ScalarExpression::Constant(_) | ScalarExpression::Placeholder { .. } => false,
             ScalarExpression::Reference { .. } | ScalarExpression::Empty => unreachable!(),
         }
     }
 
     fn unpack_val(&self) -> Option<ValueRef> {
         match self {
-            ScalarExpression::Constant(val) => Some(val.clone()),
+            ScalarExpression::Constant(val)
+            | ScalarExpression::Placeholder {
+                value: Some(val), ..
+            } => Some(val.clone()),
             ScalarExpression::Alias { expr, .. } => expr.unpack_val(),
             ScalarExpression::TypeCast { expr, ty, .. } => expr
                 .unpack_val()
@@ -527,6 +547,12 @@ impl ScalarExpression {
 
     pub fn constant_calculation(&mut self) -> Result<(), DatabaseError> {
         match self {
+            ScalarExpression::Placeholder {
+                value: Some(value), ..
+            } => {
+                let value = value.clone();
+                let _ = mem::replace(self, ScalarExpression::Constant(value));
+            }
             ScalarExpression::Unary { expr, op, .. } => {
                 expr.constant_calculation()?;
 
@@ -561,6 +587,66 @@ impl ScalarExpression {
                     expr.constant_calculation()?;
                 }
             }
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ty,
+            } => {
+                let ty = *ty;
+
+                if let Some(expr) = operand_expr {
+                    expr.constant_calculation()?;
+                }
+                for (when_expr, then_expr) in expr_pairs.iter_mut() {
+                    when_expr.constant_calculation()?;
+                    then_expr.constant_calculation()?;
+                }
+                if let Some(expr) = else_expr {
+                    expr.constant_calculation()?;
+                }
+                let operand_val = match operand_expr.as_deref() {
+                    Some(ScalarExpression::Constant(val)) => Some(val.clone()),
+                    Some(_) => return Ok(()),
+                    None => None,
+                };
+                // the branches whose `WHEN` expressions are constants can be decided
+                let mut i = 0;
+                while i < expr_pairs.len() {
+                    let ScalarExpression::Constant(when_val) = &expr_pairs[i].0 else {
+                        i += 1;
+                        continue;
+                    };
+                    let is_matched = match &operand_val {
+                        Some(operand_val) => matches!(
+                            DataValue::binary_op(operand_val, when_val, &BinaryOperator::Eq)?,
+                            DataValue::Boolean(Some(true))
+                        ),
+                        None => matches!(when_val.as_ref(), DataValue::Boolean(Some(true))),
+                    };
+                    if is_matched {
+                        // Tips: the branches after the matched one are never reached
+                        let (_, then_expr) = expr_pairs.drain(i..).next().unwrap();
+                        *else_expr = Some(Box::new(then_expr));
+                        break;
+                    }
+                    expr_pairs.remove(i);
+                }
+                if expr_pairs.is_empty() {
+                    let new_expr = match else_expr.take().map(|expr| *expr) {
+                        Some(ScalarExpression::Constant(val)) => {
+                            ScalarExpression::Constant(Arc::new(DataValue::clone(&val).cast(&ty)?))
+                        }
+                        Some(expr) if expr.return_type() == ty => expr,
+                        Some(expr) => ScalarExpression::TypeCast {
+                            expr: Box::new(expr),
+                            ty,
+                        },
+                        None => ScalarExpression::Constant(Arc::new(DataValue::none(&ty))),
+                    };
+                    let _ = mem::replace(self, new_expr);
+                }
+            }
             _ => (),
         }
 
@@ -986,6 +1072,7 @@ impl ScalarExpression {
                     Ok(None)
                 }
                 ScalarExpression::Constant(_)
+                | ScalarExpression::Placeholder { .. }
                 | ScalarExpression::Alias { .. }
                 | ScalarExpression::TypeCast { .. }
                 | ScalarExpression::IsNull { .. }
@@ -995,16 +1082,22 @@ impl ScalarExpression {
                 | ScalarExpression::In { .. }
                 | ScalarExpression::Between { .. }
                 | ScalarExpression::SubString { .. }
-                | ScalarExpression::Function(_) => expr.convert_binary(table_name, id),
+                | ScalarExpression::Function(_)
+                | ScalarExpression::WindowCall { .. }
+                | ScalarExpression::CaseWhen { .. } => expr.convert_binary(table_name, id),
                 ScalarExpression::Tuple(_)
                 | ScalarExpression::Reference { .. }
                 | ScalarExpression::Empty => unreachable!(),
             },
-            ScalarExpression::Constant(_) | ScalarExpression::ColumnRef(_) => Ok(None),
+            ScalarExpression::Constant(_)
+            | ScalarExpression::ColumnRef(_)
+            | ScalarExpression::Placeholder { .. } => Ok(None),
             // FIXME: support `convert_binary`
             ScalarExpression::Tuple(_)
             | ScalarExpression::AggCall { .. }
-            | ScalarExpression::Function(_) => Ok(None),
+            | ScalarExpression::Function(_)
+            | ScalarExpression::WindowCall { .. }
+            | ScalarExpression::CaseWhen { .. } => Ok(None),
             ScalarExpression::Reference { .. } | ScalarExpression::Empty => unreachable!(),
         }
     }
@@ -1128,7 +1221,7 @@ mod test {
             nullable: false,
             desc: ColumnDesc {
                 column_datatype: LogicalType::Integer,
-                is_primary: false,
+                primary: None,
                 is_unique: false,
                 default: None,
             },
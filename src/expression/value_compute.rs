@@ -25,6 +25,38 @@ fn unpack_tuple(value: DataValue) -> Option<Vec<ValueRef>> {
     }
 }
 
+/// The remainder of `%`, whose divisor is not zero
+trait Remainder: Copy + Default + PartialEq {
+    fn remainder(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_integer_remainder {
+    ($($ty:ty),*) => {
+        $(
+            impl Remainder for $ty {
+                fn remainder(self, rhs: Self) -> Self {
+                    // Tips: `MIN % -1` overflows, whose remainder is 0
+                    self.checked_rem(rhs).unwrap_or(0)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_remainder!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl Remainder for f32 {
+    fn remainder(self, rhs: Self) -> Self {
+        self % rhs
+    }
+}
+
+impl Remainder for f64 {
+    fn remainder(self, rhs: Self) -> Self {
+        self % rhs
+    }
+}
+
 macro_rules! numeric_binary_compute {
     ($compute_type:path, $left:expr, $right:expr, $op:expr, $unified_type:expr) => {
         match $op {
@@ -72,6 +104,20 @@ macro_rules! numeric_binary_compute {
 
                 DataValue::Float64(value)
             }
+            BinaryOperator::Modulo => {
+                let value = if let ($compute_type(Some(v1)), $compute_type(Some(v2))) =
+                    ($left.cast($unified_type)?, $right.cast($unified_type)?)
+                {
+                    if v2 == Default::default() {
+                        return Err(DatabaseError::DivisionByZero);
+                    }
+                    Some(Remainder::remainder(v1, v2))
+                } else {
+                    None
+                };
+
+                $compute_type(value)
+            }
 
             BinaryOperator::Gt => {
                 let value = if let ($compute_type(Some(v1)), $compute_type(Some(v2))) =
@@ -389,6 +435,21 @@ impl DataValue {
 
                         DataValue::Decimal(value)
                     }
+                    BinaryOperator::Modulo => {
+                        let value =
+                            if let (DataValue::Decimal(Some(v1)), DataValue::Decimal(Some(v2))) =
+                                (left_value, right_value)
+                            {
+                                if v2.is_zero() {
+                                    return Err(DatabaseError::DivisionByZero);
+                                }
+                                Some(v1 % v2)
+                            } else {
+                                None
+                            };
+
+                        DataValue::Decimal(value)
+                    }
 
                     BinaryOperator::Gt => {
                         let value =
@@ -598,6 +659,7 @@ mod test {
     use crate::errors::DatabaseError;
     use crate::expression::BinaryOperator;
     use crate::types::value::DataValue;
+    use rust_decimal::Decimal;
 
     #[test]
     fn test_binary_op_arithmetic_plus() -> Result<(), DatabaseError> {
@@ -919,6 +981,58 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_binary_op_arithmetic_modulo() -> Result<(), DatabaseError> {
+        let modulo_i32_1 = DataValue::binary_op(
+            &DataValue::Int32(Some(10)),
+            &DataValue::Int32(None),
+            &BinaryOperator::Modulo,
+        )?;
+        let modulo_i32_2 = DataValue::binary_op(
+            &DataValue::Int32(Some(-10)),
+            &DataValue::Int32(Some(3)),
+            &BinaryOperator::Modulo,
+        )?;
+        let modulo_i32_3 = DataValue::binary_op(
+            &DataValue::Int32(Some(i32::MIN)),
+            &DataValue::Int32(Some(-1)),
+            &BinaryOperator::Modulo,
+        )?;
+
+        assert_eq!(modulo_i32_1, DataValue::Int32(None));
+        assert_eq!(modulo_i32_2, DataValue::Int32(Some(-1)));
+        assert_eq!(modulo_i32_3, DataValue::Int32(Some(0)));
+        assert!(matches!(
+            DataValue::binary_op(
+                &DataValue::Int32(Some(10)),
+                &DataValue::Int32(Some(0)),
+                &BinaryOperator::Modulo,
+            ),
+            Err(DatabaseError::DivisionByZero)
+        ));
+
+        let modulo_decimal = DataValue::binary_op(
+            &DataValue::Decimal(Some(Decimal::new(105, 1))),
+            &DataValue::Decimal(Some(Decimal::new(3, 0))),
+            &BinaryOperator::Modulo,
+        )?;
+
+        assert_eq!(
+            modulo_decimal,
+            DataValue::Decimal(Some(Decimal::new(15, 1)))
+        );
+        assert!(matches!(
+            DataValue::binary_op(
+                &DataValue::Decimal(Some(Decimal::new(105, 1))),
+                &DataValue::Decimal(Some(Decimal::ZERO)),
+                &BinaryOperator::Modulo,
+            ),
+            Err(DatabaseError::DivisionByZero)
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn test_binary_op_cast() -> Result<(), DatabaseError> {
         let i32_cast_1 = DataValue::binary_op(
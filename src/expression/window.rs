@@ -0,0 +1,95 @@
+use crate::expression::agg::AggKind;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WindowKind {
+    RowNumber,
+    Rank,
+    DenseRank,
+    Lag,
+    Lead,
+    FirstValue,
+    LastValue,
+    Agg(AggKind),
+}
+
+impl WindowKind {
+    /// Only the aggregates and the `FIRST_VALUE`/`LAST_VALUE` are computed over the frame,
+    /// the others are computed over the whole partition.
+    pub fn use_frame(&self) -> bool {
+        matches!(
+            self,
+            WindowKind::FirstValue | WindowKind::LastValue | WindowKind::Agg(_)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowFrameBound {
+    UnboundedPreceding,
+    Preceding(u64),
+    CurrentRow,
+    Following(u64),
+    UnboundedFollowing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowFrame {
+    pub units: WindowFrameUnits,
+    pub start: WindowFrameBound,
+    pub end: WindowFrameBound,
+}
+
+impl WindowFrame {
+    /// `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW` if the window is ordered,
+    /// otherwise the whole partition.
+    pub fn default_with(is_ordered: bool) -> Self {
+        WindowFrame {
+            units: WindowFrameUnits::Range,
+            start: WindowFrameBound::UnboundedPreceding,
+            end: if is_ordered {
+                WindowFrameBound::CurrentRow
+            } else {
+                WindowFrameBound::UnboundedFollowing
+            },
+        }
+    }
+}
+
+impl fmt::Display for WindowKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            WindowKind::Agg(kind) => write!(f, "{}", kind),
+            kind => write!(f, "{:?}", kind),
+        }
+    }
+}
+
+impl fmt::Display for WindowFrameBound {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            WindowFrameBound::UnboundedPreceding => write!(f, "Unbounded Preceding"),
+            WindowFrameBound::Preceding(offset) => write!(f, "{} Preceding", offset),
+            WindowFrameBound::CurrentRow => write!(f, "Current Row"),
+            WindowFrameBound::Following(offset) => write!(f, "{} Following", offset),
+            WindowFrameBound::UnboundedFollowing => write!(f, "Unbounded Following"),
+        }
+    }
+}
+
+impl fmt::Display for WindowFrame {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?} Between {} And {}",
+            self.units, self.start, self.end
+        )
+    }
+}
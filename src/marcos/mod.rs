@@ -107,7 +107,7 @@ macro_rules! function {
             }
 
             fn monotonicity(&self) -> Option<FuncMonotonicity> {
-                todo!()
+                None
             }
 
             fn return_type(&self) -> &LogicalType {
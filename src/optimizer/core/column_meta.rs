@@ -128,7 +128,7 @@ mod tests {
             nullable: false,
             desc: ColumnDesc {
                 column_datatype: LogicalType::UInteger,
-                is_primary: false,
+                primary: None,
                 is_unique: false,
                 default: None,
             },
@@ -73,6 +73,11 @@ impl HistogramBuilder {
         Ok(())
     }
 
+    /// The number of the appended values which are not null
+    pub fn values_len(&self) -> usize {
+        self.values.len()
+    }
+
     pub fn build(
         self,
         number_of_buckets: usize,
@@ -212,6 +217,10 @@ impl Histogram {
         self.values_len
     }
 
+    pub fn number_of_distinct_value(&self) -> usize {
+        self.number_of_distinct_value
+    }
+
     /// Tips: binaries must be used `ConstantBinary::scope_aggregation` and `ConstantBinary::rearrange`
     pub fn collect_count(
         &self,
@@ -457,7 +466,7 @@ mod tests {
             nullable: false,
             desc: ColumnDesc {
                 column_datatype: LogicalType::UInteger,
-                is_primary: false,
+                primary: None,
                 is_unique: false,
                 default: None,
             },
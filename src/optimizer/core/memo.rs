@@ -161,4 +161,50 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_choose_join_implementation() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build().await?;
+        database
+            .run("create table t1 (c1 int primary key, c2 int)")
+            .await?;
+        database
+            .run("create table t2 (c3 int primary key, c4 int)")
+            .await?;
+
+        for i in 0..100 {
+            let _ = database
+                .run(format!("insert into t1 values({}, {})", i, i + 1).as_str())
+                .await?;
+            let _ = database
+                .run(format!("insert into t2 values({}, {})", i, i + 1).as_str())
+                .await?;
+        }
+        database.run("analyze table t1").await?;
+        database.run("analyze table t2").await?;
+
+        let explain = |sql: &str| {
+            let sql = format!("explain {}", sql);
+            let database = &database;
+
+            async move {
+                let tuples = database.run(sql).await?;
+
+                Ok::<String, DatabaseError>(tuples[0].values[0].to_string())
+            }
+        };
+        // both sides are in the order of the primary keys, so there is no need to sort them
+        assert!(explain("select * from t1 join t2 on c1 = c3")
+            .await?
+            .contains("[SortMergeJoin]"));
+        assert!(explain("select * from t1 join t2 on c2 = c4")
+            .await?
+            .contains("[HashJoin]"));
+        assert!(explain("select * from t1 join t2 on c1 > c3")
+            .await?
+            .contains("[NestedLoopJoin]"));
+
+        Ok(())
+    }
 }
@@ -11,8 +11,15 @@ pub trait MatchPattern {
     fn pattern(&self) -> &Pattern;
 }
 
-pub trait NormalizationRule: MatchPattern {
-    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError>;
+/// Tips: the loader of the statistics is only used by the cost-based rules,
+/// which keep the plan as it is without the loader
+pub trait NormalizationRule<T: Transaction>: MatchPattern {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        loader: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError>;
 }
 
 pub trait ImplementationRule<T: Transaction>: MatchPattern {
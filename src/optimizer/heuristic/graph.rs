@@ -6,6 +6,7 @@ use crate::planner::LogicalPlan;
 use itertools::Itertools;
 use petgraph::stable_graph::{NodeIndex, StableDiGraph};
 use petgraph::visit::{Bfs, EdgeRef};
+use std::collections::HashMap;
 use std::mem;
 
 /// HepNodeId is used in optimizer to identify a node.
@@ -16,6 +17,8 @@ pub struct HepGraph {
     graph: StableDiGraph<Operator, usize, usize>,
     root_index: HepNodeId,
     pub version: usize,
+    /// The rows of the nodes estimated by the statistics
+    pub(crate) estimated_rows: HashMap<HepNodeId, usize>,
 }
 
 impl HepGraph {
@@ -46,6 +49,7 @@ impl HepGraph {
             graph,
             root_index,
             version: 0,
+            estimated_rows: HashMap::new(),
         }
     }
 
@@ -95,6 +99,23 @@ impl HepGraph {
         self.version += 1;
     }
 
+    /// Replaces the children of the node, which are ordered as given.
+    pub fn replace_childrens(&mut self, source_id: HepNodeId, children_ids: &[HepNodeId]) {
+        let edge_ids = self
+            .graph
+            .edges(source_id)
+            .map(|edge_ref| edge_ref.id())
+            .collect_vec();
+
+        for edge_id in edge_ids {
+            let _ = self.graph.remove_edge(edge_id);
+        }
+        for (order, children_id) in children_ids.iter().enumerate() {
+            let _ = self.graph.add_edge(source_id, *children_id, order);
+        }
+        self.version += 1;
+    }
+
     pub fn swap_node(&mut self, a: HepNodeId, b: HepNodeId) {
         let tmp = self.graph[a].clone();
 
@@ -126,6 +147,7 @@ impl HepGraph {
         }
 
         self.version += 1;
+        self.estimated_rows.remove(&source_id);
         self.graph.remove_node(source_id)
     }
 
@@ -196,6 +218,7 @@ impl HepGraph {
     fn build_childrens(&mut self, start: HepNodeId, memo: Option<&Memo>) -> Option<LogicalPlan> {
         let mut childrens = Vec::with_capacity(2);
         let physical_option = memo.and_then(|memo| memo.cheapest_physical_option(&start));
+        let estimated_rows = self.estimated_rows.get(&start).cloned();
 
         for child_id in self.children_at(start).collect_vec() {
             if let Some(child_plan) = self.build_childrens(child_id, memo) {
@@ -207,6 +230,7 @@ impl HepGraph {
             operator,
             childrens,
             physical_option,
+            estimated_rows,
             _output_schema_ref: None,
         })
     }
@@ -103,19 +103,23 @@ mod tests {
                         operator: Operator::Dummy,
                         childrens: vec![],
                         physical_option: None,
+                        estimated_rows: None,
                         _output_schema_ref: None,
                     }],
                     physical_option: None,
+                    estimated_rows: None,
                     _output_schema_ref: None,
                 },
                 LogicalPlan {
                     operator: Operator::Dummy,
                     childrens: vec![],
                     physical_option: None,
+                    estimated_rows: None,
                     _output_schema_ref: None,
                 },
             ],
             physical_option: None,
+            estimated_rows: None,
             _output_schema_ref: None,
         };
         let graph = HepGraph::new(all_dummy_plan.clone());
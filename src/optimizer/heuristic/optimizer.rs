@@ -51,7 +51,7 @@ impl HepOptimizer {
             let mut iteration = 1usize;
 
             while iteration <= batch.strategy.max_iteration && !batch_over {
-                if Self::apply_batch(&mut self.graph, batch)? {
+                if Self::apply_batch(&mut self.graph, batch, loader)? {
                     iteration += 1;
                 } else {
                     batch_over = true
@@ -72,17 +72,18 @@ impl HepOptimizer {
             .ok_or(DatabaseError::EmptyPlan)
     }
 
-    fn apply_batch(
+    fn apply_batch<T: Transaction>(
         graph: &mut HepGraph,
         HepBatch {
             rules, strategy, ..
         }: &HepBatch,
+        loader: Option<&ColumnMetaLoader<'_, T>>,
     ) -> Result<bool, DatabaseError> {
         let before_version = graph.version;
 
         for rule in rules {
             for node_id in graph.nodes_iter(strategy.match_order, None) {
-                if Self::apply_rule(graph, rule, node_id)? {
+                if Self::apply_rule(graph, rule, node_id, loader)? {
                     break;
                 }
             }
@@ -91,15 +92,16 @@ impl HepOptimizer {
         Ok(before_version != graph.version)
     }
 
-    fn apply_rule(
+    fn apply_rule<T: Transaction>(
         graph: &mut HepGraph,
         rule: &NormalizationRuleImpl,
         node_id: HepNodeId,
+        loader: Option<&ColumnMetaLoader<'_, T>>,
     ) -> Result<bool, DatabaseError> {
         let before_version = graph.version;
 
         if HepMatcher::new(rule.pattern(), node_id, graph).match_opt_expr() {
-            rule.apply(node_id, graph)?;
+            rule.apply(node_id, graph, loader)?;
         }
 
         Ok(before_version != graph.version)
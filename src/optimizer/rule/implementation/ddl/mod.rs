@@ -1,5 +1,12 @@
 pub(crate) mod add_column;
+pub(crate) mod alter_user;
+pub(crate) mod create_index;
 pub(crate) mod create_table;
+pub(crate) mod create_user;
 pub(crate) mod drop_column;
+pub(crate) mod drop_index;
 pub(crate) mod drop_table;
+pub(crate) mod drop_user;
+pub(crate) mod grant;
+pub(crate) mod revoke;
 pub(crate) mod truncate;
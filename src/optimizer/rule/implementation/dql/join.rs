@@ -1,14 +1,30 @@
+use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
+use crate::expression::ScalarExpression;
 use crate::optimizer::core::column_meta::ColumnMetaLoader;
 use crate::optimizer::core::memo::{Expression, GroupExpression};
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
+use crate::optimizer::rule::implementation::dql::scan::find_column_meta;
+use crate::planner::operator::join::{JoinCondition, JoinOperator};
 use crate::planner::operator::{Operator, PhysicalOption};
-use crate::single_mapping;
 use crate::storage::Transaction;
+use crate::types::LogicalType;
 use lazy_static::lazy_static;
 
 lazy_static! {
+    static ref EQUI_JOIN_PATTERN: Pattern = {
+        Pattern {
+            predicate: |op| matches!(op, Operator::Join(op) if !equi_keys(op).is_empty()),
+            children: PatternChildrenPredicate::None,
+        }
+    };
+    static ref SORT_MERGE_JOIN_PATTERN: Pattern = {
+        Pattern {
+            predicate: |op| matches!(op, Operator::Join(op) if is_sort_merge_joinable(op)),
+            children: PatternChildrenPredicate::None,
+        }
+    };
     static ref JOIN_PATTERN: Pattern = {
         Pattern {
             predicate: |op| matches!(op, Operator::Join(_)),
@@ -20,8 +36,175 @@ lazy_static! {
 #[derive(Clone)]
 pub struct HashJoinImplementation;
 
-single_mapping!(
-    HashJoinImplementation,
-    JOIN_PATTERN,
-    PhysicalOption::HashJoin
-);
+impl MatchPattern for HashJoinImplementation {
+    fn pattern(&self) -> &Pattern {
+        &EQUI_JOIN_PATTERN
+    }
+}
+
+impl<T: Transaction> ImplementationRule<T> for HashJoinImplementation {
+    fn to_expression(
+        &self,
+        op: &Operator,
+        loader: &ColumnMetaLoader<'_, T>,
+        group_expr: &mut GroupExpression,
+    ) -> Result<(), DatabaseError> {
+        if let Operator::Join(join_op) = op {
+            // building the hash table costs more than probing it
+            let cost = estimate_rows(join_op, loader)?
+                .map(|(left_rows, right_rows)| left_rows * 2 + right_rows);
+
+            group_expr.append_expr(Expression {
+                op: PhysicalOption::HashJoin,
+                cost,
+            });
+
+            Ok(())
+        } else {
+            unreachable!("invalid operator!")
+        }
+    }
+}
+
+/// Used for the theta join and the cross join, which cannot be executed by the hash join.
+#[derive(Clone)]
+pub struct NestedLoopJoinImplementation;
+
+impl MatchPattern for NestedLoopJoinImplementation {
+    fn pattern(&self) -> &Pattern {
+        &JOIN_PATTERN
+    }
+}
+
+impl<T: Transaction> ImplementationRule<T> for NestedLoopJoinImplementation {
+    fn to_expression(
+        &self,
+        op: &Operator,
+        loader: &ColumnMetaLoader<'_, T>,
+        group_expr: &mut GroupExpression,
+    ) -> Result<(), DatabaseError> {
+        if let Operator::Join(join_op) = op {
+            let cost = estimate_rows(join_op, loader)?
+                .map(|(left_rows, right_rows)| left_rows.saturating_mul(right_rows));
+
+            group_expr.append_expr(Expression {
+                op: PhysicalOption::NestedLoopJoin,
+                cost,
+            });
+
+            Ok(())
+        } else {
+            unreachable!("invalid operator!")
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SortMergeJoinImplementation;
+
+impl MatchPattern for SortMergeJoinImplementation {
+    fn pattern(&self) -> &Pattern {
+        &SORT_MERGE_JOIN_PATTERN
+    }
+}
+
+impl<T: Transaction> ImplementationRule<T> for SortMergeJoinImplementation {
+    fn to_expression(
+        &self,
+        op: &Operator,
+        loader: &ColumnMetaLoader<'_, T>,
+        group_expr: &mut GroupExpression,
+    ) -> Result<(), DatabaseError> {
+        if let Operator::Join(join_op) = op {
+            let keys = equi_keys(join_op);
+            // Tips: the tuples are scanned in the order of the primary key,
+            // so there is no need to sort the side joined by its primary key
+            let sort_cost = |rows: usize, is_ordered: bool| {
+                if is_ordered {
+                    0
+                } else {
+                    rows * (usize::BITS - rows.leading_zeros()) as usize
+                }
+            };
+            let cost = estimate_rows(join_op, loader)?.map(|(left_rows, right_rows)| {
+                sort_cost(left_rows, is_primary_key(&keys[0].0))
+                    + sort_cost(right_rows, is_primary_key(&keys[0].1))
+                    + left_rows
+                    + right_rows
+            });
+
+            group_expr.append_expr(Expression {
+                op: PhysicalOption::SortMergeJoin,
+                cost,
+            });
+
+            Ok(())
+        } else {
+            unreachable!("invalid operator!")
+        }
+    }
+}
+
+fn equi_keys(op: &JoinOperator) -> &[(ScalarExpression, ScalarExpression)] {
+    match &op.on {
+        JoinCondition::On { on, .. } => on,
+        JoinCondition::None => &[],
+    }
+}
+
+/// The tuples are sorted by the memcomparable encoding of the keys.
+fn is_sort_merge_joinable(op: &JoinOperator) -> bool {
+    let is_sortable = |expr: &ScalarExpression| {
+        !matches!(
+            expr.return_type(),
+            LogicalType::Invalid | LogicalType::Decimal(_, _) | LogicalType::Tuple
+        )
+    };
+    let keys = equi_keys(op);
+
+    !keys.is_empty()
+        && keys
+            .iter()
+            .all(|(left_expr, right_expr)| is_sortable(left_expr) && is_sortable(right_expr))
+}
+
+fn is_primary_key(expr: &ScalarExpression) -> bool {
+    key_column(expr).is_some_and(|column| column.desc().is_primary())
+}
+
+/// The column of the key, which has been replaced with `ScalarExpression::Reference`
+/// by the `ExpressionRemapper`.
+fn key_column(expr: &ScalarExpression) -> Option<&ColumnRef> {
+    match expr {
+        ScalarExpression::ColumnRef(column) => Some(column),
+        ScalarExpression::Reference { expr, .. }
+        | ScalarExpression::Alias { expr, .. }
+        | ScalarExpression::TypeCast { expr, .. } => key_column(expr),
+        _ => None,
+    }
+}
+
+/// Estimates the rows of both sides by the histograms of the columns of the join keys,
+/// none if either side has no statistics.
+fn estimate_rows<T: Transaction>(
+    op: &JoinOperator,
+    loader: &ColumnMetaLoader<'_, T>,
+) -> Result<Option<(usize, usize)>, DatabaseError> {
+    let side_rows = |exprs: Vec<&ScalarExpression>| -> Result<Option<usize>, DatabaseError> {
+        for column in exprs.into_iter().filter_map(key_column) {
+            if let (Some(table_name), Some(column_id)) = (column.table_name(), column.id()) {
+                let column_metas = loader.load(table_name.clone())?;
+
+                if let Some(column_meta) = find_column_meta(column_metas, &column_id) {
+                    return Ok(Some(column_meta.histogram().values_len()));
+                }
+            }
+        }
+        Ok(None)
+    };
+    let keys = equi_keys(op);
+    let left_rows = side_rows(keys.iter().map(|(left_expr, _)| left_expr).collect())?;
+    let right_rows = side_rows(keys.iter().map(|(_, right_expr)| right_expr).collect())?;
+
+    Ok(left_rows.zip(right_rows))
+}
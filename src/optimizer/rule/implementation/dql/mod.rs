@@ -1,9 +1,13 @@
 pub(crate) mod aggregate;
 pub(crate) mod dummy;
+pub(crate) mod except;
 pub(crate) mod filter;
+pub(crate) mod intersect;
 pub(crate) mod join;
 pub(crate) mod limit;
 pub(crate) mod projection;
 pub(crate) mod scan;
 pub(crate) mod sort;
+pub(crate) mod table_function;
 pub(crate) mod values;
+pub(crate) mod window;
@@ -1,12 +1,16 @@
 use crate::errors::DatabaseError;
+use crate::expression::simplify::ConstantBinary;
 use crate::optimizer::core::column_meta::{ColumnMeta, ColumnMetaLoader};
 use crate::optimizer::core::memo::{Expression, GroupExpression};
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
 use crate::planner::operator::{Operator, PhysicalOption};
 use crate::storage::Transaction;
+use crate::types::value::{DataValue, ValueRef};
 use crate::types::ColumnId;
+use itertools::Itertools;
 use lazy_static::lazy_static;
+use std::collections::Bound;
 
 lazy_static! {
     static ref SCAN_PATTERN: Pattern = {
@@ -37,7 +41,11 @@ impl<T: Transaction> ImplementationRule<T> for SeqScanImplementation {
             let column_metas = loader.load(scan_op.table_name.clone())?;
             let mut cost = None;
 
-            if let Some(column_meta) = find_column_meta(column_metas, &scan_op.primary_key) {
+            if let Some(column_meta) = scan_op
+                .primary_keys
+                .first()
+                .and_then(|column_id| find_column_meta(column_metas, column_id))
+            {
                 cost = Some(column_meta.histogram().values_len());
             }
 
@@ -77,12 +85,17 @@ impl<T: Transaction> ImplementationRule<T> for IndexScanImplementation {
                 let mut cost = None;
 
                 if let Some(binaries) = &index_info.binaries {
-                    // FIXME: Only UniqueIndex
+                    // Tips: the composite index is estimated by its leading column
                     if let Some(column_meta) =
                         find_column_meta(column_metas, &index_info.meta.column_ids[0])
                     {
+                        let count = if index_info.meta.column_ids.len() > 1 {
+                            column_meta.collect_count(&leading_binaries(binaries))
+                        } else {
+                            column_meta.collect_count(binaries)
+                        };
                         // need to return table query(non-covering index)
-                        cost = Some(column_meta.collect_count(binaries) * 2);
+                        cost = Some(count * 2);
                     }
                 }
                 if matches!(cost, Some(0)) {
@@ -102,7 +115,7 @@ impl<T: Transaction> ImplementationRule<T> for IndexScanImplementation {
     }
 }
 
-fn find_column_meta<'a>(
+pub(crate) fn find_column_meta<'a>(
     column_metas: &'a [ColumnMeta],
     column_id: &ColumnId,
 ) -> Option<&'a ColumnMeta> {
@@ -112,3 +125,38 @@ fn find_column_meta<'a>(
         .ok()
         .map(|i| &column_metas[i])
 }
+
+/// The binaries of the composite index use `DataValue::Tuple` for the leading values,
+/// and only the leading column has statistics.
+pub(crate) fn leading_binaries(binaries: &[ConstantBinary]) -> Vec<ConstantBinary> {
+    let leading_value = |value: &ValueRef| match value.as_ref() {
+        DataValue::Tuple(Some(values)) if !values.is_empty() => (values[0].clone(), values.len()),
+        _ => (value.clone(), 1),
+    };
+    let leading_bound = |bound: &Bound<ValueRef>| match bound {
+        Bound::Included(value) => Bound::Included(leading_value(value).0),
+        Bound::Excluded(value) => {
+            let (value, len) = leading_value(value);
+
+            // e.g. `(c1, c2) > (1, 2)` still contains `c1 = 1`
+            if len > 1 {
+                Bound::Included(value)
+            } else {
+                Bound::Excluded(value)
+            }
+        }
+        Bound::Unbounded => Bound::Unbounded,
+    };
+
+    binaries
+        .iter()
+        .map(|binary| match binary {
+            ConstantBinary::Scope { min, max } => ConstantBinary::Scope {
+                min: leading_bound(min),
+                max: leading_bound(max),
+            },
+            ConstantBinary::Eq(value) => ConstantBinary::Eq(leading_value(value).0),
+            binary => binary.clone(),
+        })
+        .collect_vec()
+}
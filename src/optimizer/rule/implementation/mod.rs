@@ -9,9 +9,16 @@ use crate::optimizer::core::memo::GroupExpression;
 use crate::optimizer::core::pattern::Pattern;
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
 use crate::optimizer::rule::implementation::ddl::add_column::AddColumnImplementation;
+use crate::optimizer::rule::implementation::ddl::alter_user::AlterUserImplementation;
+use crate::optimizer::rule::implementation::ddl::create_index::CreateIndexImplementation;
 use crate::optimizer::rule::implementation::ddl::create_table::CreateTableImplementation;
+use crate::optimizer::rule::implementation::ddl::create_user::CreateUserImplementation;
 use crate::optimizer::rule::implementation::ddl::drop_column::DropColumnImplementation;
+use crate::optimizer::rule::implementation::ddl::drop_index::DropIndexImplementation;
 use crate::optimizer::rule::implementation::ddl::drop_table::DropTableImplementation;
+use crate::optimizer::rule::implementation::ddl::drop_user::DropUserImplementation;
+use crate::optimizer::rule::implementation::ddl::grant::GrantImplementation;
+use crate::optimizer::rule::implementation::ddl::revoke::RevokeImplementation;
 use crate::optimizer::rule::implementation::ddl::truncate::TruncateImplementation;
 use crate::optimizer::rule::implementation::dml::analyze::AnalyzeImplementation;
 use crate::optimizer::rule::implementation::dml::copy_from_file::CopyFromFileImplementation;
@@ -23,15 +30,21 @@ use crate::optimizer::rule::implementation::dql::aggregate::{
     GroupByAggregateImplementation, SimpleAggregateImplementation,
 };
 use crate::optimizer::rule::implementation::dql::dummy::DummyImplementation;
+use crate::optimizer::rule::implementation::dql::except::HashExceptImplementation;
 use crate::optimizer::rule::implementation::dql::filter::FilterImplementation;
-use crate::optimizer::rule::implementation::dql::join::HashJoinImplementation;
+use crate::optimizer::rule::implementation::dql::intersect::HashIntersectImplementation;
+use crate::optimizer::rule::implementation::dql::join::{
+    HashJoinImplementation, NestedLoopJoinImplementation, SortMergeJoinImplementation,
+};
 use crate::optimizer::rule::implementation::dql::limit::LimitImplementation;
 use crate::optimizer::rule::implementation::dql::projection::ProjectionImplementation;
 use crate::optimizer::rule::implementation::dql::scan::{
     IndexScanImplementation, SeqScanImplementation,
 };
 use crate::optimizer::rule::implementation::dql::sort::SortImplementation;
+use crate::optimizer::rule::implementation::dql::table_function::TableFunctionImplementation;
 use crate::optimizer::rule::implementation::dql::values::ValuesImplementation;
+use crate::optimizer::rule::implementation::dql::window::WindowImplementation;
 use crate::planner::operator::Operator;
 use crate::storage::Transaction;
 
@@ -43,12 +56,18 @@ pub enum ImplementationRuleImpl {
     Dummy,
     Filter,
     HashJoin,
+    NestedLoopJoin,
+    SortMergeJoin,
+    HashIntersect,
+    HashExcept,
     Limit,
     Projection,
     SeqScan,
     IndexScan,
     Sort,
+    TableFunction,
     Values,
+    Window,
     // DML
     Analyze,
     CopyFromFile,
@@ -58,8 +77,15 @@ pub enum ImplementationRuleImpl {
     Update,
     // DDL
     AddColumn,
+    CreateIndex,
     CreateTable,
     DropColumn,
+    DropIndex,
+    CreateUser,
+    AlterUser,
+    DropUser,
+    Grant,
+    Revoke,
     DropTable,
     Truncate,
 }
@@ -72,20 +98,33 @@ impl MatchPattern for ImplementationRuleImpl {
             ImplementationRuleImpl::Dummy => DummyImplementation.pattern(),
             ImplementationRuleImpl::Filter => FilterImplementation.pattern(),
             ImplementationRuleImpl::HashJoin => HashJoinImplementation.pattern(),
+            ImplementationRuleImpl::NestedLoopJoin => NestedLoopJoinImplementation.pattern(),
+            ImplementationRuleImpl::SortMergeJoin => SortMergeJoinImplementation.pattern(),
+            ImplementationRuleImpl::HashIntersect => HashIntersectImplementation.pattern(),
+            ImplementationRuleImpl::HashExcept => HashExceptImplementation.pattern(),
             ImplementationRuleImpl::Limit => LimitImplementation.pattern(),
             ImplementationRuleImpl::Projection => ProjectionImplementation.pattern(),
             ImplementationRuleImpl::SeqScan => SeqScanImplementation.pattern(),
             ImplementationRuleImpl::IndexScan => IndexScanImplementation.pattern(),
             ImplementationRuleImpl::Sort => SortImplementation.pattern(),
+            ImplementationRuleImpl::TableFunction => TableFunctionImplementation.pattern(),
             ImplementationRuleImpl::Values => ValuesImplementation.pattern(),
+            ImplementationRuleImpl::Window => WindowImplementation.pattern(),
             ImplementationRuleImpl::CopyFromFile => CopyFromFileImplementation.pattern(),
             ImplementationRuleImpl::CopyToFile => CopyToFileImplementation.pattern(),
             ImplementationRuleImpl::Delete => DeleteImplementation.pattern(),
             ImplementationRuleImpl::Insert => InsertImplementation.pattern(),
             ImplementationRuleImpl::Update => UpdateImplementation.pattern(),
             ImplementationRuleImpl::AddColumn => AddColumnImplementation.pattern(),
+            ImplementationRuleImpl::CreateIndex => CreateIndexImplementation.pattern(),
             ImplementationRuleImpl::CreateTable => CreateTableImplementation.pattern(),
             ImplementationRuleImpl::DropColumn => DropColumnImplementation.pattern(),
+            ImplementationRuleImpl::DropIndex => DropIndexImplementation.pattern(),
+            ImplementationRuleImpl::CreateUser => CreateUserImplementation.pattern(),
+            ImplementationRuleImpl::AlterUser => AlterUserImplementation.pattern(),
+            ImplementationRuleImpl::DropUser => DropUserImplementation.pattern(),
+            ImplementationRuleImpl::Grant => GrantImplementation.pattern(),
+            ImplementationRuleImpl::Revoke => RevokeImplementation.pattern(),
             ImplementationRuleImpl::DropTable => DropTableImplementation.pattern(),
             ImplementationRuleImpl::Truncate => TruncateImplementation.pattern(),
             ImplementationRuleImpl::Analyze => AnalyzeImplementation.pattern(),
@@ -116,6 +155,18 @@ impl<T: Transaction> ImplementationRule<T> for ImplementationRuleImpl {
             ImplementationRuleImpl::HashJoin => {
                 HashJoinImplementation.to_expression(operator, loader, group_expr)?
             }
+            ImplementationRuleImpl::NestedLoopJoin => {
+                NestedLoopJoinImplementation.to_expression(operator, loader, group_expr)?
+            }
+            ImplementationRuleImpl::SortMergeJoin => {
+                SortMergeJoinImplementation.to_expression(operator, loader, group_expr)?
+            }
+            ImplementationRuleImpl::HashIntersect => {
+                HashIntersectImplementation.to_expression(operator, loader, group_expr)?
+            }
+            ImplementationRuleImpl::HashExcept => {
+                HashExceptImplementation.to_expression(operator, loader, group_expr)?
+            }
             ImplementationRuleImpl::Limit => {
                 LimitImplementation.to_expression(operator, loader, group_expr)?
             }
@@ -131,9 +182,15 @@ impl<T: Transaction> ImplementationRule<T> for ImplementationRuleImpl {
             ImplementationRuleImpl::Sort => {
                 SortImplementation.to_expression(operator, loader, group_expr)?
             }
+            ImplementationRuleImpl::TableFunction => {
+                TableFunctionImplementation.to_expression(operator, loader, group_expr)?
+            }
             ImplementationRuleImpl::Values => {
                 ValuesImplementation.to_expression(operator, loader, group_expr)?
             }
+            ImplementationRuleImpl::Window => {
+                WindowImplementation.to_expression(operator, loader, group_expr)?
+            }
             ImplementationRuleImpl::CopyFromFile => {
                 CopyFromFileImplementation.to_expression(operator, loader, group_expr)?
             }
@@ -152,12 +209,33 @@ impl<T: Transaction> ImplementationRule<T> for ImplementationRuleImpl {
             ImplementationRuleImpl::AddColumn => {
                 AddColumnImplementation.to_expression(operator, loader, group_expr)?
             }
+            ImplementationRuleImpl::CreateIndex => {
+                CreateIndexImplementation.to_expression(operator, loader, group_expr)?
+            }
             ImplementationRuleImpl::CreateTable => {
                 CreateTableImplementation.to_expression(operator, loader, group_expr)?
             }
             ImplementationRuleImpl::DropColumn => {
                 DropColumnImplementation.to_expression(operator, loader, group_expr)?
             }
+            ImplementationRuleImpl::DropIndex => {
+                DropIndexImplementation.to_expression(operator, loader, group_expr)?
+            }
+            ImplementationRuleImpl::CreateUser => {
+                CreateUserImplementation.to_expression(operator, loader, group_expr)?
+            }
+            ImplementationRuleImpl::AlterUser => {
+                AlterUserImplementation.to_expression(operator, loader, group_expr)?
+            }
+            ImplementationRuleImpl::DropUser => {
+                DropUserImplementation.to_expression(operator, loader, group_expr)?
+            }
+            ImplementationRuleImpl::Grant => {
+                GrantImplementation.to_expression(operator, loader, group_expr)?
+            }
+            ImplementationRuleImpl::Revoke => {
+                RevokeImplementation.to_expression(operator, loader, group_expr)?
+            }
             ImplementationRuleImpl::DropTable => {
                 DropTableImplementation.to_expression(operator, loader, group_expr)?
             }
@@ -2,10 +2,12 @@ use crate::catalog::ColumnSummary;
 use crate::errors::DatabaseError;
 use crate::expression::agg::AggKind;
 use crate::expression::ScalarExpression;
+use crate::optimizer::core::column_meta::ColumnMetaLoader;
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
 use crate::planner::operator::Operator;
+use crate::storage::Transaction;
 use crate::types::value::DataValue;
 use crate::types::LogicalType;
 use itertools::Itertools;
@@ -102,11 +104,29 @@ impl ColumnPruning {
                         .retain(|(_, column)| column_references.contains(column.summary()));
                 }
             }
+            Operator::Window(op) => {
+                if !all_referenced {
+                    Self::clear_exprs(&column_references, &mut op.window_calls);
+                }
+                let temp_columns = operator.referenced_columns(false);
+                let mut column_references = column_references;
+                for column in temp_columns.iter() {
+                    column_references.insert(column.summary());
+                }
+                for child_id in graph.children_at(node_id).collect_vec() {
+                    let copy_references = column_references.clone();
+
+                    Self::_apply(copy_references, all_referenced, child_id, graph);
+                }
+            }
             Operator::Sort(_)
             | Operator::Limit(_)
             | Operator::Join(_)
             | Operator::Filter(_)
-            | Operator::Union(_) => {
+            | Operator::Union(_)
+            | Operator::Intersect(_)
+            | Operator::Except(_)
+            | Operator::RecursiveCte(_) => {
                 let temp_columns = operator.referenced_columns(false);
                 // why?
                 let mut column_references = column_references;
@@ -120,7 +140,7 @@ impl ColumnPruning {
                 }
             }
             // Last Operator
-            Operator::Dummy | Operator::Values(_) => (),
+            Operator::Dummy | Operator::Values(_) | Operator::TableFunction(_) => (),
             Operator::Explain => {
                 if let Some(child_id) = graph.eldest_child_at(node_id) {
                     Self::_apply(column_references, true, child_id, graph);
@@ -145,6 +165,13 @@ impl ColumnPruning {
             // DDL Single Plan
             Operator::CreateTable(_)
             | Operator::DropTable(_)
+            | Operator::CreateIndex(_)
+            | Operator::DropIndex(_)
+            | Operator::CreateUser(_)
+            | Operator::AlterUser(_)
+            | Operator::DropUser(_)
+            | Operator::Grant(_)
+            | Operator::Revoke(_)
             | Operator::Truncate(_)
             | Operator::Show
             | Operator::CopyFromFile(_)
@@ -175,8 +202,13 @@ impl MatchPattern for ColumnPruning {
     }
 }
 
-impl NormalizationRule for ColumnPruning {
-    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+impl<T: Transaction> NormalizationRule<T> for ColumnPruning {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        _: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
         Self::_apply(HashSet::new(), true, node_id, graph);
         // mark changed to skip this rule batch
         graph.version += 1;
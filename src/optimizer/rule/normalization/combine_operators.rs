@@ -1,11 +1,14 @@
 use crate::errors::DatabaseError;
 use crate::expression::{BinaryOperator, ScalarExpression};
+use crate::optimizer::core::column_meta::ColumnMetaLoader;
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
 use crate::optimizer::rule::normalization::is_subset_exprs;
 use crate::planner::operator::Operator;
+use crate::storage::Transaction;
 use crate::types::LogicalType;
+use itertools::Itertools;
 use lazy_static::lazy_static;
 use std::collections::HashSet;
 
@@ -54,14 +57,39 @@ impl MatchPattern for CollapseProject {
     }
 }
 
-impl NormalizationRule for CollapseProject {
-    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+impl CollapseProject {
+    /// Whether the parent is computed over the outputs of the child, e.g. the columns of a CTE,
+    /// in which case both projects are kept.
+    fn is_over_outputs(exprs: &[ScalarExpression], child_exprs: &[ScalarExpression]) -> bool {
+        let child_outputs = child_exprs
+            .iter()
+            .map(|expr| expr.output_column())
+            .collect_vec();
+
+        exprs
+            .iter()
+            .flat_map(|expr| expr.referenced_columns(true))
+            .all(|column| {
+                child_outputs
+                    .iter()
+                    .any(|output| output.summary() == column.summary())
+            })
+    }
+}
+
+impl<T: Transaction> NormalizationRule<T> for CollapseProject {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        _: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
         if let Operator::Project(op) = graph.operator(node_id) {
             if let Some(child_id) = graph.eldest_child_at(node_id) {
                 if let Operator::Project(child_op) = graph.operator(child_id) {
                     if is_subset_exprs(&op.exprs, &child_op.exprs) {
                         graph.remove_node(child_id, false);
-                    } else {
+                    } else if !Self::is_over_outputs(&op.exprs, &child_op.exprs) {
                         graph.remove_node(node_id, false);
                     }
                 }
@@ -81,8 +109,13 @@ impl MatchPattern for CombineFilter {
     }
 }
 
-impl NormalizationRule for CombineFilter {
-    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+impl<T: Transaction> NormalizationRule<T> for CombineFilter {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        _: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
         if let Operator::Filter(op) = graph.operator(node_id).clone() {
             if let Some(child_id) = graph.eldest_child_at(node_id) {
                 if let Operator::Filter(child_op) = graph.operator_mut(child_id) {
@@ -111,8 +144,13 @@ impl MatchPattern for CollapseGroupByAgg {
     }
 }
 
-impl NormalizationRule for CollapseGroupByAgg {
-    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+impl<T: Transaction> NormalizationRule<T> for CollapseGroupByAgg {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        _: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
         if let Operator::Aggregate(op) = graph.operator(node_id).clone() {
             // if it is an aggregation operator containing agg_call
             if !op.agg_calls.is_empty() {
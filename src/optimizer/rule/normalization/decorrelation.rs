@@ -0,0 +1,403 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::agg::AggKind;
+use crate::expression::{BinaryOperator, ScalarExpression};
+use crate::optimizer::core::column_meta::ColumnMetaLoader;
+use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
+use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
+use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::optimizer::rule::normalization::pushdown_predicates::{
+    reduce_filters, split_conjunctive_predicates,
+};
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+use crate::planner::operator::Operator;
+use crate::storage::Transaction;
+use crate::types::LogicalType;
+use itertools::Itertools;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref PULL_UP_CORRELATED_PREDICATES_RULE: Pattern = {
+        Pattern {
+            predicate: |op| matches!(op, Operator::Join(_)),
+            children: PatternChildrenPredicate::None,
+        }
+    };
+}
+
+/// Decorrelates the subquery on the right side of the join, by pulling up the predicates that
+/// reference the columns of the left side into the condition of the join.
+///
+/// The predicates are pulled up through `Filter`, `Project`, `Sort` and the inner joins,
+/// and through `Aggregate` only if they are equalities, whose columns of the subquery are grouped by.
+/// e.g. `SELECT * FROM t1 WHERE EXISTS (SELECT * FROM t2 WHERE t2.c1 = t1.c1)`
+/// ```text
+/// LeftSemi Join                           LeftSemi Join On t1.c1 = t2.c1
+///   Scan t1                        =>       Scan t1
+///   Project [*]                             Project [*, t2.c1]
+///     Filter t2.c1 = t1.c1                    Scan t2
+///       Scan t2
+/// ```
+pub struct PullUpCorrelatedPredicates;
+
+impl MatchPattern for PullUpCorrelatedPredicates {
+    fn pattern(&self) -> &Pattern {
+        &PULL_UP_CORRELATED_PREDICATES_RULE
+    }
+}
+
+impl<T: Transaction> NormalizationRule<T> for PullUpCorrelatedPredicates {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        _: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
+        let Operator::Join(join_op) = graph.operator(node_id).clone() else {
+            return Ok(());
+        };
+        let &[left_id, right_id] = graph.children_at(node_id).collect_vec().as_slice() else {
+            return Ok(());
+        };
+        let right_columns = Self::output_columns(graph, right_id);
+        // the columns of the left side, which are referenced by the right side only if it is correlated
+        let outer_columns = Self::output_columns(graph, left_id)
+            .into_iter()
+            .filter(|column| !Self::contains(&right_columns, column))
+            .collect_vec();
+        if outer_columns.is_empty() {
+            return Ok(());
+        }
+        let predicates = Self::pull_up(graph, right_id, &outer_columns, join_op.join_type)?;
+
+        if Self::is_correlated(graph, right_id, &outer_columns) {
+            return Err(DatabaseError::UnsupportedStmt(
+                "the correlated subquery that cannot be decorrelated".to_string(),
+            ));
+        }
+        if predicates.is_empty() {
+            return Ok(());
+        }
+        let (mut on_keys, mut filters) = match join_op.on {
+            JoinCondition::On { on, filter } => (on, filter.into_iter().collect_vec()),
+            JoinCondition::None => (vec![], vec![]),
+        };
+        for predicate in predicates {
+            match Self::split_equality(&predicate, &outer_columns) {
+                Some((mut outer_expr, mut inner_expr)) => {
+                    let ty = LogicalType::max_logical_type(
+                        &outer_expr.return_type(),
+                        &inner_expr.return_type(),
+                    )?;
+                    // the keys are hashed, so that they must be of the same type
+                    for expr in [&mut outer_expr, &mut inner_expr] {
+                        if expr.return_type() != ty {
+                            *expr = ScalarExpression::TypeCast {
+                                expr: Box::new(expr.clone()),
+                                ty,
+                            };
+                        }
+                    }
+                    on_keys.push((outer_expr, inner_expr));
+                }
+                None => filters.push(predicate),
+            }
+        }
+        let join_type = match join_op.join_type {
+            JoinType::Cross => JoinType::Inner,
+            join_type => join_type,
+        };
+
+        graph.replace_node(
+            node_id,
+            Operator::Join(JoinOperator {
+                on: JoinCondition::On {
+                    on: on_keys,
+                    filter: reduce_filters(filters, false).map(|op| op.predicate),
+                },
+                join_type,
+            }),
+        );
+
+        Ok(())
+    }
+}
+
+impl PullUpCorrelatedPredicates {
+    /// Pulls up the correlated predicates of the subtree, and then the subtree outputs the columns
+    /// referenced by these predicates.
+    fn pull_up(
+        graph: &mut HepGraph,
+        node_id: HepNodeId,
+        outer_columns: &[ColumnRef],
+        join_type: JoinType,
+    ) -> Result<Vec<ScalarExpression>, DatabaseError> {
+        let fn_is_correlated = |expr: &ScalarExpression| {
+            expr.referenced_columns(true)
+                .iter()
+                .any(|column| Self::contains(outer_columns, column))
+        };
+
+        match graph.operator(node_id).clone() {
+            Operator::Filter(mut op) => {
+                let mut predicates = match graph.eldest_child_at(node_id) {
+                    Some(child_id) => Self::pull_up(graph, child_id, outer_columns, join_type)?,
+                    None => vec![],
+                };
+                let (correlated_predicates, predicates_left): (Vec<_>, Vec<_>) =
+                    split_conjunctive_predicates(&op.predicate)
+                        .into_iter()
+                        .partition(fn_is_correlated);
+
+                if !correlated_predicates.is_empty() {
+                    match reduce_filters(predicates_left, op.having) {
+                        Some(filter_op) => {
+                            op.predicate = filter_op.predicate;
+                            graph.replace_node(node_id, Operator::Filter(op));
+                        }
+                        None => {
+                            graph.remove_node(node_id, false);
+                        }
+                    }
+                    predicates.extend(correlated_predicates);
+                }
+                Ok(predicates)
+            }
+            Operator::Project(mut op) => {
+                let predicates = match graph.eldest_child_at(node_id) {
+                    Some(child_id) => Self::pull_up(graph, child_id, outer_columns, join_type)?,
+                    None => vec![],
+                };
+                let mut is_changed = false;
+
+                for column in predicates
+                    .iter()
+                    .flat_map(|expr| expr.referenced_columns(true))
+                {
+                    if Self::contains(outer_columns, &column)
+                        || op
+                            .exprs
+                            .iter()
+                            .any(|expr| expr.output_column().summary() == column.summary())
+                    {
+                        continue;
+                    }
+                    op.exprs.push(ScalarExpression::ColumnRef(column));
+                    is_changed = true;
+                }
+                if is_changed {
+                    graph.replace_node(node_id, Operator::Project(op));
+                }
+                Ok(predicates)
+            }
+            Operator::Aggregate(mut op) => {
+                let predicates = match graph.eldest_child_at(node_id) {
+                    Some(child_id) => Self::pull_up(graph, child_id, outer_columns, join_type)?,
+                    None => vec![],
+                };
+                if predicates.is_empty() {
+                    return Ok(predicates);
+                }
+                // the aggregation without GROUP BY outputs a row even if there are no tuples,
+                // which is the same as no row only if the row is compared with the inner join,
+                // and the result of `COUNT` is not NULL
+                if op.groupby_exprs.is_empty()
+                    && (join_type != JoinType::Inner
+                        || op.agg_calls.iter().any(|expr| {
+                            matches!(
+                                expr,
+                                ScalarExpression::AggCall {
+                                    kind: AggKind::Count,
+                                    ..
+                                }
+                            )
+                        }))
+                {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "the correlated subquery with the aggregation".to_string(),
+                    ));
+                }
+                for predicate in predicates.iter() {
+                    match Self::split_equality(predicate, outer_columns) {
+                        Some((_, inner_expr @ ScalarExpression::ColumnRef(_))) => {
+                            if !op.groupby_exprs.contains(&inner_expr) {
+                                op.groupby_exprs.push(inner_expr);
+                            }
+                        }
+                        _ => {
+                            return Err(DatabaseError::UnsupportedStmt(format!(
+                                "the correlated predicate through the aggregation: {}",
+                                predicate
+                            )))
+                        }
+                    }
+                }
+                graph.replace_node(node_id, Operator::Aggregate(op));
+
+                Ok(predicates)
+            }
+            Operator::Sort(_) => match graph.eldest_child_at(node_id) {
+                Some(child_id) => Self::pull_up(graph, child_id, outer_columns, join_type),
+                None => Ok(vec![]),
+            },
+            Operator::Join(op) => {
+                let child_ids = graph.children_at(node_id).collect_vec();
+                let child_ids = match op.join_type {
+                    JoinType::Inner | JoinType::Cross => &child_ids[..],
+                    JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti => &child_ids[..1],
+                    JoinType::RightSemi | JoinType::RightAnti => &child_ids[1..],
+                    JoinType::Right | JoinType::Full => &[],
+                };
+                let mut predicates = Vec::new();
+
+                for child_id in child_ids {
+                    predicates.append(&mut Self::pull_up(
+                        graph,
+                        *child_id,
+                        outer_columns,
+                        join_type,
+                    )?);
+                }
+                Ok(predicates)
+            }
+            _ => Ok(vec![]),
+        }
+    }
+
+    /// Splits the equality into the expression of the outer columns and the expression of the
+    /// columns of the subquery.
+    fn split_equality(
+        expr: &ScalarExpression,
+        outer_columns: &[ColumnRef],
+    ) -> Option<(ScalarExpression, ScalarExpression)> {
+        let ScalarExpression::Binary {
+            op: BinaryOperator::Eq,
+            left_expr,
+            right_expr,
+            ..
+        } = expr
+        else {
+            return None;
+        };
+        let fn_is_outer = |expr: &ScalarExpression| {
+            let columns = expr.referenced_columns(true);
+
+            !columns.is_empty()
+                && columns
+                    .iter()
+                    .all(|column| Self::contains(outer_columns, column))
+        };
+        let fn_is_inner = |expr: &ScalarExpression| {
+            let columns = expr.referenced_columns(true);
+
+            !columns.is_empty()
+                && !columns
+                    .iter()
+                    .any(|column| Self::contains(outer_columns, column))
+        };
+
+        if fn_is_outer(left_expr) && fn_is_inner(right_expr) {
+            Some((*left_expr.clone(), *right_expr.clone()))
+        } else if fn_is_inner(left_expr) && fn_is_outer(right_expr) {
+            Some((*right_expr.clone(), *left_expr.clone()))
+        } else {
+            None
+        }
+    }
+
+    /// The columns produced by the subtree, e.g. the columns of the tables and the results of expressions.
+    pub(crate) fn output_columns(graph: &HepGraph, node_id: HepNodeId) -> Vec<ColumnRef> {
+        let operator = graph.operator(node_id);
+        let mut columns = match operator {
+            Operator::Scan(_)
+            | Operator::Values(_)
+            | Operator::TableFunction(_)
+            | Operator::Union(_)
+            | Operator::Intersect(_)
+            | Operator::Except(_)
+            | Operator::RecursiveCte(_) => operator.referenced_columns(true),
+            Operator::Project(_) | Operator::Aggregate(_) => operator
+                .output_exprs()
+                .unwrap_or_default()
+                .iter()
+                .filter(|expr| !matches!(expr, ScalarExpression::ColumnRef(_)))
+                .map(ScalarExpression::output_column)
+                .collect_vec(),
+            Operator::Window(op) => op
+                .window_calls
+                .iter()
+                .map(ScalarExpression::output_column)
+                .collect_vec(),
+            _ => vec![],
+        };
+        for child_id in graph.children_at(node_id) {
+            columns.append(&mut Self::output_columns(graph, child_id));
+        }
+        columns
+    }
+
+    fn is_correlated(graph: &HepGraph, node_id: HepNodeId, outer_columns: &[ColumnRef]) -> bool {
+        graph
+            .operator(node_id)
+            .referenced_columns(true)
+            .iter()
+            .any(|column| Self::contains(outer_columns, column))
+            || graph
+                .children_at(node_id)
+                .any(|child_id| Self::is_correlated(graph, child_id, outer_columns))
+    }
+
+    fn contains(columns: &[ColumnRef], column: &ColumnRef) -> bool {
+        columns.iter().any(|c| c.summary() == column.summary())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binder::test::select_sql_run;
+    use crate::errors::DatabaseError;
+    use crate::optimizer::heuristic::batch::HepBatchStrategy;
+    use crate::optimizer::heuristic::optimizer::HepOptimizer;
+    use crate::optimizer::rule::normalization::NormalizationRuleImpl;
+    use crate::planner::operator::join::{JoinCondition, JoinType};
+    use crate::planner::operator::Operator;
+    use crate::storage::kip::KipTransaction;
+
+    #[tokio::test]
+    async fn test_pull_up_correlated_predicates() -> Result<(), DatabaseError> {
+        let plan = select_sql_run(
+            "select * from t1 where exists (select * from t2 where c3 = c1 and c4 > 1)",
+        )
+        .await?;
+
+        let best_plan = HepOptimizer::new(plan)
+            .batch(
+                "test_pull_up_correlated_predicates".to_string(),
+                HepBatchStrategy::fix_point_topdown(10),
+                vec![NormalizationRuleImpl::PullUpCorrelatedPredicates],
+            )
+            .find_best::<KipTransaction>(None)?;
+
+        let join_plan = &best_plan.childrens[0];
+        match &join_plan.operator {
+            Operator::Join(op) => {
+                assert_eq!(op.join_type, JoinType::LeftSemi);
+                match &op.on {
+                    JoinCondition::On { on, filter } => {
+                        assert_eq!(on.len(), 1);
+                        assert!(filter.is_none());
+                    }
+                    JoinCondition::None => unreachable!("Should be a on condition"),
+                }
+            }
+            _ => unreachable!("Should be a join operator"),
+        }
+        // the filter of the subquery only keeps the uncorrelated predicate
+        match &join_plan.childrens[1].childrens[0].operator {
+            Operator::Filter(op) => assert_eq!(op.predicate.referenced_columns(true).len(), 1),
+            _ => unreachable!("Should be a filter operator"),
+        }
+
+        Ok(())
+    }
+}
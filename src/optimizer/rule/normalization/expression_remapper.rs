@@ -1,10 +1,12 @@
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
+use crate::optimizer::core::column_meta::ColumnMetaLoader;
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
-use crate::planner::operator::join::JoinCondition;
+use crate::planner::operator::join::{JoinCondition, JoinType};
 use crate::planner::operator::Operator;
+use crate::storage::Transaction;
 use lazy_static::lazy_static;
 
 lazy_static! {
@@ -54,6 +56,14 @@ impl ExpressionRemapper {
                     }
                     JoinCondition::None => {}
                 }
+                // only the tuples of one side are output by the semi join and the anti join
+                match op.join_type {
+                    JoinType::LeftSemi | JoinType::LeftAnti => output_exprs.truncate(left_len),
+                    JoinType::RightSemi | JoinType::RightAnti => {
+                        output_exprs.drain(..left_len);
+                    }
+                    _ => (),
+                }
 
                 return Ok(());
             }
@@ -75,10 +85,18 @@ impl ExpressionRemapper {
                     sort_field.expr.try_reference(output_exprs);
                 }
             }
+            Operator::Window(op) => {
+                for expr in op.window_calls.iter_mut() {
+                    expr.try_reference(output_exprs);
+                }
+                // the results of the window functions follow the outputs of the child
+                output_exprs.extend(op.window_calls.iter().cloned());
+            }
             Operator::Dummy
             | Operator::Scan(_)
             | Operator::Limit(_)
             | Operator::Values(_)
+            | Operator::TableFunction(_)
             | Operator::Show
             | Operator::Explain
             | Operator::Describe(_)
@@ -90,10 +108,20 @@ impl ExpressionRemapper {
             | Operator::DropColumn(_)
             | Operator::CreateTable(_)
             | Operator::DropTable(_)
+            | Operator::CreateIndex(_)
+            | Operator::DropIndex(_)
+            | Operator::CreateUser(_)
+            | Operator::AlterUser(_)
+            | Operator::DropUser(_)
+            | Operator::Grant(_)
+            | Operator::Revoke(_)
             | Operator::Truncate(_)
             | Operator::CopyFromFile(_)
             | Operator::CopyToFile(_)
-            | Operator::Union(_) => (),
+            | Operator::Union(_)
+            | Operator::Intersect(_)
+            | Operator::Except(_)
+            | Operator::RecursiveCte(_) => (),
         }
         if let Some(exprs) = operator.output_exprs() {
             *output_exprs = exprs;
@@ -109,8 +137,13 @@ impl MatchPattern for ExpressionRemapper {
     }
 }
 
-impl NormalizationRule for ExpressionRemapper {
-    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+impl<T: Transaction> NormalizationRule<T> for ExpressionRemapper {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        _: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
         Self::_apply(&mut Vec::new(), node_id, graph)?;
         // mark changed to skip this rule batch
         graph.version += 1;
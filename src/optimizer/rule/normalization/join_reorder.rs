@@ -0,0 +1,648 @@
+use crate::catalog::{ColumnRef, TableName};
+use crate::errors::DatabaseError;
+use crate::expression::{BinaryOperator, ScalarExpression};
+use crate::optimizer::core::column_meta::{ColumnMeta, ColumnMetaLoader};
+use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
+use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
+use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::optimizer::rule::implementation::dql::scan::{find_column_meta, leading_binaries};
+use crate::optimizer::rule::normalization::decorrelation::PullUpCorrelatedPredicates;
+use crate::optimizer::rule::normalization::pushdown_predicates::{
+    reduce_filters, split_conjunctive_predicates,
+};
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+use crate::planner::operator::Operator;
+use crate::storage::Transaction;
+use crate::types::LogicalType;
+use itertools::Itertools;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref JOIN_REORDER_RULE: Pattern = {
+        Pattern {
+            predicate: |op| matches!(op, Operator::Join(op) if is_reorderable(op)),
+            children: PatternChildrenPredicate::None,
+        }
+    };
+}
+
+/// The join trees with more relations are reordered greedily instead of enumerating all the subsets.
+const DP_THRESHOLD: usize = 10;
+/// The selectivity of the predicates which cannot be estimated by the statistics.
+const DEFAULT_SELECTIVITY: f64 = 1.0 / 3.0;
+
+fn is_reorderable(op: &JoinOperator) -> bool {
+    matches!(op.join_type, JoinType::Inner | JoinType::Cross)
+}
+
+/// Reorders the trees of the inner joins and the cross joins by the rows estimated from the statistics.
+///
+/// The rows of each relation are estimated by the histogram and the count-min sketch of its table,
+/// and the rows of the joins are estimated by the distinct values of their keys.
+/// The order with the least intermediate rows is enumerated by the dynamic programming,
+/// or greedily if there are too many relations.
+///
+/// Tips: the join tree is kept as it is if any of its relations is not a table with statistics
+pub struct JoinReorder;
+
+impl MatchPattern for JoinReorder {
+    fn pattern(&self) -> &Pattern {
+        &JOIN_REORDER_RULE
+    }
+}
+
+impl<T: Transaction> NormalizationRule<T> for JoinReorder {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        loader: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
+        // Tips: the joins are kept in the written order without the statistics
+        let Some(loader) = loader else {
+            return Ok(());
+        };
+        // only the root of the join tree is reordered
+        if let Some(parent_id) = graph.parent_id(node_id) {
+            if matches!(graph.operator(parent_id), Operator::Join(op) if is_reorderable(op)) {
+                return Ok(());
+            }
+        }
+        let join_graph = match JoinGraph::new(graph, node_id, loader)? {
+            Some(join_graph) => join_graph,
+            None => return Ok(()),
+        };
+
+        for relation in join_graph.relations.iter() {
+            let _ = graph
+                .estimated_rows
+                .insert(relation.node_id, relation.rows as usize);
+        }
+        let best_tree = join_graph.best_tree();
+
+        if join_graph.cost(&best_tree) < join_graph.cost(&join_graph.tree) {
+            let mut join_ids = join_graph.join_ids.clone();
+            let mut is_placed = vec![false; join_graph.predicates.len()];
+            let root_id = join_ids.remove(0);
+
+            let _ = join_graph.rebuild(
+                graph,
+                &best_tree,
+                Some(root_id),
+                &mut join_ids,
+                &mut is_placed,
+            );
+        } else {
+            join_graph.record_rows(graph, &join_graph.tree, &mut join_graph.join_ids.iter());
+        }
+
+        Ok(())
+    }
+}
+
+struct Relation<'a> {
+    node_id: HepNodeId,
+    table_name: TableName,
+    column_metas: &'a [ColumnMeta],
+    columns: Vec<ColumnRef>,
+    rows: f64,
+}
+
+enum Predicate {
+    Equi {
+        left_expr: ScalarExpression,
+        right_expr: ScalarExpression,
+        left_mask: usize,
+        right_mask: usize,
+    },
+    Other {
+        expr: ScalarExpression,
+        mask: usize,
+    },
+}
+
+impl Predicate {
+    fn mask(&self) -> usize {
+        match self {
+            Predicate::Equi {
+                left_mask,
+                right_mask,
+                ..
+            } => left_mask | right_mask,
+            Predicate::Other { mask, .. } => *mask,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum JoinTree {
+    Relation(usize),
+    Join(Box<JoinTree>, Box<JoinTree>),
+}
+
+impl JoinTree {
+    /// The bits of the relations in the tree
+    fn mask(&self) -> usize {
+        match self {
+            JoinTree::Relation(i) => 1 << i,
+            JoinTree::Join(left, right) => left.mask() | right.mask(),
+        }
+    }
+}
+
+struct JoinGraph<'a> {
+    relations: Vec<Relation<'a>>,
+    predicates: Vec<Predicate>,
+    /// The ids of the joins in the pre-order of the current tree
+    join_ids: Vec<HepNodeId>,
+    tree: JoinTree,
+}
+
+impl<'a> JoinGraph<'a> {
+    fn new<T: Transaction>(
+        graph: &HepGraph,
+        root_id: HepNodeId,
+        loader: &'a ColumnMetaLoader<'_, T>,
+    ) -> Result<Option<JoinGraph<'a>>, DatabaseError> {
+        let mut relations = Vec::new();
+        let mut join_ids = Vec::new();
+        let mut equi_pairs = Vec::new();
+        let mut filters = Vec::new();
+
+        let tree = match Self::collect(
+            graph,
+            root_id,
+            loader,
+            &mut relations,
+            &mut join_ids,
+            &mut equi_pairs,
+            &mut filters,
+        )? {
+            Some(tree) => tree,
+            None => return Ok(None),
+        };
+        if relations.len() >= usize::BITS as usize {
+            return Ok(None);
+        }
+        let mut join_graph = JoinGraph {
+            relations,
+            predicates: Vec::with_capacity(equi_pairs.len() + filters.len()),
+            join_ids,
+            tree,
+        };
+        for (left_expr, right_expr) in equi_pairs {
+            let (left_mask, right_mask) = match (
+                join_graph.expr_mask(&left_expr),
+                join_graph.expr_mask(&right_expr),
+            ) {
+                (Some(left_mask), Some(right_mask)) => (left_mask, right_mask),
+                _ => return Ok(None),
+            };
+            join_graph.predicates.push(Predicate::Equi {
+                left_expr,
+                right_expr,
+                left_mask,
+                right_mask,
+            });
+        }
+        for expr in filters {
+            let mask = match join_graph.expr_mask(&expr) {
+                Some(mask) => mask,
+                None => return Ok(None),
+            };
+            join_graph.predicates.push(Predicate::Other { expr, mask });
+        }
+
+        Ok(Some(join_graph))
+    }
+
+    fn collect<T: Transaction>(
+        graph: &HepGraph,
+        node_id: HepNodeId,
+        loader: &'a ColumnMetaLoader<'_, T>,
+        relations: &mut Vec<Relation<'a>>,
+        join_ids: &mut Vec<HepNodeId>,
+        equi_pairs: &mut Vec<(ScalarExpression, ScalarExpression)>,
+        filters: &mut Vec<ScalarExpression>,
+    ) -> Result<Option<JoinTree>, DatabaseError> {
+        if let Operator::Join(op) = graph.operator(node_id) {
+            if is_reorderable(op) {
+                join_ids.push(node_id);
+
+                if let JoinCondition::On { on, filter } = &op.on {
+                    equi_pairs.extend(on.iter().cloned());
+                    if let Some(filter) = filter {
+                        filters.append(&mut split_conjunctive_predicates(filter));
+                    }
+                }
+                let mut childrens = Vec::with_capacity(2);
+
+                for child_id in graph.children_at(node_id).collect_vec() {
+                    match Self::collect(
+                        graph, child_id, loader, relations, join_ids, equi_pairs, filters,
+                    )? {
+                        Some(child) => childrens.push(child),
+                        None => return Ok(None),
+                    }
+                }
+                return Ok(childrens
+                    .into_iter()
+                    .collect_tuple()
+                    .map(|(left, right)| JoinTree::Join(Box::new(left), Box::new(right))));
+            }
+        }
+
+        Ok(Self::relation(graph, node_id, loader)?.map(|relation| {
+            relations.push(relation);
+
+            JoinTree::Relation(relations.len() - 1)
+        }))
+    }
+
+    /// The relation must be a table with statistics, which may be filtered or projected.
+    fn relation<T: Transaction>(
+        graph: &HepGraph,
+        node_id: HepNodeId,
+        loader: &'a ColumnMetaLoader<'_, T>,
+    ) -> Result<Option<Relation<'a>>, DatabaseError> {
+        let mut scan_id = node_id;
+
+        let scan_op = loop {
+            match graph.operator(scan_id) {
+                Operator::Filter(_) | Operator::Project(_) => {
+                    scan_id = match graph.eldest_child_at(scan_id) {
+                        Some(child_id) => child_id,
+                        None => return Ok(None),
+                    }
+                }
+                Operator::Scan(scan_op) => break scan_op,
+                _ => return Ok(None),
+            }
+        };
+        let column_metas = loader.load(scan_op.table_name.clone())?;
+        let mut rows = match scan_op
+            .primary_keys
+            .first()
+            .and_then(|column_id| find_column_meta(column_metas, column_id))
+        {
+            Some(column_meta) => column_meta.histogram().values_len(),
+            None => return Ok(None),
+        };
+        // the predicates pushed into the scan are estimated by the statistics of the indexes
+        for index_info in scan_op.index_infos.iter() {
+            if let Some(binaries) = &index_info.binaries {
+                if let Some(column_meta) =
+                    find_column_meta(column_metas, &index_info.meta.column_ids[0])
+                {
+                    let count = if index_info.meta.column_ids.len() > 1 {
+                        column_meta.collect_count(&leading_binaries(binaries))
+                    } else {
+                        column_meta.collect_count(binaries)
+                    };
+                    rows = rows.min(count);
+                }
+            }
+        }
+        if let Some(limit) = scan_op.limit.1 {
+            rows = rows.min(limit);
+        }
+
+        Ok(Some(Relation {
+            node_id,
+            table_name: scan_op.table_name.clone(),
+            column_metas,
+            columns: PullUpCorrelatedPredicates::output_columns(graph, node_id),
+            rows: rows.max(1) as f64,
+        }))
+    }
+
+    /// The bits of the relations referenced by the expression,
+    /// none if any of its columns does not belong to exactly one relation.
+    fn expr_mask(&self, expr: &ScalarExpression) -> Option<usize> {
+        let mut mask = 0;
+
+        for column in expr.referenced_columns(true) {
+            let i = self
+                .relations
+                .iter()
+                .positions(|relation| {
+                    relation
+                        .columns
+                        .iter()
+                        .any(|relation_column| relation_column.summary() == column.summary())
+                })
+                .exactly_one()
+                .ok()?;
+            mask |= 1 << i;
+        }
+
+        Some(mask)
+    }
+
+    fn full_mask(&self) -> usize {
+        (1 << self.relations.len()) - 1
+    }
+
+    /// The predicates without columns are evaluated after all the relations are joined.
+    fn predicate_mask(&self, predicate: &Predicate) -> usize {
+        match predicate.mask() {
+            0 => self.full_mask(),
+            mask => mask,
+        }
+    }
+
+    fn distinct_values(&self, expr: &ScalarExpression, mask: usize) -> Option<f64> {
+        if mask.count_ones() != 1 {
+            return None;
+        }
+        let relation = &self.relations[mask.trailing_zeros() as usize];
+        let distinct_values = match expr {
+            ScalarExpression::ColumnRef(column)
+                if column.table_name() == Some(&relation.table_name) =>
+            {
+                column
+                    .id()
+                    .and_then(|column_id| find_column_meta(relation.column_metas, &column_id))
+                    .map(|column_meta| column_meta.histogram().number_of_distinct_value() as f64)
+            }
+            _ => None,
+        };
+
+        Some(
+            distinct_values
+                .map_or(relation.rows, |distinct_values| {
+                    distinct_values.min(relation.rows)
+                })
+                .max(1.0),
+        )
+    }
+
+    fn selectivity(&self, predicate: &Predicate) -> f64 {
+        match predicate {
+            Predicate::Equi {
+                left_expr,
+                right_expr,
+                left_mask,
+                right_mask,
+            } => match (
+                self.distinct_values(left_expr, *left_mask),
+                self.distinct_values(right_expr, *right_mask),
+            ) {
+                (Some(left_distinct), Some(right_distinct)) => {
+                    1.0 / left_distinct.max(right_distinct)
+                }
+                _ => DEFAULT_SELECTIVITY,
+            },
+            Predicate::Other { .. } => DEFAULT_SELECTIVITY,
+        }
+    }
+
+    /// The estimated rows of joining the relations in the mask.
+    fn rows(&self, mask: usize) -> f64 {
+        let rows = self
+            .relations
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| mask & (1 << i) != 0)
+            .map(|(_, relation)| relation.rows)
+            .product::<f64>();
+        let selectivity = self
+            .predicates
+            .iter()
+            .filter(|predicate| self.predicate_mask(predicate) & !mask == 0)
+            .map(|predicate| self.selectivity(predicate))
+            .product::<f64>();
+
+        (rows * selectivity).max(1.0)
+    }
+
+    /// The cost is the sum of the rows of all the joins in the tree.
+    fn cost(&self, tree: &JoinTree) -> f64 {
+        match tree {
+            JoinTree::Relation(_) => 0.0,
+            JoinTree::Join(left, right) => {
+                self.cost(left) + self.cost(right) + self.rows(tree.mask())
+            }
+        }
+    }
+
+    /// The smaller side is used as the left side, which builds the hash table of the hash join.
+    fn join(&self, left: JoinTree, right: JoinTree) -> JoinTree {
+        if self.rows(right.mask()) < self.rows(left.mask()) {
+            JoinTree::Join(Box::new(right), Box::new(left))
+        } else {
+            JoinTree::Join(Box::new(left), Box::new(right))
+        }
+    }
+
+    fn best_tree(&self) -> JoinTree {
+        if self.relations.len() <= DP_THRESHOLD {
+            self.dp_tree()
+        } else {
+            self.greedy_tree()
+        }
+    }
+
+    /// Enumerates all the subsets of the relations, and each subset keeps its cheapest split.
+    fn dp_tree(&self) -> JoinTree {
+        let full_mask = self.full_mask();
+        let mut best = vec![(f64::MAX, 0usize); full_mask + 1];
+
+        for i in 0..self.relations.len() {
+            best[1 << i] = (0.0, 0);
+        }
+        for mask in 1..=full_mask {
+            if mask.count_ones() < 2 {
+                continue;
+            }
+            let rows = self.rows(mask);
+            let mut left_mask = (mask - 1) & mask;
+
+            while left_mask > 0 {
+                let cost = best[left_mask].0 + best[mask ^ left_mask].0 + rows;
+
+                if cost < best[mask].0 {
+                    best[mask] = (cost, left_mask);
+                }
+                left_mask = (left_mask - 1) & mask;
+            }
+        }
+
+        self.build_tree(&best, full_mask)
+    }
+
+    fn build_tree(&self, best: &[(f64, usize)], mask: usize) -> JoinTree {
+        if mask.count_ones() == 1 {
+            return JoinTree::Relation(mask.trailing_zeros() as usize);
+        }
+        let left_mask = best[mask].1;
+
+        self.join(
+            self.build_tree(best, left_mask),
+            self.build_tree(best, mask ^ left_mask),
+        )
+    }
+
+    /// Joins the pair of the trees with the least rows each time.
+    fn greedy_tree(&self) -> JoinTree {
+        let mut trees = (0..self.relations.len())
+            .map(JoinTree::Relation)
+            .collect_vec();
+
+        while trees.len() > 1 {
+            let (i, j) = (0..trees.len())
+                .tuple_combinations()
+                .min_by(|(i_1, j_1), (i_2, j_2)| {
+                    let rows_1 = self.rows(trees[*i_1].mask() | trees[*j_1].mask());
+                    let rows_2 = self.rows(trees[*i_2].mask() | trees[*j_2].mask());
+
+                    rows_1.total_cmp(&rows_2)
+                })
+                .unwrap();
+            let right = trees.remove(j);
+            let left = trees.remove(i);
+
+            trees.push(self.join(left, right));
+        }
+
+        trees.remove(0)
+    }
+
+    fn record_rows<'b>(
+        &self,
+        graph: &mut HepGraph,
+        tree: &JoinTree,
+        join_ids: &mut impl Iterator<Item = &'b HepNodeId>,
+    ) {
+        if let JoinTree::Join(left, right) = tree {
+            if let Some(node_id) = join_ids.next() {
+                let _ = graph
+                    .estimated_rows
+                    .insert(*node_id, self.rows(tree.mask()) as usize);
+            }
+            self.record_rows(graph, left, join_ids);
+            self.record_rows(graph, right, join_ids);
+        }
+    }
+
+    /// Rebuilds the joins with the nodes of the original joins, and each predicate is placed
+    /// on the lowest join that contains all its relations.
+    fn rebuild(
+        &self,
+        graph: &mut HepGraph,
+        tree: &JoinTree,
+        node_id: Option<HepNodeId>,
+        join_ids: &mut Vec<HepNodeId>,
+        is_placed: &mut [bool],
+    ) -> HepNodeId {
+        let (left, right) = match tree {
+            JoinTree::Relation(i) => return self.relations[*i].node_id,
+            JoinTree::Join(left, right) => (left, right),
+        };
+        let node_id = node_id.unwrap_or_else(|| join_ids.pop().unwrap());
+        let left_id = self.rebuild(graph, left, None, join_ids, is_placed);
+        let right_id = self.rebuild(graph, right, None, join_ids, is_placed);
+
+        let (left_side, right_side) = (left.mask(), right.mask());
+        let is_subset = |mask: usize, side: usize| mask != 0 && mask & !side == 0;
+        let mut on = Vec::new();
+        let mut filters = Vec::new();
+
+        for (predicate, is_placed) in self.predicates.iter().zip(is_placed.iter_mut()) {
+            if *is_placed || self.predicate_mask(predicate) & !tree.mask() != 0 {
+                continue;
+            }
+            *is_placed = true;
+
+            match predicate {
+                Predicate::Equi {
+                    left_expr,
+                    right_expr,
+                    left_mask,
+                    right_mask,
+                } => {
+                    if is_subset(*left_mask, left_side) && is_subset(*right_mask, right_side) {
+                        on.push((left_expr.clone(), right_expr.clone()));
+                    } else if is_subset(*left_mask, right_side) && is_subset(*right_mask, left_side)
+                    {
+                        on.push((right_expr.clone(), left_expr.clone()));
+                    } else {
+                        filters.push(ScalarExpression::Binary {
+                            op: BinaryOperator::Eq,
+                            left_expr: Box::new(left_expr.clone()),
+                            right_expr: Box::new(right_expr.clone()),
+                            ty: LogicalType::Boolean,
+                        });
+                    }
+                }
+                Predicate::Other { expr, .. } => filters.push(expr.clone()),
+            }
+        }
+        let filter = reduce_filters(filters, false).map(|filter_op| filter_op.predicate);
+        let (on, join_type) = if on.is_empty() && filter.is_none() {
+            (JoinCondition::None, JoinType::Cross)
+        } else {
+            (JoinCondition::On { on, filter }, JoinType::Inner)
+        };
+
+        graph.replace_node(node_id, Operator::Join(JoinOperator { on, join_type }));
+        graph.replace_childrens(node_id, &[left_id, right_id]);
+        let _ = graph
+            .estimated_rows
+            .insert(node_id, self.rows(tree.mask()) as usize);
+
+        node_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::DataBaseBuilder;
+    use crate::errors::DatabaseError;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_join_reorder() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build().await?;
+        database
+            .run("create table t1 (c1 int primary key, c2 int)")
+            .await?;
+        database
+            .run("create table t2 (c3 int primary key, c4 int)")
+            .await?;
+        database
+            .run("create table t3 (c5 int primary key, c6 int)")
+            .await?;
+
+        for i in 0..100 {
+            let _ = database
+                .run(format!("insert into t1 values({}, {})", i, i + 1).as_str())
+                .await?;
+            let _ = database
+                .run(format!("insert into t2 values({}, {})", i, i + 1).as_str())
+                .await?;
+        }
+        for i in 0..5 {
+            let _ = database
+                .run(format!("insert into t3 values({}, {})", i, i).as_str())
+                .await?;
+        }
+        database.run("analyze table t1").await?;
+        database.run("analyze table t2").await?;
+        database.run("analyze table t3").await?;
+
+        let sql = "select * from t1 join t2 on c2 = c4 join t3 on c1 = c5";
+        let tuples = database.run(format!("explain {}", sql)).await?;
+        let explain = tuples[0].values[0].to_string();
+
+        // t1 is joined with the smaller t3 first
+        assert!(explain.find("Scan t3").unwrap() < explain.find("Scan t1").unwrap());
+        assert!(explain.find("Scan t1").unwrap() < explain.find("Scan t2").unwrap());
+        assert!(explain.contains("Estimated Rows"));
+
+        assert_eq!(database.run(sql).await?.len(), 5);
+
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
+use crate::optimizer::core::column_meta::ColumnMetaLoader;
 use crate::optimizer::core::pattern::Pattern;
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
@@ -7,18 +8,23 @@ use crate::optimizer::rule::normalization::column_pruning::ColumnPruning;
 use crate::optimizer::rule::normalization::combine_operators::{
     CollapseGroupByAgg, CollapseProject, CombineFilter,
 };
+use crate::optimizer::rule::normalization::decorrelation::PullUpCorrelatedPredicates;
 use crate::optimizer::rule::normalization::expression_remapper::ExpressionRemapper;
+use crate::optimizer::rule::normalization::join_reorder::JoinReorder;
 use crate::optimizer::rule::normalization::pushdown_limit::{
     EliminateLimits, LimitProjectTranspose, PushLimitIntoScan, PushLimitThroughJoin,
 };
-use crate::optimizer::rule::normalization::pushdown_predicates::PushPredicateIntoScan;
+pub(crate) use crate::optimizer::rule::normalization::pushdown_predicates::PushPredicateIntoScan;
 use crate::optimizer::rule::normalization::pushdown_predicates::PushPredicateThroughJoin;
 use crate::optimizer::rule::normalization::simplification::ConstantCalculation;
 use crate::optimizer::rule::normalization::simplification::SimplifyFilter;
+use crate::storage::Transaction;
 
 mod column_pruning;
 mod combine_operators;
+mod decorrelation;
 mod expression_remapper;
+mod join_reorder;
 mod pushdown_limit;
 mod pushdown_predicates;
 mod simplification;
@@ -44,6 +50,10 @@ pub enum NormalizationRuleImpl {
     ConstantCalculation,
     // ColumnRemapper
     ExpressionRemapper,
+    // Decorrelation
+    PullUpCorrelatedPredicates,
+    // Cost-based, which requires the statistics
+    JoinReorder,
 }
 
 impl MatchPattern for NormalizationRuleImpl {
@@ -62,36 +72,55 @@ impl MatchPattern for NormalizationRuleImpl {
             NormalizationRuleImpl::SimplifyFilter => SimplifyFilter.pattern(),
             NormalizationRuleImpl::ConstantCalculation => ConstantCalculation.pattern(),
             NormalizationRuleImpl::ExpressionRemapper => ExpressionRemapper.pattern(),
+            NormalizationRuleImpl::PullUpCorrelatedPredicates => {
+                PullUpCorrelatedPredicates.pattern()
+            }
+            NormalizationRuleImpl::JoinReorder => JoinReorder.pattern(),
         }
     }
 }
 
-impl NormalizationRule for NormalizationRuleImpl {
-    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+impl<T: Transaction> NormalizationRule<T> for NormalizationRuleImpl {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        loader: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
         match self {
-            NormalizationRuleImpl::ColumnPruning => ColumnPruning.apply(node_id, graph),
-            NormalizationRuleImpl::CollapseProject => CollapseProject.apply(node_id, graph),
-            NormalizationRuleImpl::CollapseGroupByAgg => CollapseGroupByAgg.apply(node_id, graph),
-            NormalizationRuleImpl::CombineFilter => CombineFilter.apply(node_id, graph),
+            NormalizationRuleImpl::ColumnPruning => ColumnPruning.apply(node_id, graph, loader),
+            NormalizationRuleImpl::CollapseProject => CollapseProject.apply(node_id, graph, loader),
+            NormalizationRuleImpl::CollapseGroupByAgg => {
+                CollapseGroupByAgg.apply(node_id, graph, loader)
+            }
+            NormalizationRuleImpl::CombineFilter => CombineFilter.apply(node_id, graph, loader),
             NormalizationRuleImpl::LimitProjectTranspose => {
-                LimitProjectTranspose.apply(node_id, graph)
+                LimitProjectTranspose.apply(node_id, graph, loader)
             }
-            NormalizationRuleImpl::EliminateLimits => EliminateLimits.apply(node_id, graph),
+            NormalizationRuleImpl::EliminateLimits => EliminateLimits.apply(node_id, graph, loader),
             NormalizationRuleImpl::PushLimitThroughJoin => {
-                PushLimitThroughJoin.apply(node_id, graph)
+                PushLimitThroughJoin.apply(node_id, graph, loader)
             }
             NormalizationRuleImpl::PushLimitIntoTableScan => {
-                PushLimitIntoScan.apply(node_id, graph)
+                PushLimitIntoScan.apply(node_id, graph, loader)
             }
             NormalizationRuleImpl::PushPredicateThroughJoin => {
-                PushPredicateThroughJoin.apply(node_id, graph)
+                PushPredicateThroughJoin.apply(node_id, graph, loader)
             }
-            NormalizationRuleImpl::SimplifyFilter => SimplifyFilter.apply(node_id, graph),
+            NormalizationRuleImpl::SimplifyFilter => SimplifyFilter.apply(node_id, graph, loader),
             NormalizationRuleImpl::PushPredicateIntoScan => {
-                PushPredicateIntoScan.apply(node_id, graph)
+                PushPredicateIntoScan.apply(node_id, graph, loader)
+            }
+            NormalizationRuleImpl::ConstantCalculation => {
+                ConstantCalculation.apply(node_id, graph, loader)
+            }
+            NormalizationRuleImpl::ExpressionRemapper => {
+                ExpressionRemapper.apply(node_id, graph, loader)
+            }
+            NormalizationRuleImpl::PullUpCorrelatedPredicates => {
+                PullUpCorrelatedPredicates.apply(node_id, graph, loader)
             }
-            NormalizationRuleImpl::ConstantCalculation => ConstantCalculation.apply(node_id, graph),
-            NormalizationRuleImpl::ExpressionRemapper => ExpressionRemapper.apply(node_id, graph),
+            NormalizationRuleImpl::JoinReorder => JoinReorder.apply(node_id, graph, loader),
         }
     }
 }
@@ -1,4 +1,5 @@
 use crate::errors::DatabaseError;
+use crate::optimizer::core::column_meta::ColumnMetaLoader;
 use crate::optimizer::core::pattern::Pattern;
 use crate::optimizer::core::pattern::PatternChildrenPredicate;
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
@@ -6,6 +7,7 @@ use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
 use crate::planner::operator::join::JoinType;
 use crate::planner::operator::limit::LimitOperator;
 use crate::planner::operator::Operator;
+use crate::storage::Transaction;
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use std::cmp;
@@ -56,8 +58,13 @@ impl MatchPattern for LimitProjectTranspose {
     }
 }
 
-impl NormalizationRule for LimitProjectTranspose {
-    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+impl<T: Transaction> NormalizationRule<T> for LimitProjectTranspose {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        _: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
         if let Some(child_id) = graph.eldest_child_at(node_id) {
             graph.swap_node(node_id, child_id);
         }
@@ -76,8 +83,13 @@ impl MatchPattern for EliminateLimits {
     }
 }
 
-impl NormalizationRule for EliminateLimits {
-    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+impl<T: Transaction> NormalizationRule<T> for EliminateLimits {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        _: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
         if let Operator::Limit(op) = graph.operator(node_id) {
             if let Some(child_id) = graph.eldest_child_at(node_id) {
                 if let Operator::Limit(child_op) = graph.operator(child_id) {
@@ -125,8 +137,13 @@ impl MatchPattern for PushLimitThroughJoin {
     }
 }
 
-impl NormalizationRule for PushLimitThroughJoin {
-    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+impl<T: Transaction> NormalizationRule<T> for PushLimitThroughJoin {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        _: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
         if let Operator::Limit(op) = graph.operator(node_id) {
             if let Some(child_id) = graph.eldest_child_at(node_id) {
                 let join_type = if let Operator::Join(op) = graph.operator(child_id) {
@@ -141,6 +158,12 @@ impl NormalizationRule for PushLimitThroughJoin {
                     if let Some(grandson_id) = match ty {
                         JoinType::Left => children.first(),
                         JoinType::Right => children.last(),
+                        // the tuples may be discarded by the semi join and the anti join,
+                        // so the limit cannot be pushed down to either side
+                        JoinType::LeftSemi
+                        | JoinType::LeftAnti
+                        | JoinType::RightSemi
+                        | JoinType::RightAnti => None,
                         _ => None,
                     } {
                         graph.add_node(child_id, Some(*grandson_id), Operator::Limit(op.clone()));
@@ -162,8 +185,13 @@ impl MatchPattern for PushLimitIntoScan {
     }
 }
 
-impl NormalizationRule for PushLimitIntoScan {
-    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+impl<T: Transaction> NormalizationRule<T> for PushLimitIntoScan {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        _: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
         if let Operator::Limit(limit_op) = graph.operator(node_id) {
             if let Some(child_index) = graph.eldest_child_at(node_id) {
                 if let Operator::Scan(scan_op) = graph.operator(child_index) {
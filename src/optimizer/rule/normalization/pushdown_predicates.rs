@@ -1,17 +1,26 @@
 use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
+use crate::expression::simplify::ConstantBinary;
 use crate::expression::{BinaryOperator, ScalarExpression};
+use crate::optimizer::core::column_meta::ColumnMetaLoader;
+use crate::optimizer::core::memo::Memo;
 use crate::optimizer::core::pattern::Pattern;
 use crate::optimizer::core::pattern::PatternChildrenPredicate;
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::optimizer::rule::implementation::ImplementationRuleImpl;
 use crate::planner::operator::filter::FilterOperator;
 use crate::planner::operator::join::JoinType;
 use crate::planner::operator::Operator;
-use crate::types::index::IndexInfo;
-use crate::types::LogicalType;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::index::{IndexInfo, IndexMeta};
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::{ColumnId, LogicalType};
 use itertools::Itertools;
 use lazy_static::lazy_static;
+use std::collections::Bound;
+use std::sync::Arc;
 
 lazy_static! {
     static ref PUSH_PREDICATE_THROUGH_JOIN: Pattern = {
@@ -46,7 +55,7 @@ lazy_static! {
     };
 }
 
-fn split_conjunctive_predicates(expr: &ScalarExpression) -> Vec<ScalarExpression> {
+pub(crate) fn split_conjunctive_predicates(expr: &ScalarExpression) -> Vec<ScalarExpression> {
     match expr {
         ScalarExpression::Binary {
             op: BinaryOperator::And,
@@ -63,7 +72,10 @@ fn split_conjunctive_predicates(expr: &ScalarExpression) -> Vec<ScalarExpression
 
 /// reduce filters into a filter, and then build a new LogicalFilter node with input child.
 /// if filters is empty, return the input child.
-fn reduce_filters(filters: Vec<ScalarExpression>, having: bool) -> Option<FilterOperator> {
+pub(crate) fn reduce_filters(
+    filters: Vec<ScalarExpression>,
+    having: bool,
+) -> Option<FilterOperator> {
     filters
         .into_iter()
         .reduce(|a, b| ScalarExpression::Binary {
@@ -101,9 +113,14 @@ impl MatchPattern for PushPredicateThroughJoin {
     }
 }
 
-impl NormalizationRule for PushPredicateThroughJoin {
+impl<T: Transaction> NormalizationRule<T> for PushPredicateThroughJoin {
     // TODO: pushdown_predicates need to consider output columns
-    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        _: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
         let child_id = match graph.eldest_child_at(node_id) {
             Some(child_id) => child_id,
             None => return Ok(()),
@@ -111,7 +128,13 @@ impl NormalizationRule for PushPredicateThroughJoin {
         if let Operator::Join(child_op) = graph.operator(child_id) {
             if !matches!(
                 child_op.join_type,
-                JoinType::Inner | JoinType::Left | JoinType::Right
+                JoinType::Inner
+                    | JoinType::Left
+                    | JoinType::Right
+                    | JoinType::LeftSemi
+                    | JoinType::LeftAnti
+                    | JoinType::RightSemi
+                    | JoinType::RightAnti
             ) {
                 return Ok(());
             }
@@ -149,7 +172,9 @@ impl NormalizationRule for PushPredicateThroughJoin {
 
                         common_filters
                     }
-                    JoinType::Left => {
+                    // the semi join and the anti join only filter the tuples of one side,
+                    // so the predicates on that side can be evaluated before the join
+                    JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti => {
                         if !left_filters.is_empty() {
                             if let Some(left_filter_op) = reduce_filters(left_filters, op.having) {
                                 new_ops.0 = Some(Operator::Filter(left_filter_op));
@@ -161,7 +186,7 @@ impl NormalizationRule for PushPredicateThroughJoin {
                             .chain(right_filters)
                             .collect_vec()
                     }
-                    JoinType::Right => {
+                    JoinType::Right | JoinType::RightSemi | JoinType::RightAnti => {
                         if !right_filters.is_empty() {
                             if let Some(right_filter_op) = reduce_filters(right_filters, op.having)
                             {
@@ -208,29 +233,178 @@ impl MatchPattern for PushPredicateIntoScan {
     }
 }
 
-impl NormalizationRule for PushPredicateIntoScan {
-    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+impl PushPredicateIntoScan {
+    fn push_into_indexes(
+        predicate: &ScalarExpression,
+        index_infos: &mut [IndexInfo],
+    ) -> Result<(), DatabaseError> {
+        for IndexInfo { meta, binaries } in index_infos {
+            let option = if meta.column_ids.len() > 1 {
+                Self::composite_binaries(predicate, meta)?
+            } else {
+                Self::column_binaries(predicate, meta.table_name.as_str(), &meta.column_ids[0])?
+            };
+
+            // every index gets its binaries, and `IndexScanImplementation` chooses by cost
+            if let Some(rearrange_binaries) = option {
+                let _ = binaries.replace(rearrange_binaries);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The ranges of the indexes are unknown until the values of the `$n` parameters in the predicate are bound,
+    /// so they are pushed into the scan under the filter again, and the scan is chosen by cost as the memo does.
+    pub(crate) fn bind_scan<T: Transaction>(
+        predicate: &ScalarExpression,
+        scan_plan: &mut LogicalPlan,
+        loader: &ColumnMetaLoader<'_, T>,
+    ) -> Result<(), DatabaseError> {
+        let Operator::Scan(scan_op) = &mut scan_plan.operator else {
+            return Ok(());
+        };
+        // Tips: the predicate of the cached plan has been remapped by `ExpressionRemapper`
+        let mut predicate = predicate.clone();
+        predicate.unpack_reference();
+        predicate.constant_calculation()?;
+        predicate.simplify()?;
+        Self::push_into_indexes(&predicate, &mut scan_op.index_infos)?;
+
+        let graph = HepGraph::new(LogicalPlan::new(scan_plan.operator.clone(), vec![]));
+        let memo = Memo::new(
+            &graph,
+            loader,
+            &[
+                ImplementationRuleImpl::SeqScan,
+                ImplementationRuleImpl::IndexScan,
+            ],
+        )?;
+        scan_plan.physical_option = graph
+            .into_plan(Some(&memo))
+            .and_then(|plan| plan.physical_option);
+
+        Ok(())
+    }
+
+    fn column_binaries(
+        predicate: &ScalarExpression,
+        table_name: &str,
+        column_id: &ColumnId,
+    ) -> Result<Option<Vec<ConstantBinary>>, DatabaseError> {
+        if let Some(mut binary) = predicate.convert_binary(table_name, column_id)? {
+            binary.scope_aggregation()?;
+            let rearrange_binaries = binary.rearrange()?;
+
+            if !rearrange_binaries.is_empty() {
+                return Ok(Some(rearrange_binaries));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// The binaries of the composite index are built from its leading columns:
+    /// - the leading columns that are fixed by `Eq` are combined into the prefixes
+    /// - the first column that is not fixed uses its binaries after the prefixes
+    ///
+    /// e.g. index (c1, c2, c3): `c1 = 1 and c2 > 2` => `Scope { min: Excluded((1, 2)), max: Included((1)) }`
+    ///
+    /// Tips: the values are `DataValue::Tuple` with the leading values, and the filter will still be retained
+    fn composite_binaries(
+        predicate: &ScalarExpression,
+        meta: &IndexMeta,
+    ) -> Result<Option<Vec<ConstantBinary>>, DatabaseError> {
+        let tuple = |prefix: &[ValueRef], value: Option<&ValueRef>| {
+            let mut values = prefix.to_vec();
+            values.extend(value.cloned());
+
+            Arc::new(DataValue::Tuple(Some(values)))
+        };
+        let mut prefixes: Vec<Vec<ValueRef>> = vec![vec![]];
+
+        for column_id in meta.column_ids.iter() {
+            let binaries =
+                match Self::column_binaries(predicate, meta.table_name.as_str(), column_id)? {
+                    Some(binaries) => binaries,
+                    None => break,
+                };
+            if binaries.iter().any(|binary| {
+                match binary {
+                ConstantBinary::Scope { min, max } => [min, max].iter().any(|bound| {
+                    matches!(bound, Bound::Included(val) | Bound::Excluded(val) if val.is_null())
+                }),
+                ConstantBinary::Eq(val) => val.is_null(),
+                _ => true,
+            }
+            }) {
+                break;
+            }
+            if binaries
+                .iter()
+                .all(|binary| matches!(binary, ConstantBinary::Eq(_)))
+            {
+                prefixes = prefixes
+                    .iter()
+                    .flat_map(|prefix| {
+                        binaries.iter().map(move |binary| match binary {
+                            ConstantBinary::Eq(val) => {
+                                let mut prefix = prefix.clone();
+                                prefix.push(val.clone());
+                                prefix
+                            }
+                            _ => unreachable!(),
+                        })
+                    })
+                    .collect_vec();
+                continue;
+            }
+            let binaries = prefixes
+                .iter()
+                .flat_map(|prefix| {
+                    binaries.iter().map(move |binary| match binary {
+                        ConstantBinary::Scope { min, max } => {
+                            let bound = |bound: &Bound<ValueRef>| match bound {
+                                Bound::Included(val) => Bound::Included(tuple(prefix, Some(val))),
+                                Bound::Excluded(val) => Bound::Excluded(tuple(prefix, Some(val))),
+                                Bound::Unbounded if prefix.is_empty() => Bound::Unbounded,
+                                Bound::Unbounded => Bound::Included(tuple(prefix, None)),
+                            };
+
+                            ConstantBinary::Scope {
+                                min: bound(min),
+                                max: bound(max),
+                            }
+                        }
+                        ConstantBinary::Eq(val) => ConstantBinary::Eq(tuple(prefix, Some(val))),
+                        _ => unreachable!(),
+                    })
+                })
+                .collect_vec();
+
+            return Ok(Some(binaries));
+        }
+        let binaries = prefixes
+            .iter()
+            .filter(|prefix| !prefix.is_empty())
+            .map(|prefix| ConstantBinary::Eq(tuple(prefix, None)))
+            .collect_vec();
+
+        Ok((!binaries.is_empty()).then_some(binaries))
+    }
+}
+
+impl<T: Transaction> NormalizationRule<T> for PushPredicateIntoScan {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        _: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
         if let Operator::Filter(op) = graph.operator(node_id).clone() {
             if let Some(child_id) = graph.eldest_child_at(node_id) {
                 if let Operator::Scan(child_op) = graph.operator_mut(child_id) {
-                    //FIXME: now only support unique
-                    for IndexInfo { meta, binaries } in &mut child_op.index_infos {
-                        let mut option = op
-                            .predicate
-                            .convert_binary(meta.table_name.as_str(), &meta.column_ids[0])?;
-
-                        if let Some(mut binary) = option.take() {
-                            binary.scope_aggregation()?;
-                            let rearrange_binaries = binary.rearrange()?;
-
-                            if rearrange_binaries.is_empty() {
-                                continue;
-                            }
-                            let _ = binaries.replace(rearrange_binaries);
-
-                            return Ok(());
-                        }
-                    }
+                    Self::push_into_indexes(&op.predicate, &mut child_op.index_infos)?;
                 }
             }
         }
@@ -287,6 +461,40 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_push_predicate_into_scan_by_composite_primary_key() -> Result<(), DatabaseError> {
+        let plan = select_sql_run("select * from t3 where c5 = 1 and c6 > 2").await?;
+
+        let best_plan = HepOptimizer::new(plan)
+            .batch(
+                "test_push_predicate_into_scan".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![NormalizationRuleImpl::PushPredicateIntoScan],
+            )
+            .find_best::<KipTransaction>(None)?;
+
+        if let Operator::Scan(op) = &best_plan.childrens[0].childrens[0].operator {
+            let tuple = |values: Vec<i32>| {
+                Arc::new(DataValue::Tuple(Some(
+                    values
+                        .into_iter()
+                        .map(|value| Arc::new(DataValue::Int32(Some(value))))
+                        .collect(),
+                )))
+            };
+            let mock_binaries = vec![Scope {
+                min: Bound::Excluded(tuple(vec![1, 2])),
+                max: Bound::Included(tuple(vec![1])),
+            }];
+
+            assert_eq!(op.index_infos[0].binaries, Some(mock_binaries));
+        } else {
+            unreachable!("Should be a filter operator")
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_push_predicate_through_join_in_left_join() -> Result<(), DatabaseError> {
         let plan =
@@ -1,9 +1,11 @@
 use crate::errors::DatabaseError;
+use crate::optimizer::core::column_meta::ColumnMetaLoader;
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
 use crate::planner::operator::join::JoinCondition;
 use crate::planner::operator::Operator;
+use crate::storage::Transaction;
 use itertools::Itertools;
 use lazy_static::lazy_static;
 lazy_static! {
@@ -77,8 +79,13 @@ impl MatchPattern for ConstantCalculation {
     }
 }
 
-impl NormalizationRule for ConstantCalculation {
-    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+impl<T: Transaction> NormalizationRule<T> for ConstantCalculation {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        _: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
         Self::_apply(node_id, graph)?;
         // mark changed to skip this rule batch
         graph.version += 1;
@@ -96,8 +103,13 @@ impl MatchPattern for SimplifyFilter {
     }
 }
 
-impl NormalizationRule for SimplifyFilter {
-    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+impl<T: Transaction> NormalizationRule<T> for SimplifyFilter {
+    fn apply(
+        &self,
+        node_id: HepNodeId,
+        graph: &mut HepGraph,
+        _: Option<&ColumnMetaLoader<'_, T>>,
+    ) -> Result<(), DatabaseError> {
         if let Operator::Filter(mut filter_op) = graph.operator(node_id).clone() {
             filter_op.predicate.simplify()?;
             filter_op.predicate.constant_calculation()?;
@@ -258,7 +270,7 @@ mod test {
                 nullable: false,
                 desc: ColumnDesc {
                     column_datatype: LogicalType::Integer,
-                    is_primary: true,
+                    primary: Some(0),
                     is_unique: false,
                     default: None,
                 },
@@ -272,7 +284,7 @@ mod test {
                 nullable: false,
                 desc: ColumnDesc {
                     column_datatype: LogicalType::Integer,
-                    is_primary: false,
+                    primary: None,
                     is_unique: true,
                     default: None,
                 },
@@ -1,4 +1,6 @@
+use sqlparser::keywords::Keyword;
 use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Token, Tokenizer};
 use sqlparser::{ast::Statement, dialect::PostgreSqlDialect, parser::Parser};
 
 const DIALECT: PostgreSqlDialect = PostgreSqlDialect {};
@@ -16,5 +18,167 @@ const DIALECT: PostgreSqlDialect = PostgreSqlDialect {};
 /// println!("{:?}", ast);
 /// ```
 pub fn parse_sql<S: AsRef<str>>(sql: S) -> Result<Vec<Statement>, ParserError> {
-    Parser::parse_sql(&DIALECT, sql.as_ref())
+    let mut tokens = Tokenizer::new(&DIALECT, sql.as_ref()).tokenize()?;
+    let mut placeholder_count = 0;
+    let mut has_numbered = false;
+
+    // `?` is numbered in the order of appearance, so that it is bound in the same way as `$n`
+    for token in tokens.iter_mut() {
+        if let Token::Placeholder(placeholder) = token {
+            if placeholder == "?" {
+                placeholder_count += 1;
+                *placeholder = format!("${placeholder_count}");
+            } else {
+                has_numbered = true;
+            }
+        }
+    }
+    // Tips: `?` cannot be numbered after the `$n` written by the user
+    if has_numbered && placeholder_count > 0 {
+        return Err(ParserError::ParserError(
+            "`$n` and `?` placeholders cannot be mixed".to_string(),
+        ));
+    }
+    // `USER` is the alias of `ROLE` in `CREATE USER`, `ALTER USER` and `DROP USER`,
+    // which is rewritten at the beginning of every statement
+    for statement in tokens.split_mut(|token| matches!(token, Token::SemiColon)) {
+        let mut words = statement
+            .iter_mut()
+            .filter(|token| !matches!(token, Token::Whitespace(_)));
+        if let (Some(Token::Word(first)), Some(second)) = (words.next(), words.next()) {
+            let is_user = matches!(second, Token::Word(word) if word.keyword == Keyword::USER);
+
+            if is_user
+                && matches!(
+                    first.keyword,
+                    Keyword::CREATE | Keyword::ALTER | Keyword::DROP
+                )
+            {
+                *second = Token::make_keyword("ROLE");
+            }
+        }
+    }
+    let mut statements = Vec::new();
+
+    for tokens in tokens.split(|token| matches!(token, Token::SemiColon)) {
+        statements.append(&mut parse_statement(tokens)?);
+    }
+    Ok(statements)
+}
+
+/// Tips: `RETURNING` after the table is taken as its alias by sqlparser,
+/// so `RETURNING` of `INSERT`, `UPDATE` and `DELETE` is parsed apart from the statement
+fn parse_statement(tokens: &[Token]) -> Result<Vec<Statement>, ParserError> {
+    let is_dml = tokens
+        .iter()
+        .find(|token| !matches!(token, Token::Whitespace(_)))
+        .map_or(false, |token| {
+            matches!(
+                token,
+                Token::Word(word) if matches!(word.keyword, Keyword::INSERT | Keyword::UPDATE | Keyword::DELETE)
+            )
+        });
+    let mut depth = 0;
+    let returning_pos = tokens.iter().position(|token| {
+        match token {
+            Token::LParen => depth += 1,
+            Token::RParen => depth -= 1,
+            _ => (),
+        }
+        depth == 0 && matches!(token, Token::Word(word) if word.keyword == Keyword::RETURNING)
+    });
+    let Some(pos) = returning_pos.filter(|_| is_dml) else {
+        return Parser::new(&DIALECT)
+            .with_tokens(tokens.to_vec())
+            .parse_statements();
+    };
+    let mut statements = Parser::new(&DIALECT)
+        .with_tokens(tokens[..pos].to_vec())
+        .parse_statements()?;
+    let mut parser = Parser::new(&DIALECT).with_tokens(tokens[pos + 1..].to_vec());
+    let items = parser.parse_comma_separated(Parser::parse_select_item)?;
+    parser.expect_token(&Token::EOF)?;
+
+    match statements.as_mut_slice() {
+        [Statement::Insert { returning, .. }
+        | Statement::Update { returning, .. }
+        | Statement::Delete { returning, .. }] => *returning = Some(items),
+        _ => {
+            return Err(ParserError::ParserError(
+                "RETURNING is only supported by INSERT, UPDATE and DELETE".to_string(),
+            ))
+        }
+    }
+    Ok(statements)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_sql;
+    use sqlparser::ast::{ObjectType, Statement};
+    use sqlparser::parser::ParserError;
+
+    #[test]
+    fn test_parse_user_statements() -> Result<(), ParserError> {
+        let statements = parse_sql(
+            "create user kip with password 'kip'; alter user kip with password 'sql'; drop user kip",
+        )?;
+
+        assert!(matches!(statements[0], Statement::CreateRole { .. }));
+        assert!(matches!(statements[1], Statement::AlterRole { .. }));
+        assert!(matches!(
+            statements[2],
+            Statement::Drop {
+                object_type: ObjectType::Role,
+                ..
+            }
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_placeholders() -> Result<(), ParserError> {
+        assert_eq!(
+            parse_sql("select * from t1 where c1 = ? and c2 = ?")?,
+            parse_sql("select * from t1 where c1 = $1 and c2 = $2")?
+        );
+        assert!(parse_sql("select * from t1 where c1 = $1 and c2 = ?").is_err());
+        assert!(parse_sql("select * from t1 where c1 = ? and c2 = $1").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_returning() -> Result<(), ParserError> {
+        let statements = parse_sql(
+            "delete from t1 returning a; \
+             update t1 set a = (select max(a) from t2) returning a, b; \
+             insert into t1 (a) select a from t2 returning *",
+        )?;
+
+        assert_eq!(statements.len(), 3);
+        for statement in statements {
+            assert!(matches!(
+                statement,
+                Statement::Delete {
+                    returning: Some(_),
+                    ..
+                } | Statement::Update {
+                    returning: Some(_),
+                    ..
+                } | Statement::Insert {
+                    returning: Some(_),
+                    ..
+                }
+            ));
+        }
+        assert_eq!(
+            parse_sql("update t1 set a = 1 returning a")?[0].to_string(),
+            "UPDATE t1 SET a = 1 RETURNING a"
+        );
+        assert!(parse_sql("delete from t1 returning").is_err());
+
+        Ok(())
+    }
 }
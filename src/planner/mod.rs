@@ -1,7 +1,15 @@
 pub mod operator;
 
 use crate::catalog::TableName;
+use crate::planner::operator::delete::DeleteOperator;
+use crate::planner::operator::except::ExceptOperator;
+use crate::planner::operator::insert::InsertOperator;
+use crate::planner::operator::intersect::IntersectOperator;
+use crate::planner::operator::join::JoinType;
+use crate::planner::operator::recursive_cte::RecursiveCteOperator;
+use crate::planner::operator::table_function::TableFunctionOperator;
 use crate::planner::operator::union::UnionOperator;
+use crate::planner::operator::update::UpdateOperator;
 use crate::planner::operator::values::ValuesOperator;
 use crate::planner::operator::{Operator, PhysicalOption};
 use crate::types::tuple::SchemaRef;
@@ -13,6 +21,8 @@ pub struct LogicalPlan {
     pub(crate) operator: Operator,
     pub(crate) childrens: Vec<LogicalPlan>,
     pub(crate) physical_option: Option<PhysicalOption>,
+    /// The rows estimated by the statistics, which are shown by `EXPLAIN`.
+    pub(crate) estimated_rows: Option<usize>,
 
     pub(crate) _output_schema_ref: Option<SchemaRef>,
 }
@@ -23,6 +33,7 @@ impl LogicalPlan {
             operator,
             childrens,
             physical_option: None,
+            estimated_rows: None,
             _output_schema_ref: None,
         }
     }
@@ -33,8 +44,10 @@ impl LogicalPlan {
 
     pub fn referenced_table(&self) -> Vec<TableName> {
         fn collect_table(plan: &LogicalPlan, results: &mut Vec<TableName>) {
-            if let Operator::Scan(op) = &plan.operator {
-                results.push(op.table_name.clone());
+            match &plan.operator {
+                Operator::Scan(op) => results.push(op.table_name.clone()),
+                Operator::TableFunction(op) => results.push(op.table_name.clone()),
+                _ => (),
             }
             for child in &plan.childrens {
                 collect_table(child, results);
@@ -61,6 +74,16 @@ impl LogicalPlan {
                         .collect_vec();
                     Arc::new(out_columns)
                 }
+                Operator::Join(op)
+                    if matches!(op.join_type, JoinType::LeftSemi | JoinType::LeftAnti) =>
+                {
+                    self.childrens[0].output_schema().clone()
+                }
+                Operator::Join(op)
+                    if matches!(op.join_type, JoinType::RightSemi | JoinType::RightAnti) =>
+                {
+                    self.childrens[1].output_schema().clone()
+                }
                 Operator::Join(_) => {
                     let out_columns = self
                         .childrens
@@ -85,7 +108,15 @@ impl LogicalPlan {
                         .collect_vec();
                     Arc::new(out_columns)
                 }
-                Operator::Values(ValuesOperator { schema_ref, .. }) => schema_ref.clone(),
+                Operator::Values(ValuesOperator { schema_ref, .. })
+                | Operator::TableFunction(TableFunctionOperator { schema_ref, .. }) => {
+                    schema_ref.clone()
+                }
+                Operator::Window(op) => {
+                    let mut out_columns = Vec::clone(self.childrens[0].output_schema());
+                    out_columns.extend(op.window_calls.iter().map(|expr| expr.output_column()));
+                    Arc::new(out_columns)
+                }
                 Operator::Union(UnionOperator {
                     left_schema_ref,
                     right_schema_ref,
@@ -94,18 +125,39 @@ impl LogicalPlan {
                     schema.extend_from_slice(right_schema_ref.as_slice());
                     Arc::new(schema)
                 }
+                Operator::Intersect(IntersectOperator {
+                    left_schema_ref, ..
+                })
+                | Operator::Except(ExceptOperator {
+                    left_schema_ref, ..
+                }) => left_schema_ref.clone(),
+                Operator::RecursiveCte(RecursiveCteOperator { schema_ref, .. }) => {
+                    schema_ref.clone()
+                }
+                Operator::Insert(InsertOperator { returning, .. })
+                | Operator::Update(UpdateOperator { returning, .. })
+                | Operator::Delete(DeleteOperator { returning, .. }) => Arc::new(
+                    returning
+                        .iter()
+                        .map(|expr| expr.output_column())
+                        .collect_vec(),
+                ),
                 Operator::Dummy
                 | Operator::Show
                 | Operator::Explain
                 | Operator::Describe(_)
-                | Operator::Insert(_)
-                | Operator::Update(_)
-                | Operator::Delete(_)
                 | Operator::Analyze(_)
                 | Operator::AddColumn(_)
                 | Operator::DropColumn(_)
                 | Operator::CreateTable(_)
                 | Operator::DropTable(_)
+                | Operator::CreateIndex(_)
+                | Operator::DropIndex(_)
+                | Operator::CreateUser(_)
+                | Operator::AlterUser(_)
+                | Operator::DropUser(_)
+                | Operator::Grant(_)
+                | Operator::Revoke(_)
                 | Operator::Truncate(_)
                 | Operator::CopyFromFile(_)
                 | Operator::CopyToFile(_) => Arc::new(vec![]),
@@ -118,6 +170,9 @@ impl LogicalPlan {
         if let Some(physical_option) = &self.physical_option {
             result.push_str(&format!(" [{}]", physical_option));
         }
+        if let Some(estimated_rows) = self.estimated_rows {
+            result.push_str(&format!(" (Estimated Rows: {})", estimated_rows));
+        }
 
         for child in &self.childrens {
             result.push('\n');
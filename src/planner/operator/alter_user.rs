@@ -0,0 +1,16 @@
+use crate::catalog::UserCatalog;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct AlterUserOperator {
+    pub user: UserCatalog,
+}
+
+impl fmt::Display for AlterUserOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Alter User {}", self.user.name)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use crate::catalog::{ColumnRef, TableName};
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct CreateIndexOperator {
+    pub table_name: TableName,
+    pub index_name: String,
+    /// List of columns of the index, the order is the order of the index key
+    pub columns: Vec<ColumnRef>,
+    pub is_unique: bool,
+    pub if_not_exists: bool,
+}
+
+impl fmt::Display for CreateIndexOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| column.name().to_string())
+            .join(", ");
+        write!(
+            f,
+            "Create Index {} On {} -> [{}], Unique: {}, If Not Exists: {}",
+            self.index_name, self.table_name, columns, self.is_unique, self.if_not_exists
+        )?;
+
+        Ok(())
+    }
+}
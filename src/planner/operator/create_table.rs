@@ -9,7 +9,12 @@ pub struct CreateTableOperator {
     pub table_name: TableName,
     /// List of columns of the table
     pub columns: Vec<ColumnCatalog>,
+    /// Unique constraints with multiple columns,
+    /// the single column one is marked on `ColumnDesc::is_unique`
+    pub unique_keys: Vec<Vec<String>>,
     pub if_not_exists: bool,
+    /// The user who creates the table is granted all the privileges on it
+    pub owner: Option<String>,
 }
 
 impl fmt::Display for CreateTableOperator {
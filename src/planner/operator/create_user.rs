@@ -0,0 +1,21 @@
+use crate::catalog::UserCatalog;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct CreateUserOperator {
+    pub user: UserCatalog,
+    pub if_not_exists: bool,
+}
+
+impl fmt::Display for CreateUserOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Create User {}, If Not Exists: {}",
+            self.user.name, self.if_not_exists
+        )?;
+
+        Ok(())
+    }
+}
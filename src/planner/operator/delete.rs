@@ -1,4 +1,6 @@
 use crate::catalog::{ColumnRef, TableName};
+use crate::expression::ScalarExpression;
+use itertools::Itertools;
 use std::fmt;
 use std::fmt::Formatter;
 
@@ -6,12 +8,18 @@ use std::fmt::Formatter;
 pub struct DeleteOperator {
     pub table_name: TableName,
     // for column pruning
-    pub primary_key_column: ColumnRef,
+    pub primary_keys: Vec<ColumnRef>,
+    /// `RETURNING`, which is evaluated on the deleted tuples,
+    /// the affected rows are counted instead if empty
+    pub returning: Vec<ScalarExpression>,
 }
 
 impl fmt::Display for DeleteOperator {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "Delete {}", self.table_name)?;
+        if !self.returning.is_empty() {
+            write!(f, ", Returning: [{}]", self.returning.iter().join(", "))?;
+        }
 
         Ok(())
     }
@@ -0,0 +1,22 @@
+use crate::catalog::TableName;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct DropIndexOperator {
+    pub table_name: TableName,
+    pub index_name: String,
+    pub if_exists: bool,
+}
+
+impl fmt::Display for DropIndexOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Drop Index {} On {}, If Exists: {}",
+            self.index_name, self.table_name, self.if_exists
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct DropUserOperator {
+    pub user_name: String,
+    pub if_exists: bool,
+}
+
+impl fmt::Display for DropUserOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Drop User {}, If Exists: {}",
+            self.user_name, self.if_exists
+        )?;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,8 @@
-use crate::catalog::TableName;
+use crate::catalog::{ColumnRef, TableName};
+use crate::expression::ScalarExpression;
+use crate::types::index::IndexMetaRef;
+use crate::types::tuple::SchemaRef;
+use itertools::Itertools;
 use std::fmt;
 use std::fmt::Formatter;
 
@@ -6,6 +10,33 @@ use std::fmt::Formatter;
 pub struct InsertOperator {
     pub table_name: TableName,
     pub is_overwrite: bool,
+    /// The target columns of the input by position,
+    /// the input is mapped to the table by the column ids if none
+    pub columns: Option<Vec<ColumnRef>>,
+    pub on_conflict: Option<OnConflict>,
+    /// `RETURNING`, which is evaluated on the inserted or updated tuples,
+    /// the affected rows are counted instead if empty
+    pub returning: Vec<ScalarExpression>,
+}
+
+/// `ON CONFLICT [(columns)] DO NOTHING | DO UPDATE SET ... [WHERE ...]`
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct OnConflict {
+    /// The unique index whose conflicts are handled, the primary index and all the unique indexes if none
+    pub index_meta: Option<IndexMetaRef>,
+    pub action: OnConflictAction,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum OnConflictAction {
+    DoNothing,
+    /// The values and the selection are evaluated on the existing tuple followed by the excluded one,
+    /// which is the tuple proposed for insertion and referenced as `EXCLUDED`
+    DoUpdate {
+        excluded_schema_ref: SchemaRef,
+        value_exprs: Vec<(ColumnRef, ScalarExpression)>,
+        selection: Option<ScalarExpression>,
+    },
 }
 
 impl fmt::Display for InsertOperator {
@@ -15,6 +46,41 @@ impl fmt::Display for InsertOperator {
             "Insert {}, Is Overwrite: {}",
             self.table_name, self.is_overwrite
         )?;
+        if let Some(on_conflict) = &self.on_conflict {
+            write!(f, ", {}", on_conflict)?;
+        }
+        if !self.returning.is_empty() {
+            write!(f, ", Returning: [{}]", self.returning.iter().join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for OnConflict {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "On Conflict")?;
+        if let Some(index_meta) = &self.index_meta {
+            write!(f, " {}", index_meta.name)?;
+        }
+        match &self.action {
+            OnConflictAction::DoNothing => write!(f, " Do Nothing")?,
+            OnConflictAction::DoUpdate {
+                value_exprs,
+                selection,
+                ..
+            } => {
+                let values = value_exprs
+                    .iter()
+                    .map(|(column, expr)| format!("{} = {}", column.name(), expr))
+                    .join(", ");
+
+                write!(f, " Do Update -> [{}]", values)?;
+                if let Some(selection) = selection {
+                    write!(f, " Where {}", selection)?;
+                }
+            }
+        }
 
         Ok(())
     }
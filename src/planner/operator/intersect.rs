@@ -0,0 +1,57 @@
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::types::tuple::SchemaRef;
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct IntersectOperator {
+    pub left_schema_ref: SchemaRef,
+    pub right_schema_ref: SchemaRef,
+    pub is_all: bool,
+}
+
+impl IntersectOperator {
+    pub fn build(
+        left_schema_ref: SchemaRef,
+        right_schema_ref: SchemaRef,
+        is_all: bool,
+        left_plan: LogicalPlan,
+        right_plan: LogicalPlan,
+    ) -> LogicalPlan {
+        LogicalPlan::new(
+            Operator::Intersect(IntersectOperator {
+                left_schema_ref,
+                right_schema_ref,
+                is_all,
+            }),
+            vec![left_plan, right_plan],
+        )
+    }
+}
+
+impl fmt::Display for IntersectOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let left_columns = self
+            .left_schema_ref
+            .iter()
+            .map(|column| column.name().to_string())
+            .join(", ");
+        let right_columns = self
+            .right_schema_ref
+            .iter()
+            .map(|column| column.name().to_string())
+            .join(", ");
+
+        write!(
+            f,
+            "Intersect{} left: [{}], right: [{}]",
+            if self.is_all { " All" } else { "" },
+            left_columns,
+            right_columns
+        )?;
+
+        Ok(())
+    }
+}
@@ -14,6 +14,14 @@ pub enum JoinType {
     Right,
     Full,
     Cross,
+    /// Outputs the left tuples that have matches on the right
+    LeftSemi,
+    /// Outputs the left tuples that have no matches on the right
+    LeftAnti,
+    /// Outputs the right tuples that have matches on the left
+    RightSemi,
+    /// Outputs the right tuples that have no matches on the left
+    RightAnti,
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum JoinCondition {
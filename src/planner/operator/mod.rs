@@ -1,44 +1,71 @@
 pub mod aggregate;
 pub mod alter_table;
+pub mod alter_user;
 pub mod analyze;
 pub mod copy_from_file;
 pub mod copy_to_file;
+pub mod create_index;
 pub mod create_table;
+pub mod create_user;
 pub mod delete;
 pub mod describe;
+pub mod drop_index;
 pub mod drop_table;
+pub mod drop_user;
+pub mod except;
 pub mod filter;
+pub mod grant;
 pub mod insert;
+pub mod intersect;
 pub mod join;
 pub mod limit;
 pub mod project;
+pub mod recursive_cte;
+pub mod revoke;
 pub mod scan;
 pub mod sort;
+pub mod table_function;
 pub mod truncate;
 pub mod union;
 pub mod update;
 pub mod values;
+pub mod window;
 
 use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
 use crate::planner::operator::alter_table::drop_column::DropColumnOperator;
+use crate::planner::operator::alter_user::AlterUserOperator;
 use crate::planner::operator::analyze::AnalyzeOperator;
 use crate::planner::operator::copy_from_file::CopyFromFileOperator;
 use crate::planner::operator::copy_to_file::CopyToFileOperator;
+use crate::planner::operator::create_index::CreateIndexOperator;
 use crate::planner::operator::create_table::CreateTableOperator;
+use crate::planner::operator::create_user::CreateUserOperator;
 use crate::planner::operator::delete::DeleteOperator;
 use crate::planner::operator::describe::DescribeOperator;
+use crate::planner::operator::drop_index::DropIndexOperator;
 use crate::planner::operator::drop_table::DropTableOperator;
-use crate::planner::operator::insert::InsertOperator;
+use crate::planner::operator::drop_user::DropUserOperator;
+use crate::planner::operator::except::ExceptOperator;
+use crate::planner::operator::grant::GrantOperator;
+use crate::planner::operator::insert::{InsertOperator, OnConflict, OnConflictAction};
+use crate::planner::operator::intersect::IntersectOperator;
 use crate::planner::operator::join::JoinCondition;
+use crate::planner::operator::recursive_cte::RecursiveCteOperator;
+use crate::planner::operator::revoke::RevokeOperator;
+use crate::planner::operator::table_function::TableFunctionOperator;
 use crate::planner::operator::truncate::TruncateOperator;
 use crate::planner::operator::union::UnionOperator;
 use crate::planner::operator::update::UpdateOperator;
 use crate::planner::operator::values::ValuesOperator;
+use crate::planner::operator::window::WindowOperator;
 use crate::types::index::IndexInfo;
+use crate::types::value::{DataValue, ValueRef};
 use itertools::Itertools;
 use std::fmt;
 use std::fmt::Formatter;
+use std::sync::Arc;
 
 use self::{
     aggregate::AggregateOperator, alter_table::add_column::AddColumnOperator,
@@ -58,10 +85,15 @@ pub enum Operator {
     Sort(SortOperator),
     Limit(LimitOperator),
     Values(ValuesOperator),
+    TableFunction(TableFunctionOperator),
+    Window(WindowOperator),
     Show,
     Explain,
     Describe(DescribeOperator),
     Union(UnionOperator),
+    Intersect(IntersectOperator),
+    Except(ExceptOperator),
+    RecursiveCte(RecursiveCteOperator),
     // DML
     Insert(InsertOperator),
     Update(UpdateOperator),
@@ -72,6 +104,13 @@ pub enum Operator {
     DropColumn(DropColumnOperator),
     CreateTable(CreateTableOperator),
     DropTable(DropTableOperator),
+    CreateIndex(CreateIndexOperator),
+    DropIndex(DropIndexOperator),
+    CreateUser(CreateUserOperator),
+    AlterUser(AlterUserOperator),
+    DropUser(DropUserOperator),
+    Grant(GrantOperator),
+    Revoke(RevokeOperator),
     Truncate(TruncateOperator),
     // Copy
     CopyFromFile(CopyFromFileOperator),
@@ -85,13 +124,19 @@ pub enum PhysicalOption {
     HashAggregate,
     Filter,
     HashJoin,
+    NestedLoopJoin,
+    SortMergeJoin,
+    HashIntersect,
+    HashExcept,
     Project,
     SeqScan,
     IndexScan(IndexInfo),
     RadixSort,
     // NormalSort,
+    Window,
     Limit,
     Values,
+    TableFunction,
     Insert,
     Update,
     Delete,
@@ -99,6 +144,13 @@ pub enum PhysicalOption {
     DropColumn,
     CreateTable,
     DropTable,
+    CreateIndex,
+    DropIndex,
+    CreateUser,
+    AlterUser,
+    DropUser,
+    Grant,
+    Revoke,
     Truncate,
     Show,
     CopyFromFile,
@@ -126,7 +178,7 @@ impl Operator {
                     .map(|(_, column)| ScalarExpression::ColumnRef(column))
                     .collect_vec(),
             ),
-            Operator::Sort(_) | Operator::Limit(_) => None,
+            Operator::Sort(_) | Operator::Limit(_) | Operator::Window(_) => None,
             Operator::Values(ValuesOperator { schema_ref, .. }) => Some(
                 schema_ref
                     .iter()
@@ -134,6 +186,13 @@ impl Operator {
                     .map(ScalarExpression::ColumnRef)
                     .collect_vec(),
             ),
+            Operator::TableFunction(TableFunctionOperator { schema_ref, .. }) => Some(
+                schema_ref
+                    .iter()
+                    .cloned()
+                    .map(ScalarExpression::ColumnRef)
+                    .collect_vec(),
+            ),
             Operator::Union(UnionOperator {
                 left_schema_ref,
                 right_schema_ref,
@@ -145,6 +204,25 @@ impl Operator {
                     .map(ScalarExpression::ColumnRef)
                     .collect_vec(),
             ),
+            Operator::Intersect(IntersectOperator {
+                left_schema_ref, ..
+            })
+            | Operator::Except(ExceptOperator {
+                left_schema_ref, ..
+            }) => Some(
+                left_schema_ref
+                    .iter()
+                    .cloned()
+                    .map(ScalarExpression::ColumnRef)
+                    .collect_vec(),
+            ),
+            Operator::RecursiveCte(RecursiveCteOperator { schema_ref, .. }) => Some(
+                schema_ref
+                    .iter()
+                    .cloned()
+                    .map(ScalarExpression::ColumnRef)
+                    .collect_vec(),
+            ),
             Operator::Show
             | Operator::Explain
             | Operator::Describe(_)
@@ -156,12 +234,123 @@ impl Operator {
             | Operator::DropColumn(_)
             | Operator::CreateTable(_)
             | Operator::DropTable(_)
+            | Operator::CreateIndex(_)
+            | Operator::DropIndex(_)
+            | Operator::CreateUser(_)
+            | Operator::AlterUser(_)
+            | Operator::DropUser(_)
+            | Operator::Grant(_)
+            | Operator::Revoke(_)
             | Operator::Truncate(_)
             | Operator::CopyFromFile(_)
             | Operator::CopyToFile(_) => None,
         }
     }
 
+    /// Bind the values of the `$n` parameters to the placeholders of the operator,
+    /// and returns whether there is any placeholder in it.
+    pub fn bind_placeholders(&mut self, params: &[ValueRef]) -> Result<bool, DatabaseError> {
+        let mut has_placeholder = false;
+        let mut bind = |expr: &mut ScalarExpression| -> Result<(), DatabaseError> {
+            has_placeholder |= expr.bind_placeholders(params)?;
+            Ok(())
+        };
+
+        match self {
+            Operator::Aggregate(op) => {
+                for expr in op.agg_calls.iter_mut().chain(op.groupby_exprs.iter_mut()) {
+                    bind(expr)?;
+                }
+            }
+            Operator::Filter(op) => bind(&mut op.predicate)?,
+            Operator::Join(op) => {
+                if let JoinCondition::On { on, filter } = &mut op.on {
+                    for (left_expr, right_expr) in on {
+                        bind(left_expr)?;
+                        bind(right_expr)?;
+                    }
+                    if let Some(filter_expr) = filter {
+                        bind(filter_expr)?;
+                    }
+                }
+            }
+            Operator::Project(op) => {
+                for expr in op.exprs.iter_mut() {
+                    bind(expr)?;
+                }
+            }
+            Operator::Sort(op) => {
+                for field in op.sort_fields.iter_mut() {
+                    bind(&mut field.expr)?;
+                }
+            }
+            Operator::Window(op) => {
+                for expr in op.window_calls.iter_mut() {
+                    bind(expr)?;
+                }
+            }
+            Operator::TableFunction(op) => {
+                for expr in op.function.args.iter_mut() {
+                    bind(expr)?;
+                }
+            }
+            Operator::Values(ValuesOperator {
+                rows,
+                schema_ref,
+                placeholders,
+            }) => {
+                for (row, column, index) in placeholders.iter() {
+                    let param = params.get(*index).ok_or_else(|| {
+                        DatabaseError::InvalidPlaceholder(format!("${}", index + 1))
+                    })?;
+                    let ty = schema_ref[*column].datatype();
+                    param.check_len(ty)?;
+
+                    rows[*row][*column] = Arc::new(DataValue::clone(param).cast(ty)?);
+                }
+                return Ok(!placeholders.is_empty());
+            }
+            Operator::Insert(op) => {
+                if let Some(OnConflict {
+                    action:
+                        OnConflictAction::DoUpdate {
+                            value_exprs,
+                            selection,
+                            ..
+                        },
+                    ..
+                }) = &mut op.on_conflict
+                {
+                    for (_, expr) in value_exprs.iter_mut() {
+                        bind(expr)?;
+                    }
+                    if let Some(expr) = selection {
+                        bind(expr)?;
+                    }
+                }
+                for expr in op.returning.iter_mut() {
+                    bind(expr)?;
+                }
+            }
+            Operator::Update(op) => {
+                for (_, expr) in op.value_exprs.iter_mut() {
+                    bind(expr)?;
+                }
+                for expr in op.returning.iter_mut() {
+                    bind(expr)?;
+                }
+            }
+            Operator::Delete(op) => {
+                for expr in op.returning.iter_mut() {
+                    bind(expr)?;
+                }
+            }
+            _ => (),
+        }
+
+        Ok(has_placeholder)
+    }
+
     pub fn referenced_columns(&self, only_column_ref: bool) -> Vec<ColumnRef> {
         match self {
             Operator::Aggregate(op) => op
@@ -203,7 +392,15 @@ impl Operator {
                 .map(|field| &field.expr)
                 .flat_map(|expr| expr.referenced_columns(only_column_ref))
                 .collect_vec(),
-            Operator::Values(ValuesOperator { schema_ref, .. }) => Vec::clone(schema_ref),
+            Operator::Values(ValuesOperator { schema_ref, .. })
+            | Operator::TableFunction(TableFunctionOperator { schema_ref, .. }) => {
+                Vec::clone(schema_ref)
+            }
+            Operator::Window(op) => op
+                .window_calls
+                .iter()
+                .flat_map(|expr| expr.referenced_columns(only_column_ref))
+                .collect_vec(),
             Operator::Union(UnionOperator {
                 left_schema_ref,
                 right_schema_ref,
@@ -212,19 +409,66 @@ impl Operator {
                 schema.extend_from_slice(right_schema_ref.as_slice());
                 schema
             }
+            Operator::Intersect(IntersectOperator {
+                left_schema_ref,
+                right_schema_ref,
+                ..
+            })
+            | Operator::Except(ExceptOperator {
+                left_schema_ref,
+                right_schema_ref,
+                ..
+            }) => {
+                let mut schema = Vec::clone(left_schema_ref);
+                schema.extend_from_slice(right_schema_ref.as_slice());
+                schema
+            }
+            Operator::RecursiveCte(RecursiveCteOperator {
+                schema_ref,
+                anchor_schema_ref,
+                recursive_schema_ref,
+                ..
+            }) => schema_ref
+                .iter()
+                .chain(anchor_schema_ref.iter())
+                .chain(recursive_schema_ref.iter())
+                .cloned()
+                .collect_vec(),
             Operator::Analyze(op) => op.columns.clone(),
-            Operator::Delete(op) => vec![op.primary_key_column.clone()],
+            Operator::Delete(op) => op
+                .primary_keys
+                .iter()
+                .cloned()
+                .chain(
+                    op.returning
+                        .iter()
+                        .flat_map(|expr| expr.referenced_columns(only_column_ref)),
+                )
+                .collect_vec(),
+            Operator::Update(op) => op
+                .value_exprs
+                .iter()
+                .map(|(_, expr)| expr)
+                .chain(op.returning.iter())
+                .flat_map(|expr| expr.referenced_columns(only_column_ref))
+                .collect_vec(),
             Operator::Dummy
             | Operator::Limit(_)
             | Operator::Show
             | Operator::Explain
             | Operator::Describe(_)
             | Operator::Insert(_)
-            | Operator::Update(_)
             | Operator::AddColumn(_)
             | Operator::DropColumn(_)
             | Operator::CreateTable(_)
             | Operator::DropTable(_)
+            | Operator::CreateIndex(_)
+            | Operator::DropIndex(_)
+            | Operator::CreateUser(_)
+            | Operator::AlterUser(_)
+            | Operator::DropUser(_)
+            | Operator::Grant(_)
+            | Operator::Revoke(_)
             | Operator::Truncate(_)
             | Operator::CopyFromFile(_)
             | Operator::CopyToFile(_) => vec![],
@@ -244,6 +488,7 @@ impl fmt::Display for Operator {
             Operator::Sort(op) => write!(f, "{}", op),
             Operator::Limit(op) => write!(f, "{}", op),
             Operator::Values(op) => write!(f, "{}", op),
+            Operator::TableFunction(op) => write!(f, "{}", op),
             Operator::Show => write!(f, "Show Tables"),
             Operator::Explain => unreachable!(),
             Operator::Describe(op) => write!(f, "{}", op),
@@ -255,10 +500,21 @@ impl fmt::Display for Operator {
             Operator::DropColumn(op) => write!(f, "{}", op),
             Operator::CreateTable(op) => write!(f, "{}", op),
             Operator::DropTable(op) => write!(f, "{}", op),
+            Operator::CreateIndex(op) => write!(f, "{}", op),
+            Operator::DropIndex(op) => write!(f, "{}", op),
+            Operator::CreateUser(op) => write!(f, "{}", op),
+            Operator::AlterUser(op) => write!(f, "{}", op),
+            Operator::DropUser(op) => write!(f, "{}", op),
+            Operator::Grant(op) => write!(f, "{}", op),
+            Operator::Revoke(op) => write!(f, "{}", op),
             Operator::Truncate(op) => write!(f, "{}", op),
             Operator::CopyFromFile(op) => write!(f, "{}", op),
             Operator::CopyToFile(_) => todo!(),
             Operator::Union(op) => write!(f, "{}", op),
+            Operator::Intersect(op) => write!(f, "{}", op),
+            Operator::Except(op) => write!(f, "{}", op),
+            Operator::RecursiveCte(op) => write!(f, "{}", op),
+            Operator::Window(op) => write!(f, "{}", op),
         }
     }
 }
@@ -271,12 +527,18 @@ impl fmt::Display for PhysicalOption {
             PhysicalOption::HashAggregate => write!(f, "HashAggregate"),
             PhysicalOption::Filter => write!(f, "Filter"),
             PhysicalOption::HashJoin => write!(f, "HashJoin"),
+            PhysicalOption::NestedLoopJoin => write!(f, "NestedLoopJoin"),
+            PhysicalOption::SortMergeJoin => write!(f, "SortMergeJoin"),
+            PhysicalOption::HashIntersect => write!(f, "HashIntersect"),
+            PhysicalOption::HashExcept => write!(f, "HashExcept"),
             PhysicalOption::Project => write!(f, "Project"),
             PhysicalOption::SeqScan => write!(f, "SeqScan"),
             PhysicalOption::IndexScan(index) => write!(f, "IndexScan By {}", index),
             PhysicalOption::RadixSort => write!(f, "RadixSort"),
+            PhysicalOption::Window => write!(f, "Window"),
             PhysicalOption::Limit => write!(f, "Limit"),
             PhysicalOption::Values => write!(f, "Values"),
+            PhysicalOption::TableFunction => write!(f, "TableFunction"),
             PhysicalOption::Insert => write!(f, "Insert"),
             PhysicalOption::Update => write!(f, "Update"),
             PhysicalOption::Delete => write!(f, "Delete"),
@@ -284,6 +546,13 @@ impl fmt::Display for PhysicalOption {
             PhysicalOption::DropColumn => write!(f, "DropColumn"),
             PhysicalOption::CreateTable => write!(f, "CreateTable"),
             PhysicalOption::DropTable => write!(f, "DropTable"),
+            PhysicalOption::CreateIndex => write!(f, "CreateIndex"),
+            PhysicalOption::DropIndex => write!(f, "DropIndex"),
+            PhysicalOption::CreateUser => write!(f, "CreateUser"),
+            PhysicalOption::AlterUser => write!(f, "AlterUser"),
+            PhysicalOption::DropUser => write!(f, "DropUser"),
+            PhysicalOption::Grant => write!(f, "Grant"),
+            PhysicalOption::Revoke => write!(f, "Revoke"),
             PhysicalOption::Truncate => write!(f, "Truncate"),
             PhysicalOption::Show => write!(f, "Show"),
             PhysicalOption::CopyFromFile => write!(f, "CopyFromFile"),
@@ -0,0 +1,69 @@
+use crate::catalog::TableName;
+use crate::planner::operator::values::ValuesOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::types::tuple::SchemaRef;
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::Formatter;
+
+/// `WITH RECURSIVE`: the anchor is the left child and the recursive term is the right child,
+/// in which the references to the common table expression are bound to the working table
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct RecursiveCteOperator {
+    pub table_name: TableName,
+    pub schema_ref: SchemaRef,
+    pub anchor_schema_ref: SchemaRef,
+    pub recursive_schema_ref: SchemaRef,
+    pub is_distinct: bool,
+    /// The times the recursive term can be evaluated at most
+    pub max_recursion_depth: usize,
+}
+
+impl RecursiveCteOperator {
+    pub fn build(
+        op: RecursiveCteOperator,
+        anchor_plan: LogicalPlan,
+        recursive_plan: LogicalPlan,
+    ) -> LogicalPlan {
+        LogicalPlan::new(
+            Operator::RecursiveCte(op),
+            vec![anchor_plan, recursive_plan],
+        )
+    }
+
+    /// The working table holds the rows produced by the last iteration,
+    /// it is empty until the executor fills it in.
+    pub fn working_table(schema_ref: SchemaRef) -> LogicalPlan {
+        LogicalPlan::new(
+            Operator::Values(ValuesOperator {
+                rows: vec![],
+                schema_ref,
+                placeholders: vec![],
+            }),
+            vec![],
+        )
+    }
+
+    pub fn is_working_table(operator: &Operator, schema_ref: &SchemaRef) -> bool {
+        matches!(operator, Operator::Values(op) if &op.schema_ref == schema_ref)
+    }
+}
+
+impl fmt::Display for RecursiveCteOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let columns = self
+            .schema_ref
+            .iter()
+            .map(|column| column.name().to_string())
+            .join(", ");
+
+        write!(
+            f,
+            "RecursiveCTE {} [{}], Distinct: {}",
+            self.table_name, columns, self.is_distinct
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use crate::catalog::{Privilege, TableName};
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct RevokeOperator {
+    pub privileges: Vec<Privilege>,
+    pub table_names: Vec<TableName>,
+    pub user_names: Vec<String>,
+}
+
+impl fmt::Display for RevokeOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Revoke {} On [{}] To [{}]",
+            self.privileges.iter().join(", "),
+            self.table_names.iter().join(", "),
+            self.user_names.join(", ")
+        )?;
+
+        Ok(())
+    }
+}
@@ -12,7 +12,7 @@ use super::Operator;
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct ScanOperator {
     pub table_name: TableName,
-    pub primary_key: ColumnId,
+    pub primary_keys: Vec<ColumnId>,
     pub columns: Vec<(usize, ColumnRef)>,
     // Support push down limit.
     pub limit: Bounds,
@@ -23,19 +23,25 @@ pub struct ScanOperator {
 }
 impl ScanOperator {
     pub fn build(table_name: TableName, table_catalog: &TableCatalog) -> LogicalPlan {
-        let mut primary_key_option = None;
+        let mut primary_keys = Vec::new();
         // Fill all Columns in TableCatalog by default
         let columns = table_catalog
             .columns()
             .enumerate()
             .map(|(i, column)| {
-                if column.desc.is_primary {
-                    primary_key_option = column.id();
+                if let (Some(column_id), Some(pos)) = (column.id(), column.desc.primary) {
+                    primary_keys.push((pos, column_id));
                 }
 
                 (i, column.clone())
             })
             .collect_vec();
+        // Tips: the primary keys follow the declared order as the tuple keys do
+        let primary_keys = primary_keys
+            .into_iter()
+            .sorted_by_key(|(pos, _)| *pos)
+            .map(|(_, column_id)| column_id)
+            .collect_vec();
         let index_infos = table_catalog
             .indexes
             .iter()
@@ -49,7 +55,7 @@ impl ScanOperator {
             Operator::Scan(ScanOperator {
                 index_infos,
                 table_name,
-                primary_key: primary_key_option.unwrap(),
+                primary_keys,
                 columns,
                 limit: (None, None),
             }),
@@ -0,0 +1,40 @@
+use crate::catalog::TableName;
+use crate::expression::function::table::TableFunction;
+use crate::types::tuple::SchemaRef;
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::Formatter;
+
+/// Produces the rows of the table function in `FROM`, whose columns are those of the bound table
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct TableFunctionOperator {
+    pub table_name: TableName,
+    pub function: TableFunction,
+    pub schema_ref: SchemaRef,
+}
+
+impl fmt::Display for TableFunctionOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let args = self
+            .function
+            .args
+            .iter()
+            .map(|arg| arg.output_name())
+            .join(", ");
+        let columns = self
+            .schema_ref
+            .iter()
+            .map(|column| column.name().to_string())
+            .join(", ");
+
+        write!(
+            f,
+            "TableFunction {}({}) -> [{}]",
+            self.function.summary().name,
+            args,
+            columns
+        )?;
+
+        Ok(())
+    }
+}
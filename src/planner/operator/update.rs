@@ -1,15 +1,31 @@
-use crate::catalog::TableName;
+use crate::catalog::{ColumnRef, TableName};
+use crate::expression::ScalarExpression;
+use itertools::Itertools;
 use std::fmt;
 use std::fmt::Formatter;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct UpdateOperator {
     pub table_name: TableName,
+    /// The columns to be updated and their values, which are evaluated on the tuples of the input
+    pub value_exprs: Vec<(ColumnRef, ScalarExpression)>,
+    /// `RETURNING`, which is evaluated on the updated tuples,
+    /// the affected rows are counted instead if empty
+    pub returning: Vec<ScalarExpression>,
 }
 
 impl fmt::Display for UpdateOperator {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "Update {}", self.table_name)?;
+        let values = self
+            .value_exprs
+            .iter()
+            .map(|(column, expr)| format!("{} = {}", column.name(), expr))
+            .join(", ");
+
+        write!(f, "Update {} -> [{}]", self.table_name, values)?;
+        if !self.returning.is_empty() {
+            write!(f, ", Returning: [{}]", self.returning.iter().join(", "))?;
+        }
 
         Ok(())
     }
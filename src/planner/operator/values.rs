@@ -8,6 +8,8 @@ use std::fmt::Formatter;
 pub struct ValuesOperator {
     pub rows: Vec<Vec<ValueRef>>,
     pub schema_ref: SchemaRef,
+    /// The `$n` parameters in the rows as `(row, column, n - 1)`, which are NULL until they are bound
+    pub placeholders: Vec<(usize, usize, usize)>,
 }
 
 impl fmt::Display for ValuesOperator {
@@ -0,0 +1,33 @@
+use crate::planner::LogicalPlan;
+use crate::{expression::ScalarExpression, planner::operator::Operator};
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::Formatter;
+
+/// Appends the results of the window functions to each row of the child
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct WindowOperator {
+    pub window_calls: Vec<ScalarExpression>,
+}
+
+impl WindowOperator {
+    pub fn build(children: LogicalPlan, window_calls: Vec<ScalarExpression>) -> LogicalPlan {
+        LogicalPlan::new(
+            Operator::Window(WindowOperator { window_calls }),
+            vec![children],
+        )
+    }
+}
+
+impl fmt::Display for WindowOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let calls = self
+            .window_calls
+            .iter()
+            .map(|call| format!("{}", call))
+            .join(", ");
+        write!(f, "Window [{}]", calls)?;
+
+        Ok(())
+    }
+}
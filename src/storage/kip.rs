@@ -1,10 +1,12 @@
-use crate::catalog::{ColumnCatalog, ColumnRef, TableCatalog, TableMeta, TableName};
+use crate::catalog::{
+    ColumnCatalog, ColumnRef, Privilege, TableCatalog, TableMeta, TableName, UserCatalog,
+};
 use crate::errors::DatabaseError;
 use crate::expression::simplify::ConstantBinary;
 use crate::optimizer::core::column_meta::{ColumnMeta, ColumnMetaLoader};
 use crate::storage::table_codec::TableCodec;
 use crate::storage::{Bounds, IndexIter, Iter, Storage, Transaction};
-use crate::types::index::{Index, IndexMetaRef};
+use crate::types::index::{Index, IndexId, IndexMetaRef};
 use crate::types::tuple::{Tuple, TupleId};
 use crate::types::{ColumnId, LogicalType};
 use itertools::Itertools;
@@ -77,8 +79,9 @@ impl Transaction for KipTransaction {
             .ok_or(DatabaseError::TableNotFound)?;
         let table_types = table.types();
         if columns.is_empty() {
-            let (i, column) = table.primary_key()?;
-            columns.push((i, column.clone()));
+            for (i, column) in table.primary_keys()? {
+                columns.push((i, column.clone()));
+            }
         }
         let mut tuple_columns = Vec::with_capacity(columns.len());
         let mut projections = Vec::with_capacity(columns.len());
@@ -144,31 +147,95 @@ impl Transaction for KipTransaction {
         tuple_ids: Vec<TupleId>,
         is_unique: bool,
     ) -> Result<(), DatabaseError> {
-        let (key, value) = TableCodec::encode_index(table_name, &index, &tuple_ids)?;
+        for tuple_id in tuple_ids {
+            let (key, value) = TableCodec::encode_index(table_name, &index, &tuple_id, is_unique)?;
 
-        if let Some(bytes) = self.tx.get(&key)? {
+            // the non-unique index has a key for each tuple, so only the unique index needs to be checked
             if is_unique {
-                let old_tuple_ids = TableCodec::decode_index(&bytes)?;
-
-                return if old_tuple_ids[0] != tuple_ids[0] {
-                    Err(DatabaseError::DuplicateUniqueValue)
-                } else {
-                    Ok(())
-                };
-            } else {
-                todo!("联合索引")
+                if let Some(bytes) = self.tx.get(&key)? {
+                    if TableCodec::decode_index(&bytes)?[0] != tuple_id {
+                        return Err(DatabaseError::DuplicateUniqueValue);
+                    }
+                    continue;
+                }
             }
+            self.tx.set(key, value);
         }
 
-        self.tx.set(key, value);
+        Ok(())
+    }
+
+    fn del_index(
+        &mut self,
+        table_name: &str,
+        index: &Index,
+        tuple_id: &TupleId,
+        is_unique: bool,
+    ) -> Result<(), DatabaseError> {
+        let key =
+            TableCodec::encode_index_key(table_name, index, (!is_unique).then_some(tuple_id))?;
+
+        if let Some(bytes) = self.tx.get(&key)? {
+            // the key of the unique index may have been taken by another tuple
+            if !is_unique || TableCodec::decode_index(&bytes)?.contains(tuple_id) {
+                self.tx.remove(&key)?;
+            }
+        }
 
         Ok(())
     }
 
-    fn del_index(&mut self, table_name: &str, index: &Index) -> Result<(), DatabaseError> {
-        let key = TableCodec::encode_index_key(table_name, index)?;
+    fn add_index_meta(
+        &mut self,
+        table_name: &TableName,
+        index_name: String,
+        column_ids: Vec<ColumnId>,
+        is_unique: bool,
+    ) -> Result<IndexId, DatabaseError> {
+        if let Some(mut table) = self.table(table_name.clone()).cloned() {
+            if table.get_index_by_name(&index_name).is_some() {
+                return Err(DatabaseError::Duplicated("index", index_name));
+            }
+            let meta_ref = table.add_index_meta(index_name, column_ids, is_unique, false);
+            let index_id = meta_ref.id;
+            let (key, value) = TableCodec::encode_index_meta(table_name, meta_ref)?;
+            self.tx.set(key, value);
+            self.table_cache.remove(table_name);
 
-        self.tx.remove(&key)?;
+            Ok(index_id)
+        } else {
+            Err(DatabaseError::TableNotFound)
+        }
+    }
+
+    fn drop_index(
+        &mut self,
+        table_name: &TableName,
+        index_name: &str,
+        if_exists: bool,
+    ) -> Result<(), DatabaseError> {
+        let table = self
+            .table(table_name.clone())
+            .ok_or(DatabaseError::TableNotFound)?;
+        let index_meta = match table.get_index_by_name(index_name) {
+            Some(index_meta) => index_meta.clone(),
+            None => {
+                return if if_exists {
+                    Ok(())
+                } else {
+                    Err(DatabaseError::NotFound("index", index_name.to_string()))
+                };
+            }
+        };
+        if index_meta.is_primary {
+            return Err(DatabaseError::InvalidIndex);
+        }
+        let (index_meta_key, _) = TableCodec::encode_index_meta(table_name, &index_meta)?;
+        self.tx.remove(&index_meta_key)?;
+
+        let (index_min, index_max) = TableCodec::index_bound(table_name, &index_meta.id);
+        Self::_drop_data(&mut self.tx, &index_min, &index_max)?;
+        self.table_cache.remove(table_name);
 
         Ok(())
     }
@@ -249,7 +316,7 @@ impl Transaction for KipTransaction {
         if let Some(catalog) = self.table(table_name.clone()).cloned() {
             let column = catalog.get_column_by_name(column_name).unwrap();
 
-            if let Some(index_meta) = catalog.get_unique_index(&column.id().unwrap()) {
+            for index_meta in catalog.indexes_by_column(&column.id().unwrap()) {
                 let (index_meta_key, _) = TableCodec::encode_index_meta(table_name, index_meta)?;
                 self.tx.remove(&index_meta_key)?;
 
@@ -323,6 +390,9 @@ impl Transaction for KipTransaction {
         self.tx
             .remove(&TableCodec::encode_root_table_key(table_name))?;
 
+        let (privilege_min, privilege_max) = TableCodec::privilege_bound(table_name);
+        Self::_drop_data(&mut self.tx, &privilege_min, &privilege_max)?;
+
         let _ = self.table_cache.remove(&table_name.to_string());
 
         Ok(())
@@ -400,6 +470,122 @@ impl Transaction for KipTransaction {
         ColumnMetaLoader::new(self, &self.meta_cache)
     }
 
+    fn create_user(&mut self, user: UserCatalog, if_not_exists: bool) -> Result<(), DatabaseError> {
+        if self.user(&user.name)?.is_some() {
+            if if_not_exists {
+                return Ok(());
+            }
+            return Err(DatabaseError::Duplicated("user", user.name));
+        }
+        let (key, value) = TableCodec::encode_user(&user)?;
+        self.tx.set(key, value);
+
+        Ok(())
+    }
+
+    fn alter_user(&mut self, user: UserCatalog) -> Result<(), DatabaseError> {
+        if self.user(&user.name)?.is_none() {
+            return Err(DatabaseError::NotFound("user", user.name));
+        }
+        let (key, value) = TableCodec::encode_user(&user)?;
+        self.tx.set(key, value);
+
+        Ok(())
+    }
+
+    fn drop_user(&mut self, user_name: &str, if_exists: bool) -> Result<(), DatabaseError> {
+        if self.user(user_name)?.is_none() {
+            if if_exists {
+                return Ok(());
+            }
+            return Err(DatabaseError::NotFound("user", user_name.to_string()));
+        }
+        self.tx.remove(&TableCodec::encode_user_key(user_name))?;
+
+        let (privilege_min, privilege_max) = TableCodec::all_privilege_bound();
+        let mut iter = self.tx.iter(
+            Bound::Included(&privilege_min),
+            Bound::Included(&privilege_max),
+        )?;
+        let mut privilege_keys = vec![];
+
+        while let Some((key, value_option)) = iter.try_next()? {
+            if value_option.is_some()
+                && TableCodec::decode_privilege_user(&key) == Some(user_name.as_bytes())
+            {
+                privilege_keys.push(key);
+            }
+        }
+        drop(iter);
+
+        for key in privilege_keys {
+            self.tx.remove(&key)?;
+        }
+
+        Ok(())
+    }
+
+    fn user(&self, user_name: &str) -> Result<Option<UserCatalog>, DatabaseError> {
+        self.tx
+            .get(&TableCodec::encode_user_key(user_name))?
+            .map(|bytes| TableCodec::decode_user(&bytes))
+            .transpose()
+    }
+
+    fn grant(
+        &mut self,
+        user_name: &str,
+        table_name: &str,
+        privileges: &[Privilege],
+    ) -> Result<(), DatabaseError> {
+        self.check_privilege_target(user_name, table_name)?;
+
+        let mut granted = self.privileges(user_name, table_name)?;
+        granted.extend_from_slice(privileges);
+        granted.sort();
+        granted.dedup();
+
+        let (key, value) = TableCodec::encode_privileges(table_name, user_name, &granted)?;
+        self.tx.set(key, value);
+
+        Ok(())
+    }
+
+    fn revoke(
+        &mut self,
+        user_name: &str,
+        table_name: &str,
+        privileges: &[Privilege],
+    ) -> Result<(), DatabaseError> {
+        self.check_privilege_target(user_name, table_name)?;
+
+        let mut granted = self.privileges(user_name, table_name)?;
+        granted.retain(|privilege| !privileges.contains(privilege));
+
+        if granted.is_empty() {
+            self.tx
+                .remove(&TableCodec::encode_privilege_key(table_name, user_name))?;
+        } else {
+            let (key, value) = TableCodec::encode_privileges(table_name, user_name, &granted)?;
+            self.tx.set(key, value);
+        }
+
+        Ok(())
+    }
+
+    fn privileges(
+        &self,
+        user_name: &str,
+        table_name: &str,
+    ) -> Result<Vec<Privilege>, DatabaseError> {
+        Ok(self
+            .tx
+            .get(&TableCodec::encode_privilege_key(table_name, user_name))?
+            .map(|bytes| TableCodec::decode_privileges(&bytes))
+            .transpose()?
+            .unwrap_or_default())
+    }
+
     async fn commit(self) -> Result<(), DatabaseError> {
         self.tx.commit().await?;
 
@@ -408,6 +594,21 @@ impl Transaction for KipTransaction {
 }
 
 impl KipTransaction {
+    fn check_privilege_target(
+        &self,
+        user_name: &str,
+        table_name: &str,
+    ) -> Result<(), DatabaseError> {
+        if self.user(user_name)?.is_none() {
+            return Err(DatabaseError::NotFound("user", user_name.to_string()));
+        }
+        if self.table(Arc::new(table_name.to_string())).is_none() {
+            return Err(DatabaseError::TableNotFound);
+        }
+
+        Ok(())
+    }
+
     fn table_collect(
         table_name: TableName,
         tx: &mvcc::Transaction,
@@ -455,23 +656,35 @@ impl KipTransaction {
         table: &mut TableCatalog,
     ) -> Result<(), DatabaseError> {
         let table_name = table.name.clone();
+        let (primary_keys, primary_names): (Vec<_>, Vec<_>) = table
+            .primary_keys()?
+            .into_iter()
+            .map(|(_, column)| (column.id().unwrap(), column.name().to_string()))
+            .unzip();
         let index_column = table
             .columns()
             .filter(|column| column.desc.is_index())
             .map(|column| (column.id().unwrap(), column.clone()))
             .collect_vec();
+        let mut is_primary_created = false;
 
         for (col_id, col) in index_column {
-            let is_primary = col.desc.is_primary;
-            // FIXME: composite indexes may exist on future
-            let prefix = if is_primary { "pk" } else { "uk" };
-
-            let meta_ref = table.add_index_meta(
-                format!("{}_{}", prefix, col.name()),
-                vec![col_id],
-                col.desc.is_unique,
-                is_primary,
-            );
+            let meta_ref = if col.desc.is_primary() {
+                // the primary key index covers all primary key columns at the first of them
+                if is_primary_created {
+                    continue;
+                }
+                is_primary_created = true;
+
+                table.add_index_meta(
+                    format!("pk_{}", primary_names.join("_")),
+                    primary_keys.clone(),
+                    false,
+                    true,
+                )
+            } else {
+                table.add_index_meta(format!("uk_{}", col.name()), vec![col_id], true, false)
+            };
             let (key, value) = TableCodec::encode_index_meta(&table_name, meta_ref)?;
             tx.set(key, value);
         }
@@ -524,7 +737,7 @@ impl Iter for KipIter<'_> {
 
 #[cfg(test)]
 mod test {
-    use crate::catalog::{ColumnCatalog, ColumnDesc};
+    use crate::catalog::{ColumnCatalog, ColumnDesc, Privilege, UserCatalog, UserPassword};
     use crate::db::DataBaseBuilder;
     use crate::errors::DatabaseError;
     use crate::expression::simplify::ConstantBinary;
@@ -711,4 +924,239 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_read_by_composite_index() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build().await?;
+        let _ = fnck_sql
+            .run("create table t1 (a int, b int, c int, primary key (a, b), unique (b, c))")
+            .await?;
+        let _ = fnck_sql
+            .run("insert into t1 (a, b, c) values (0, 0, 0), (0, 1, 1), (1, 0, 2), (1, 1, 3), (2, 0, 4)")
+            .await?;
+        let transaction = fnck_sql.storage.transaction().await.unwrap();
+
+        let table = transaction
+            .table(Arc::new("t1".to_string()))
+            .unwrap()
+            .clone();
+        assert_eq!(table.indexes.len(), 2);
+        assert_eq!(table.indexes[0].column_ids, vec![0, 1]);
+        assert_eq!(table.indexes[1].column_ids, vec![1, 2]);
+
+        let tuple_value = |values: Vec<i32>| {
+            Arc::new(DataValue::Tuple(Some(
+                values
+                    .into_iter()
+                    .map(|value| Arc::new(DataValue::Int32(Some(value))))
+                    .collect_vec(),
+            )))
+        };
+        let read_ids = |index: usize, binaries: Vec<ConstantBinary>| {
+            let columns = table.columns().cloned().enumerate().collect_vec();
+            let mut iter = transaction.read_by_index(
+                Arc::new("t1".to_string()),
+                (None, None),
+                columns,
+                table.indexes[index].clone(),
+                binaries,
+            )?;
+            let mut ids = Vec::new();
+
+            while let Some(tuple) = iter.next_tuple()? {
+                ids.push(tuple.id.unwrap());
+            }
+            Ok::<_, DatabaseError>(ids)
+        };
+
+        // prefix: a = 1
+        assert_eq!(
+            read_ids(0, vec![ConstantBinary::Eq(tuple_value(vec![1]))])?,
+            vec![tuple_value(vec![1, 0]), tuple_value(vec![1, 1])]
+        );
+        // full key: a = 0 and b = 1
+        assert_eq!(
+            read_ids(0, vec![ConstantBinary::Eq(tuple_value(vec![0, 1]))])?,
+            vec![tuple_value(vec![0, 1])]
+        );
+        // prefix range: a > 0 and a <= 1
+        assert_eq!(
+            read_ids(
+                0,
+                vec![ConstantBinary::Scope {
+                    min: Bound::Excluded(tuple_value(vec![0])),
+                    max: Bound::Included(tuple_value(vec![1])),
+                }]
+            )?,
+            vec![tuple_value(vec![1, 0]), tuple_value(vec![1, 1])]
+        );
+        // a = 0 and b > 0
+        assert_eq!(
+            read_ids(
+                0,
+                vec![ConstantBinary::Scope {
+                    min: Bound::Excluded(tuple_value(vec![0, 0])),
+                    max: Bound::Included(tuple_value(vec![0])),
+                }]
+            )?,
+            vec![tuple_value(vec![0, 1])]
+        );
+        // composite unique index: b = 0 and c >= 2
+        assert_eq!(
+            read_ids(
+                1,
+                vec![ConstantBinary::Scope {
+                    min: Bound::Included(tuple_value(vec![0, 2])),
+                    max: Bound::Included(tuple_value(vec![0])),
+                }]
+            )?,
+            vec![tuple_value(vec![1, 0]), tuple_value(vec![2, 0])]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_by_non_unique_index() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build().await?;
+        let _ = fnck_sql
+            .run("create table t1 (a int primary key, b int)")
+            .await?;
+        let _ = fnck_sql
+            .run("insert into t1 (a, b) values (0, 0), (1, 1), (2, 1), (3, 2)")
+            .await?;
+        let _ = fnck_sql.run("create index idx_b on t1 (b)").await?;
+        let _ = fnck_sql
+            .run("insert into t1 (a, b) values (4, 1), (5, 2)")
+            .await?;
+        let _ = fnck_sql.run("delete from t1 where a = 2").await?;
+        let transaction = fnck_sql.storage.transaction().await.unwrap();
+
+        let table = transaction
+            .table(Arc::new("t1".to_string()))
+            .unwrap()
+            .clone();
+        let index_meta = table.get_index_by_name("idx_b").unwrap().clone();
+        assert!(!index_meta.is_unique);
+        assert!(!index_meta.is_primary);
+
+        let read_ids = |binaries: Vec<ConstantBinary>| {
+            let columns = table.columns().cloned().enumerate().collect_vec();
+            let mut iter = transaction.read_by_index(
+                Arc::new("t1".to_string()),
+                (None, None),
+                columns,
+                index_meta.clone(),
+                binaries,
+            )?;
+            let mut ids = Vec::new();
+
+            while let Some(tuple) = iter.next_tuple()? {
+                ids.push(tuple.id.unwrap());
+            }
+            Ok::<_, DatabaseError>(ids)
+        };
+        let int_value = |value: i32| Arc::new(DataValue::Int32(Some(value)));
+
+        assert_eq!(
+            read_ids(vec![ConstantBinary::Eq(int_value(1))])?,
+            vec![int_value(1), int_value(4)]
+        );
+        assert_eq!(
+            read_ids(vec![ConstantBinary::Scope {
+                min: Bound::Excluded(int_value(0)),
+                max: Bound::Unbounded,
+            }])?,
+            vec![int_value(1), int_value(4), int_value(3), int_value(5)]
+        );
+
+        drop(transaction);
+        let _ = fnck_sql.run("drop index t1.idx_b").await?;
+        let transaction = fnck_sql.storage.transaction().await.unwrap();
+        let table = transaction.table(Arc::new("t1".to_string())).unwrap();
+        assert!(table.get_index_by_name("idx_b").is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_user_catalog() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = KipStorage::new(temp_dir.path()).await?;
+        let mut transaction = storage.transaction().await?;
+        let user = UserCatalog::new("kip".to_string(), Some(UserPassword::new("pencil")), false);
+
+        transaction.create_user(user.clone(), false)?;
+        assert!(transaction.create_user(user.clone(), false).is_err());
+        transaction.create_user(user.clone(), true)?;
+        transaction.commit().await?;
+
+        let mut transaction = storage.transaction().await?;
+        assert_eq!(transaction.user("kip")?, Some(user));
+        assert_eq!(transaction.user("fnck")?, None);
+
+        transaction.alter_user(UserCatalog::new("kip".to_string(), None, false))?;
+        assert_eq!(transaction.user("kip")?.unwrap().password, None);
+        assert!(transaction
+            .alter_user(UserCatalog::new("fnck".to_string(), None, false))
+            .is_err());
+
+        transaction.drop_user("kip", false)?;
+        assert_eq!(transaction.user("kip")?, None);
+        assert!(transaction.drop_user("kip", false).is_err());
+        transaction.drop_user("kip", true)?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_privilege_catalog() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build().await?;
+        let _ = fnck_sql.run("create table t1 (a int primary key)").await?;
+        let _ = fnck_sql.run("create table t2 (a int primary key)").await?;
+
+        let mut transaction = fnck_sql.storage.transaction().await?;
+        transaction.create_user(UserCatalog::new("kip".to_string(), None, false), false)?;
+        transaction.create_user(UserCatalog::new("fnck".to_string(), None, false), false)?;
+
+        transaction.grant("kip", "t1", &[Privilege::Select, Privilege::Insert])?;
+        transaction.grant("kip", "t1", &[Privilege::Insert, Privilege::Delete])?;
+        transaction.grant("kip", "t2", &Privilege::ALL)?;
+        transaction.grant("fnck", "t1", &[Privilege::Select])?;
+        assert!(transaction
+            .grant("sql", "t1", &[Privilege::Select])
+            .is_err());
+        assert!(transaction
+            .grant("kip", "t3", &[Privilege::Select])
+            .is_err());
+
+        assert_eq!(
+            transaction.privileges("kip", "t1")?,
+            vec![Privilege::Select, Privilege::Insert, Privilege::Delete]
+        );
+        assert_eq!(transaction.privileges("fnck", "t2")?, vec![]);
+
+        transaction.revoke("kip", "t1", &[Privilege::Select])?;
+        assert_eq!(
+            transaction.privileges("kip", "t1")?,
+            vec![Privilege::Insert, Privilege::Delete]
+        );
+        transaction.commit().await?;
+
+        let _ = fnck_sql.run("drop table t2").await?;
+        let mut transaction = fnck_sql.storage.transaction().await?;
+        assert_eq!(transaction.privileges("kip", "t2")?, vec![]);
+
+        transaction.drop_user("kip", false)?;
+        assert_eq!(transaction.privileges("kip", "t1")?, vec![]);
+        assert_eq!(
+            transaction.privileges("fnck", "t1")?,
+            vec![Privilege::Select]
+        );
+
+        Ok(())
+    }
 }
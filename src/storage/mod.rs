@@ -1,18 +1,21 @@
 pub mod kip;
 mod table_codec;
 
-use crate::catalog::{ColumnCatalog, ColumnRef, TableCatalog, TableMeta, TableName};
+use crate::catalog::{
+    ColumnCatalog, ColumnRef, Privilege, TableCatalog, TableMeta, TableName, UserCatalog,
+};
 use crate::errors::DatabaseError;
 use crate::expression::simplify::ConstantBinary;
 use crate::optimizer::core::column_meta::ColumnMetaLoader;
 use crate::storage::table_codec::TableCodec;
-use crate::types::index::{Index, IndexMetaRef};
+use crate::types::index::{Index, IndexId, IndexMetaRef};
 use crate::types::tuple::{Tuple, TupleId};
-use crate::types::value::ValueRef;
+use crate::types::value::{DataValue, ValueRef};
 use crate::types::ColumnId;
 use kip_db::kernel::lsm::iterator::Iter as DBIter;
 use kip_db::kernel::lsm::mvcc;
 use std::collections::{Bound, VecDeque};
+use std::future::Future;
 use std::mem;
 use std::ops::SubAssign;
 use std::sync::Arc;
@@ -57,7 +60,28 @@ pub trait Transaction: Sync + Send + 'static {
         is_unique: bool,
     ) -> Result<(), DatabaseError>;
 
-    fn del_index(&mut self, table_name: &str, index: &Index) -> Result<(), DatabaseError>;
+    fn del_index(
+        &mut self,
+        table_name: &str,
+        index: &Index,
+        tuple_id: &TupleId,
+        is_unique: bool,
+    ) -> Result<(), DatabaseError>;
+
+    fn add_index_meta(
+        &mut self,
+        table_name: &TableName,
+        index_name: String,
+        column_ids: Vec<ColumnId>,
+        is_unique: bool,
+    ) -> Result<IndexId, DatabaseError>;
+
+    fn drop_index(
+        &mut self,
+        table_name: &TableName,
+        index_name: &str,
+        if_exists: bool,
+    ) -> Result<(), DatabaseError>;
 
     fn append(
         &mut self,
@@ -99,8 +123,31 @@ pub trait Transaction: Sync + Send + 'static {
     where
         Self: Sized;
 
-    #[allow(async_fn_in_trait)]
-    async fn commit(self) -> Result<(), DatabaseError>;
+    fn create_user(&mut self, user: UserCatalog, if_not_exists: bool) -> Result<(), DatabaseError>;
+    fn alter_user(&mut self, user: UserCatalog) -> Result<(), DatabaseError>;
+    fn drop_user(&mut self, user_name: &str, if_exists: bool) -> Result<(), DatabaseError>;
+    fn user(&self, user_name: &str) -> Result<Option<UserCatalog>, DatabaseError>;
+
+    fn grant(
+        &mut self,
+        user_name: &str,
+        table_name: &str,
+        privileges: &[Privilege],
+    ) -> Result<(), DatabaseError>;
+    fn revoke(
+        &mut self,
+        user_name: &str,
+        table_name: &str,
+        privileges: &[Privilege],
+    ) -> Result<(), DatabaseError>;
+    fn privileges(
+        &self,
+        user_name: &str,
+        table_name: &str,
+    ) -> Result<Vec<Privilege>, DatabaseError>;
+
+    /// Tips: the future is `Send` so that the streams holding the transaction can commit it
+    fn commit(self) -> impl Future<Output = Result<(), DatabaseError>> + Send;
 }
 
 enum IndexValue {
@@ -137,12 +184,36 @@ impl IndexIter<'_> {
     }
 
     fn val_to_key(&self, val: ValueRef) -> Result<Vec<u8>, DatabaseError> {
-        if self.index_meta.is_unique {
-            let index = Index::new(self.index_meta.id, vec![val]);
-
-            TableCodec::encode_index_key(&self.table.name, &index)
-        } else {
+        if self.index_meta.is_primary {
             TableCodec::encode_tuple_key(&self.table.name, &val)
+        } else {
+            let column_values = match val.as_ref() {
+                DataValue::Tuple(Some(values)) => values.clone(),
+                _ => vec![val],
+            };
+            let index = Index::new(self.index_meta.id, column_values);
+
+            TableCodec::encode_index_key(&self.table.name, &index, None)
+        }
+    }
+
+    fn is_non_unique(&self) -> bool {
+        !self.index_meta.is_primary && !self.index_meta.is_unique
+    }
+
+    /// The values of composite indexes are `DataValue::Tuple`, which may only contain the leading columns,
+    /// and the keys of non-unique indexes always end with the TupleID after the values
+    fn is_prefix(&self, val: &ValueRef) -> bool {
+        self.is_non_unique() || matches!(val.as_ref(), DataValue::Tuple(_))
+    }
+
+    fn is_full_key(&self, val: &ValueRef) -> bool {
+        if self.is_non_unique() {
+            return false;
+        }
+        match val.as_ref() {
+            DataValue::Tuple(Some(values)) => values.len() == self.index_meta.column_ids.len(),
+            _ => true,
         }
     }
 
@@ -232,28 +303,47 @@ impl Iter for IndexIter<'_> {
                     let table_name = &self.table.name;
                     let index_meta = &self.index_meta;
 
-                    let bound_encode = |bound: Bound<ValueRef>| -> Result<_, DatabaseError> {
-                        match bound {
-                            Bound::Included(val) => Ok(Bound::Included(self.val_to_key(val)?)),
-                            Bound::Excluded(val) => Ok(Bound::Excluded(self.val_to_key(val)?)),
-                            Bound::Unbounded => Ok(Bound::Unbounded),
-                        }
-                    };
+                    let bound_encode =
+                        |bound: Bound<ValueRef>, is_max: bool| -> Result<_, DatabaseError> {
+                            match bound {
+                                Bound::Included(val) => {
+                                    let is_prefix = self.is_prefix(&val);
+                                    let mut key = self.val_to_key(val)?;
+
+                                    // all keys with the prefix are less than or equal to the max bound
+                                    if is_prefix && is_max {
+                                        key = TableCodec::prefix_upper_bound(key);
+                                    }
+                                    Ok(Bound::Included(key))
+                                }
+                                Bound::Excluded(val) => {
+                                    let is_prefix = self.is_prefix(&val);
+                                    let mut key = self.val_to_key(val)?;
+
+                                    // all keys with the prefix are less than or equal to the min bound
+                                    if is_prefix && !is_max {
+                                        key = TableCodec::prefix_upper_bound(key);
+                                    }
+                                    Ok(Bound::Excluded(key))
+                                }
+                                Bound::Unbounded => Ok(Bound::Unbounded),
+                            }
+                        };
                     let check_bound = |value: &mut Bound<Vec<u8>>, bound: Vec<u8>| {
                         if matches!(value, Bound::Unbounded) {
                             let _ = mem::replace(value, Bound::Included(bound));
                         }
                     };
-                    let (bound_min, bound_max) = if index_meta.is_unique {
-                        TableCodec::index_bound(table_name, &index_meta.id)
-                    } else {
+                    let (bound_min, bound_max) = if index_meta.is_primary {
                         TableCodec::tuple_bound(table_name)
+                    } else {
+                        TableCodec::index_bound(table_name, &index_meta.id)
                     };
 
-                    let mut encode_min = bound_encode(min)?;
+                    let mut encode_min = bound_encode(min, false)?;
                     check_bound(&mut encode_min, bound_min);
 
-                    let mut encode_max = bound_encode(max)?;
+                    let mut encode_max = bound_encode(max, true)?;
                     check_bound(&mut encode_max, bound_max);
 
                     let iter = self.tx.iter(
@@ -262,14 +352,18 @@ impl Iter for IndexIter<'_> {
                     )?;
                     self.scope_iter = Some(iter);
                 }
+                // Prefix of the composite index or the non-unique index: scan all keys beginning with the values
+                ConstantBinary::Eq(val) if !self.is_full_key(&val) => {
+                    let min = self.val_to_key(val)?;
+                    let max = TableCodec::prefix_upper_bound(min.clone());
+
+                    let iter = self.tx.iter(Bound::Included(&min), Bound::Included(&max))?;
+                    self.scope_iter = Some(iter);
+                }
                 ConstantBinary::Eq(val) => {
                     let key = self.val_to_key(val)?;
                     if let Some(bytes) = self.tx.get(&key)? {
-                        if self.index_meta.is_unique {
-                            for tuple_id in TableCodec::decode_index(&bytes)? {
-                                self.index_values.push_back(IndexValue::Normal(tuple_id));
-                            }
-                        } else if self.index_meta.is_primary {
+                        if self.index_meta.is_primary {
                             let tuple = TableCodec::decode_tuple(
                                 &self.table.types(),
                                 &self.projections,
@@ -279,7 +373,9 @@ impl Iter for IndexIter<'_> {
 
                             self.index_values.push_back(IndexValue::PrimaryKey(tuple));
                         } else {
-                            todo!()
+                            for tuple_id in TableCodec::decode_index(&bytes)? {
+                                self.index_values.push_back(IndexValue::Normal(tuple_id));
+                            }
                         }
                     }
                     self.scope_iter = None;
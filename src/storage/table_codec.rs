@@ -1,16 +1,22 @@
-use crate::catalog::{ColumnCatalog, TableMeta};
+use crate::catalog::{ColumnCatalog, Privilege, TableMeta, UserCatalog};
 use crate::errors::DatabaseError;
 use crate::types::index::{Index, IndexId, IndexMeta};
 use crate::types::tuple::{SchemaRef, Tuple, TupleId};
+use crate::types::value::DataValue;
 use crate::types::LogicalType;
 use bytes::Bytes;
 use lazy_static::lazy_static;
+use std::slice;
 
 const BOUND_MIN_TAG: u8 = 0;
 const BOUND_MAX_TAG: u8 = 1;
+const NULL_TAG: u8 = 0;
+const NOT_NULL_TAG: u8 = 1;
 
 lazy_static! {
     static ref ROOT_BYTES: Vec<u8> = b"Root".to_vec();
+    static ref USER_BYTES: Vec<u8> = b"User".to_vec();
+    static ref PRIVILEGE_BYTES: Vec<u8> = b"Privilege".to_vec();
 }
 
 #[derive(Clone)]
@@ -137,6 +143,9 @@ impl TableCodec {
 
     /// Key: {TableName}{TUPLE_TAG}{BOUND_MIN_TAG}{RowID}(Sorted)
     /// Value: Tuple
+    ///
+    /// Composite primary key:
+    /// Key: {TableName}{TUPLE_TAG}{BOUND_MIN_TAG}{DataValue1}{BOUND_MIN_TAG}{DataValue2}{BOUND_MIN_TAG} ..
     pub fn encode_tuple(table_name: &str, tuple: &Tuple) -> Result<(Bytes, Bytes), DatabaseError> {
         let tuple_id = tuple.id.clone().ok_or(DatabaseError::PrimaryKeyNotFound)?;
         let key = Self::encode_tuple_key(table_name, &tuple_id)?;
@@ -150,9 +159,29 @@ impl TableCodec {
     ) -> Result<Vec<u8>, DatabaseError> {
         let mut key_prefix = Self::key_prefix(CodecType::Tuple, table_name);
         key_prefix.push(BOUND_MIN_TAG);
+        Self::encode_tuple_id(tuple_id, &mut key_prefix)?;
+
+        Ok(key_prefix)
+    }
+
+    fn encode_tuple_id(tuple_id: &TupleId, bytes: &mut Vec<u8>) -> Result<(), DatabaseError> {
+        if let DataValue::Tuple(Some(primary_keys)) = tuple_id.as_ref() {
+            for primary_key in primary_keys {
+                Self::check_primary_key_type(primary_key)?;
+                primary_key.memcomparable_encode(bytes)?;
+                bytes.push(BOUND_MIN_TAG);
+            }
+        } else {
+            Self::check_primary_key_type(tuple_id)?;
+            tuple_id.memcomparable_encode(bytes)?;
+        }
+
+        Ok(())
+    }
 
+    fn check_primary_key_type(value: &DataValue) -> Result<(), DatabaseError> {
         if !matches!(
-            tuple_id.logical_type(),
+            value.logical_type(),
             LogicalType::Tinyint
                 | LogicalType::Smallint
                 | LogicalType::Integer
@@ -165,9 +194,20 @@ impl TableCodec {
         ) {
             return Err(DatabaseError::InvalidType);
         }
-        tuple_id.memcomparable_encode(&mut key_prefix)?;
 
-        Ok(key_prefix)
+        Ok(())
+    }
+
+    /// Every value of a composite key is followed by `BOUND_MIN_TAG`,
+    /// so replacing the last tag with `BOUND_MAX_TAG` gives a key that is greater
+    /// than all keys starting with the given prefix and less than the next prefix.
+    ///
+    /// Tips: only for the keys of composite primary keys and indexes
+    pub fn prefix_upper_bound(mut prefix_key: Vec<u8>) -> Vec<u8> {
+        if let Some(tag) = prefix_key.last_mut() {
+            *tag = BOUND_MAX_TAG;
+        }
+        prefix_key
     }
 
     pub fn decode_tuple(
@@ -199,39 +239,53 @@ impl TableCodec {
         Ok(bincode::deserialize(bytes)?)
     }
 
-    /// NonUnique Index:
-    /// Key: {TableName}{INDEX_TAG}{BOUND_MIN_TAG}{IndexID}{BOUND_MIN_TAG}{DataValue1}{DataValue2} ..
+    /// Key: {TableName}{INDEX_TAG}{BOUND_MIN_TAG}{IndexID}{BOUND_MIN_TAG}{NullTag}{DataValue1}{BOUND_MIN_TAG}{NullTag}{DataValue2}{BOUND_MIN_TAG} ..
     /// Value: TupleIDs
     ///
-    /// Unique Index:
-    /// Key: {TableName}{INDEX_TAG}{BOUND_MIN_TAG}{IndexID}{BOUND_MIN_TAG}{DataValue}
-    /// Value: TupleIDs
+    /// Unique Index: TupleIDs only has one TupleID
+    /// NonUnique Index: the TupleID is appended to the Key, so each tuple has its own Key,
+    /// and TupleIDs only has the TupleID too
     ///
-    /// Tips: The unique index has only one ColumnID and one corresponding DataValue,
-    /// so it can be positioned directly.
+    /// Tips: The index is positioned directly by all of its DataValues,
+    /// and the leading DataValues can be used for a prefix range scan.
+    /// NULL only has the `NULL_TAG`, which is less than any other DataValue
     pub fn encode_index(
         name: &str,
         index: &Index,
-        tuple_ids: &[TupleId],
+        tuple_id: &TupleId,
+        is_unique: bool,
     ) -> Result<(Bytes, Bytes), DatabaseError> {
-        let key = TableCodec::encode_index_key(name, index)?;
+        let key = TableCodec::encode_index_key(name, index, (!is_unique).then_some(tuple_id))?;
 
         Ok((
             Bytes::from(key),
-            Bytes::from(bincode::serialize(tuple_ids)?),
+            Bytes::from(bincode::serialize(slice::from_ref(tuple_id))?),
         ))
     }
 
-    pub fn encode_index_key(name: &str, index: &Index) -> Result<Vec<u8>, DatabaseError> {
+    /// The key of the non-unique index ends with the TupleID, without it the key is the prefix of them
+    pub fn encode_index_key(
+        name: &str,
+        index: &Index,
+        tuple_id: Option<&TupleId>,
+    ) -> Result<Vec<u8>, DatabaseError> {
         let mut key_prefix = Self::key_prefix(CodecType::Index, name);
         key_prefix.push(BOUND_MIN_TAG);
         key_prefix.append(&mut index.id.to_be_bytes().to_vec());
         key_prefix.push(BOUND_MIN_TAG);
 
         for col_v in &index.column_values {
-            col_v.memcomparable_encode(&mut key_prefix)?;
+            if col_v.is_null() {
+                key_prefix.push(NULL_TAG);
+            } else {
+                key_prefix.push(NOT_NULL_TAG);
+                col_v.memcomparable_encode(&mut key_prefix)?;
+            }
             key_prefix.push(BOUND_MIN_TAG);
         }
+        if let Some(tuple_id) = tuple_id {
+            Self::encode_tuple_id(tuple_id, &mut key_prefix)?;
+        }
 
         Ok(key_prefix)
     }
@@ -276,6 +330,90 @@ impl TableCodec {
     pub fn decode_root_table(bytes: &[u8]) -> Result<TableMeta, DatabaseError> {
         Ok(bincode::deserialize(bytes)?)
     }
+
+    /// Key: User{BOUND_MIN_TAG}{UserName}
+    /// Value: UserCatalog
+    pub fn encode_user(user: &UserCatalog) -> Result<(Bytes, Bytes), DatabaseError> {
+        let key = Self::encode_user_key(&user.name);
+
+        Ok((Bytes::from(key), Bytes::from(bincode::serialize(user)?)))
+    }
+
+    pub fn encode_user_key(user_name: &str) -> Vec<u8> {
+        let mut key_prefix = USER_BYTES.clone();
+        key_prefix.push(BOUND_MIN_TAG);
+        key_prefix.append(&mut user_name.to_string().into_bytes());
+
+        key_prefix
+    }
+
+    pub fn decode_user(bytes: &[u8]) -> Result<UserCatalog, DatabaseError> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+
+    /// Key: Privilege{BOUND_MIN_TAG}{TableName}{BOUND_MIN_TAG}{UserName}
+    ///
+    /// Value: Vec<Privilege>
+    pub fn encode_privileges(
+        table_name: &str,
+        user_name: &str,
+        privileges: &[Privilege],
+    ) -> Result<(Bytes, Bytes), DatabaseError> {
+        let key = Self::encode_privilege_key(table_name, user_name);
+
+        Ok((
+            Bytes::from(key),
+            Bytes::from(bincode::serialize(privileges)?),
+        ))
+    }
+
+    pub fn encode_privilege_key(table_name: &str, user_name: &str) -> Vec<u8> {
+        let mut key_prefix = PRIVILEGE_BYTES.clone();
+        key_prefix.push(BOUND_MIN_TAG);
+        key_prefix.append(&mut table_name.to_string().into_bytes());
+        key_prefix.push(BOUND_MIN_TAG);
+        key_prefix.append(&mut user_name.to_string().into_bytes());
+
+        key_prefix
+    }
+
+    /// The user name is the part after the table name
+    pub fn decode_privilege_user(key: &[u8]) -> Option<&[u8]> {
+        let table_start = PRIVILEGE_BYTES.len() + 1;
+
+        key.get(table_start..)?
+            .iter()
+            .position(|byte| *byte == BOUND_MIN_TAG)
+            .map(|pos| &key[table_start + pos + 1..])
+    }
+
+    pub fn decode_privileges(bytes: &[u8]) -> Result<Vec<Privilege>, DatabaseError> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+
+    pub fn privilege_bound(table_name: &str) -> (Vec<u8>, Vec<u8>) {
+        let op = |bound_id| {
+            let mut key_prefix = PRIVILEGE_BYTES.clone();
+
+            key_prefix.push(BOUND_MIN_TAG);
+            key_prefix.append(&mut table_name.to_string().into_bytes());
+            key_prefix.push(bound_id);
+            key_prefix
+        };
+
+        (op(BOUND_MIN_TAG), op(BOUND_MAX_TAG))
+    }
+
+    pub fn all_privilege_bound() -> (Vec<u8>, Vec<u8>) {
+        let op = |bound_id| {
+            let mut key_prefix = PRIVILEGE_BYTES.clone();
+
+            key_prefix.push(bound_id);
+            key_prefix
+        };
+
+        (op(BOUND_MIN_TAG), op(BOUND_MAX_TAG))
+    }
 }
 
 #[cfg(test)]
@@ -373,14 +511,96 @@ mod tests {
             id: 0,
             column_values: vec![Arc::new(DataValue::Int32(Some(0)))],
         };
-        let tuple_ids = vec![Arc::new(DataValue::Int32(Some(0)))];
-        let (_, bytes) = TableCodec::encode_index(&table_catalog.name, &index, &tuple_ids)?;
+        let tuple_id = Arc::new(DataValue::Int32(Some(0)));
+        let (_, bytes) = TableCodec::encode_index(&table_catalog.name, &index, &tuple_id, true)?;
+
+        assert_eq!(TableCodec::decode_index(&bytes)?, vec![tuple_id]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_table_codec_non_unique_index() -> Result<(), DatabaseError> {
+        let mut set = BTreeSet::new();
+        let table_name = "T1".to_string();
+        let op = |value: DataValue, tuple_id: i32| {
+            let index = Index {
+                id: 0,
+                column_values: vec![Arc::new(value), Arc::new(DataValue::Int32(Some(0)))],
+            };
+            let tuple_id = Arc::new(DataValue::Int32(Some(tuple_id)));
+
+            TableCodec::encode_index(&table_name, &index, &tuple_id, false).unwrap()
+        };
+
+        set.insert(op(DataValue::Utf8(Some("".to_string())), 0).0);
+        set.insert(op(DataValue::Utf8(None), 1).0);
+        set.insert(op(DataValue::Utf8(None), 2).0);
+        set.insert(op(DataValue::Utf8(Some("a".to_string())), 3).0);
+
+        // NULL is less than any other values and the tuples with the same values have their own keys
+        assert_eq!(
+            set.into_iter().collect_vec(),
+            vec![
+                op(DataValue::Utf8(None), 1).0,
+                op(DataValue::Utf8(None), 2).0,
+                op(DataValue::Utf8(Some("".to_string())), 0).0,
+                op(DataValue::Utf8(Some("a".to_string())), 3).0,
+            ]
+        );
 
-        assert_eq!(TableCodec::decode_index(&bytes)?, tuple_ids);
+        let (_, bytes) = op(DataValue::Utf8(None), 1);
+        assert_eq!(
+            TableCodec::decode_index(&bytes)?,
+            vec![Arc::new(DataValue::Int32(Some(1)))]
+        );
 
         Ok(())
     }
 
+    #[test]
+    fn test_table_codec_composite_tuple_key_bound() {
+        let mut set = BTreeSet::new();
+        let op = |c1: i32, c2: &str| {
+            let tuple_id = Arc::new(DataValue::Tuple(Some(vec![
+                Arc::new(DataValue::Int32(Some(c1))),
+                Arc::new(DataValue::Utf8(Some(c2.to_string()))),
+            ])));
+
+            TableCodec::encode_tuple_key("T0", &tuple_id).unwrap()
+        };
+        let prefix = |c1: i32| {
+            let tuple_id = Arc::new(DataValue::Tuple(Some(vec![Arc::new(DataValue::Int32(
+                Some(c1),
+            ))])));
+
+            TableCodec::encode_tuple_key("T0", &tuple_id).unwrap()
+        };
+
+        set.insert(op(0, "b"));
+        set.insert(op(0, "abcdefghijk"));
+        set.insert(op(1, "a"));
+        set.insert(op(1, "abcdefghijk"));
+        set.insert(op(1, "b"));
+        set.insert(op(2, "a"));
+
+        let min = prefix(1);
+        let max = TableCodec::prefix_upper_bound(prefix(1));
+
+        let vec = set
+            .range::<Vec<u8>, (Bound<&Vec<u8>>, Bound<&Vec<u8>>)>((
+                Bound::Included(&min),
+                Bound::Included(&max),
+            ))
+            .collect_vec();
+
+        assert_eq!(vec.len(), 3);
+
+        assert_eq!(vec[0], &op(1, "a"));
+        assert_eq!(vec[1], &op(1, "abcdefghijk"));
+        assert_eq!(vec[2], &op(1, "b"));
+    }
+
     #[test]
     fn test_table_codec_column() {
         let table_catalog = build_table_codec();
@@ -401,7 +621,7 @@ mod tests {
                 false,
                 ColumnDesc {
                     column_datatype: LogicalType::Invalid,
-                    is_primary: false,
+                    primary: None,
                     is_unique: false,
                     default: None,
                 },
@@ -503,7 +723,7 @@ mod tests {
                 column_values: vec![Arc::new(value)],
             };
 
-            TableCodec::encode_index_key(table_name, &index).unwrap()
+            TableCodec::encode_index_key(table_name, &index, None).unwrap()
         };
 
         set.insert(op(DataValue::Int32(Some(0)), 0, &table_catalog.name));
@@ -557,7 +777,7 @@ mod tests {
                 column_values: vec![Arc::new(value)],
             };
 
-            TableCodec::encode_index_key(&table_name.to_string(), &index).unwrap()
+            TableCodec::encode_index_key(&table_name.to_string(), &index, None).unwrap()
         };
 
         set.insert(op(DataValue::Int32(Some(0)), 0, "T0"));
@@ -649,4 +869,43 @@ mod tests {
         assert_eq!(vec[1], &op("T1"));
         assert_eq!(vec[2], &op("T2"));
     }
+
+    #[test]
+    fn test_privilege_codec_bound() {
+        let mut set = BTreeSet::new();
+        let op = |table_name: &str, user_name: &str| {
+            TableCodec::encode_privilege_key(table_name, user_name)
+        };
+
+        set.insert(op("t", "kip"));
+        set.insert(op("t", "sql"));
+        set.insert(op("t1", "kip"));
+        set.insert(TableCodec::encode_user_key("kip"));
+
+        let (min, max) = TableCodec::privilege_bound("t");
+
+        let vec = set
+            .range::<Vec<u8>, (Bound<&Vec<u8>>, Bound<&Vec<u8>>)>((
+                Bound::Included(&min),
+                Bound::Included(&max),
+            ))
+            .collect_vec();
+
+        assert_eq!(vec, vec![&op("t", "kip"), &op("t", "sql")]);
+
+        let (min, max) = TableCodec::all_privilege_bound();
+
+        let vec = set
+            .range::<Vec<u8>, (Bound<&Vec<u8>>, Bound<&Vec<u8>>)>((
+                Bound::Included(&min),
+                Bound::Included(&max),
+            ))
+            .collect_vec();
+
+        assert_eq!(vec.len(), 3);
+        assert_eq!(
+            TableCodec::decode_privilege_user(&op("t1", "kip")),
+            Some(b"kip".as_slice())
+        );
+    }
 }
@@ -196,6 +196,24 @@ impl LogicalType {
         Err(DatabaseError::Incomparable(*left, *right))
     }
 
+    /// The type which all the types can be cast to, e.g. the type of the results of `CASE`
+    pub fn common_type(types: &[LogicalType]) -> Result<LogicalType, DatabaseError> {
+        let mut common_type = LogicalType::SqlNull;
+
+        for ty in types {
+            common_type = match (&common_type, ty) {
+                (LogicalType::Varchar(_), LogicalType::Varchar(_)) => LogicalType::Varchar(None),
+                (LogicalType::Decimal(_, _), ty) | (ty, LogicalType::Decimal(_, _))
+                    if ty.is_numeric() || matches!(ty, LogicalType::Decimal(_, _)) =>
+                {
+                    LogicalType::Decimal(None, None)
+                }
+                (left, right) => LogicalType::max_logical_type(left, right)?,
+            };
+        }
+        Ok(common_type)
+    }
+
     fn combine_numeric_types(
         left: &LogicalType,
         right: &LogicalType,
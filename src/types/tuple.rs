@@ -36,7 +36,7 @@ impl Tuple {
         let values_len = tuple_schema_ref.len();
         let mut tuple_values = Vec::with_capacity(values_len);
         let bits_len = (values_len + BITS_MAX_INDEX) / BITS_MAX_INDEX;
-        let mut id_option = None;
+        let mut primary_keys = Vec::new();
 
         let mut projection_i = 0;
         let mut pos = bits_len;
@@ -51,7 +51,7 @@ impl Tuple {
                     Self::values_push(
                         tuple_schema_ref,
                         &tuple_values,
-                        &mut id_option,
+                        &mut primary_keys,
                         &mut projection_i,
                     );
                 }
@@ -65,7 +65,7 @@ impl Tuple {
                     Self::values_push(
                         tuple_schema_ref,
                         &tuple_values,
-                        &mut id_option,
+                        &mut primary_keys,
                         &mut projection_i,
                     );
                 }
@@ -82,7 +82,7 @@ impl Tuple {
                     Self::values_push(
                         tuple_schema_ref,
                         &tuple_values,
-                        &mut id_option,
+                        &mut primary_keys,
                         &mut projection_i,
                     );
                 }
@@ -91,20 +91,36 @@ impl Tuple {
         }
 
         Tuple {
-            id: id_option,
+            id: Self::primary_key_id(
+                primary_keys
+                    .into_iter()
+                    .sorted_by_key(|(pos, _)| *pos)
+                    .map(|(_, value)| value)
+                    .collect_vec(),
+            ),
             schema_ref: tuple_schema_ref.clone(),
             values: tuple_values,
         }
     }
 
+    /// A single primary key is used as the TupleId directly,
+    /// and a composite primary key is packed into a `DataValue::Tuple` in the declared order.
+    pub fn primary_key_id(mut primary_keys: Vec<ValueRef>) -> Option<TupleId> {
+        match primary_keys.len() {
+            0 => None,
+            1 => primary_keys.pop(),
+            _ => Some(Arc::new(DataValue::Tuple(Some(primary_keys)))),
+        }
+    }
+
     fn values_push(
         tuple_columns: &Arc<Vec<ColumnRef>>,
         tuple_values: &[ValueRef],
-        id_option: &mut Option<Arc<DataValue>>,
+        primary_keys: &mut Vec<(usize, ValueRef)>,
         projection_i: &mut usize,
     ) {
-        if tuple_columns[*projection_i].desc.is_primary {
-            let _ = id_option.replace(tuple_values[*projection_i].clone());
+        if let Some(pos) = tuple_columns[*projection_i].desc.primary {
+            primary_keys.push((pos, tuple_values[*projection_i].clone()));
         }
         *projection_i += 1;
     }
@@ -1,9 +1,14 @@
-use crate::catalog::ColumnCatalog;
+use crate::catalog::{ColumnCatalog, ColumnDesc};
 use crate::errors::DatabaseError;
 use crate::types::tuple::{SchemaRef, Tuple};
 use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+use itertools::Itertools;
 use std::sync::Arc;
 
+/// The name of the only column in the result of DML
+pub const AFFECTED_ROWS: &str = "affected rows";
+
 pub struct TupleBuilder<'a> {
     schema_ref: &'a SchemaRef,
 }
@@ -24,6 +29,22 @@ impl<'a> TupleBuilder<'a> {
         })
     }
 
+    /// The result of `INSERT`, `UPDATE`, `DELETE` and `COPY FROM`
+    pub fn build_affected_rows(count: usize) -> Result<Tuple, DatabaseError> {
+        let columns = Arc::new(vec![Arc::new(ColumnCatalog::new(
+            AFFECTED_ROWS.to_string(),
+            false,
+            ColumnDesc::new(LogicalType::UBigint, false, false, None),
+        ))]);
+        let values = vec![Arc::new(DataValue::UInt64(Some(count as u64)))];
+
+        Ok(Tuple {
+            id: None,
+            schema_ref: columns,
+            values,
+        })
+    }
+
     pub fn build(
         &self,
         id: Option<ValueRef>,
@@ -45,15 +66,15 @@ impl<'a> TupleBuilder<'a> {
         row: impl IntoIterator<Item = &'b str>,
     ) -> Result<Tuple, DatabaseError> {
         let mut values = Vec::with_capacity(self.schema_ref.len());
-        let mut primary_key = None;
+        let mut primary_keys = Vec::new();
 
         for (i, value) in row.into_iter().enumerate() {
             let data_value = Arc::new(
                 DataValue::Utf8(Some(value.to_string())).cast(self.schema_ref[i].datatype())?,
             );
 
-            if primary_key.is_none() && self.schema_ref[i].desc.is_primary {
-                primary_key = Some(data_value.clone());
+            if let Some(pos) = self.schema_ref[i].desc.primary {
+                primary_keys.push((pos, data_value.clone()));
             }
             values.push(data_value);
         }
@@ -62,7 +83,13 @@ impl<'a> TupleBuilder<'a> {
         }
 
         Ok(Tuple {
-            id: primary_key,
+            id: Tuple::primary_key_id(
+                primary_keys
+                    .into_iter()
+                    .sorted_by_key(|(pos, _)| *pos)
+                    .map(|(_, value)| value)
+                    .collect_vec(),
+            ),
             schema_ref: self.schema_ref.clone(),
             values,
         })
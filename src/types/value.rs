@@ -184,6 +184,7 @@ impl PartialOrd for DataValue {
             (Date64(_), _) => None,
             (Decimal(v1), Decimal(v2)) => v1.partial_cmp(v2),
             (Decimal(_), _) => None,
+            (Tuple(values_1), Tuple(values_2)) => values_1.partial_cmp(values_2),
             (Tuple(_), _) => None,
         }
     }
@@ -501,7 +502,7 @@ impl DataValue {
     fn encode_bytes(b: &mut Vec<u8>, data: &[u8]) {
         let d_len = data.len();
         let realloc_size = (d_len / ENCODE_GROUP_SIZE + 1) * (ENCODE_GROUP_SIZE + 1);
-        Self::realloc_bytes(b, realloc_size);
+        b.reserve(realloc_size);
 
         let mut idx = 0;
         while idx <= d_len {
@@ -522,15 +523,6 @@ impl DataValue {
         }
     }
 
-    fn realloc_bytes(b: &mut Vec<u8>, size: usize) {
-        let len = b.len();
-
-        if size > len {
-            b.reserve(size - len);
-            b.resize(size, 0);
-        }
-    }
-
     pub fn memcomparable_encode(&self, b: &mut Vec<u8>) -> Result<(), DatabaseError> {
         match self {
             DataValue::Int8(Some(v)) => encode_u!(b, *v as u8 ^ 0x80_u8),
@@ -748,6 +740,11 @@ impl DataValue {
                 LogicalType::UBigint => {
                     Ok(DataValue::UInt64(value.map(u64::try_from).transpose()?))
                 }
+                LogicalType::Tinyint => Ok(DataValue::Int8(value.map(i8::try_from).transpose()?)),
+                LogicalType::Smallint => {
+                    Ok(DataValue::Int16(value.map(i16::try_from).transpose()?))
+                }
+                LogicalType::Integer => Ok(DataValue::Int32(value.map(i32::try_from).transpose()?)),
                 LogicalType::Bigint => Ok(DataValue::Int64(value)),
                 LogicalType::Float => Ok(DataValue::Float32(value.map(|v| v as f32))),
                 LogicalType::Double => Ok(DataValue::Float64(value.map(|v| v as f64))),
@@ -894,8 +891,15 @@ impl DataValue {
 
                     Ok(DataValue::Date64(option))
                 }
-                LogicalType::Decimal(_, _) => Ok(DataValue::Decimal(
-                    value.map(|v| Decimal::from_str(&v)).transpose()?,
+                LogicalType::Decimal(_, option) => Ok(DataValue::Decimal(
+                    value
+                        .map(|v| {
+                            let mut decimal = Decimal::from_str(&v)?;
+                            Self::decimal_round_f(option, &mut decimal);
+
+                            Ok::<Decimal, DatabaseError>(decimal)
+                        })
+                        .transpose()?,
                 )),
                 _ => Err(DatabaseError::CastFail),
             },
@@ -932,7 +936,10 @@ impl DataValue {
                 LogicalType::SqlNull => Ok(DataValue::Null),
                 LogicalType::Float => Ok(DataValue::Float32(value.and_then(|v| v.to_f32()))),
                 LogicalType::Double => Ok(DataValue::Float64(value.and_then(|v| v.to_f64()))),
-                LogicalType::Decimal(_, _) => Ok(DataValue::Decimal(value)),
+                LogicalType::Decimal(_, option) => Ok(DataValue::Decimal(value.map(|mut v| {
+                    Self::decimal_round_f(option, &mut v);
+                    v
+                }))),
                 LogicalType::Varchar(len) => varchar_cast!(value, len),
                 _ => Err(DatabaseError::CastFail),
             },
@@ -1146,6 +1153,8 @@ impl fmt::Debug for DataValue {
 mod test {
     use crate::errors::DatabaseError;
     use crate::types::value::DataValue;
+    use crate::types::LogicalType;
+    use rust_decimal::Decimal;
 
     #[test]
     fn test_mem_comparable_int() -> Result<(), DatabaseError> {
@@ -1234,4 +1243,24 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_cast_decimal_with_scale() -> Result<(), DatabaseError> {
+        let decimal_type = LogicalType::Decimal(Some(4), Some(2));
+
+        assert_eq!(
+            DataValue::Decimal(Some(Decimal::new(12345, 3))).cast(&decimal_type)?,
+            DataValue::Decimal(Some(Decimal::new(1235, 2)))
+        );
+        assert_eq!(
+            DataValue::Utf8(Some("1.005".to_string())).cast(&decimal_type)?,
+            DataValue::Decimal(Some(Decimal::new(101, 2)))
+        );
+        assert_eq!(
+            DataValue::Utf8(None).cast(&decimal_type)?,
+            DataValue::Decimal(None)
+        );
+
+        Ok(())
+    }
 }